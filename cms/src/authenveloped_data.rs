@@ -3,6 +3,8 @@
 use der::{asn1::SetOfVec, Sequence};
 use x509_cert::attr::Attribute;
 
+pub use pkcs5::pbes2::GcmParameters;
+
 use crate::{
     authenticated_data::MessageAuthenticationCode,
     content_info::CmsVersion,
@@ -23,7 +25,7 @@ use crate::{
 /// ```
 ///
 /// [RFC 5083 Section 4]: https://www.rfc-editor.org/rfc/rfc5083#section-4
-#[derive(Clone, Debug, Sequence)]
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
 #[allow(missing_docs)]
 pub struct AuthEnvelopedData {
     pub version: CmsVersion,
@@ -5,6 +5,9 @@ pub use x509_cert as x509;
 use core::cmp::Ordering;
 use der::{asn1::ObjectIdentifier, Any, Choice, Sequence, ValueOrd};
 use x509_cert::name::Name;
+
+#[cfg(any(feature = "rsa", feature = "verify"))]
+use x509_cert::ext::pkix::SubjectKeyIdentifier;
 use x509_cert::serial_number::SerialNumber;
 use x509_cert::Certificate;
 
@@ -79,3 +82,47 @@ pub struct IssuerAndSerialNumber {
     pub issuer: Name,
     pub serial_number: SerialNumber,
 }
+
+impl IssuerAndSerialNumber {
+    /// Whether `cert` has this issuer and serial number.
+    pub fn matches(&self, cert: &Certificate) -> bool {
+        let tbs = cert.tbs_certificate();
+        tbs.issuer() == &self.issuer && tbs.serial_number() == &self.serial_number
+    }
+}
+
+/// Reference to a certificate, as made by `SignerIdentifier` and
+/// `RecipientIdentifier`.
+#[cfg(any(feature = "rsa", feature = "verify"))]
+pub(crate) enum CertificateId<'a> {
+    IssuerAndSerialNumber(&'a IssuerAndSerialNumber),
+    SubjectKeyIdentifier(&'a SubjectKeyIdentifier),
+}
+
+#[cfg(any(feature = "rsa", feature = "verify"))]
+impl CertificateId<'_> {
+    /// Whether `cert` is the referenced certificate.
+    pub(crate) fn matches(&self, cert: &Certificate) -> bool {
+        match self {
+            CertificateId::IssuerAndSerialNumber(isn) => isn.matches(cert),
+            CertificateId::SubjectKeyIdentifier(ski) => cert
+                .tbs_certificate()
+                .get_extension::<SubjectKeyIdentifier>()
+                .is_ok_and(|ext| ext.is_some_and(|(_, cert_ski)| &cert_ski == *ski)),
+        }
+    }
+}
+
+#[cfg(any(feature = "rsa", feature = "verify"))]
+impl<'a> From<&'a IssuerAndSerialNumber> for CertificateId<'a> {
+    fn from(isn: &'a IssuerAndSerialNumber) -> Self {
+        CertificateId::IssuerAndSerialNumber(isn)
+    }
+}
+
+#[cfg(any(feature = "rsa", feature = "verify"))]
+impl<'a> From<&'a SubjectKeyIdentifier> for CertificateId<'a> {
+    fn from(ski: &'a SubjectKeyIdentifier) -> Self {
+        CertificateId::SubjectKeyIdentifier(ski)
+    }
+}
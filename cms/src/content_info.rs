@@ -1,15 +1,21 @@
 //! ContentInfo types
 
-use crate::cert::CertificateChoices;
-use crate::revocation::RevocationInfoChoices;
-use crate::signed_data::EncapsulatedContentInfo;
-use crate::signed_data::{CertificateSet, SignedData, SignerInfos};
+use crate::authenticated_data::AuthenticatedData;
+use crate::authenveloped_data::AuthEnvelopedData;
+use crate::digested_data::DigestedData;
+use crate::encrypted_data::EncryptedData;
+use crate::enveloped_data::EnvelopedData;
+use crate::signed_data::SignedData;
+use alloc::{boxed::Box, vec::Vec};
+use const_oid::db::rfc5911;
 use core::cmp::Ordering;
-use der::asn1::SetOfVec;
-use der::Encode;
-use der::{asn1::ObjectIdentifier, Any, AnyRef, Enumerated, Sequence, ValueOrd};
+use der::asn1::OctetString;
+use der::{asn1::ObjectIdentifier, Any, Enumerated, Sequence, Tag, ValueOrd};
 use x509_cert::{Certificate, PkiPath};
 
+#[cfg(feature = "pem")]
+use der::pem::PemLabel;
+
 /// The `OtherCertificateFormat` type is defined in [RFC 5652 Section 10.2.5].
 ///
 /// ```text
@@ -55,34 +61,94 @@ pub struct ContentInfo {
     pub content: Any,
 }
 
+impl ContentInfo {
+    /// Decode the content according to the content type.
+    ///
+    /// Content types which aren't defined in this crate are returned as
+    /// [`Content::Other`].
+    pub fn decode_content(&self) -> der::Result<Content> {
+        Ok(match self.content_type {
+            rfc5911::ID_DATA => Content::Data(self.content.decode_as()?),
+            rfc5911::ID_SIGNED_DATA => Content::SignedData(Box::new(self.content.decode_as()?)),
+            rfc5911::ID_ENVELOPED_DATA => {
+                Content::EnvelopedData(Box::new(self.content.decode_as()?))
+            }
+            rfc5911::ID_DIGESTED_DATA => Content::DigestedData(Box::new(self.content.decode_as()?)),
+            rfc5911::ID_ENCRYPTED_DATA => {
+                Content::EncryptedData(Box::new(self.content.decode_as()?))
+            }
+            rfc5911::ID_CT_AUTH_DATA => {
+                Content::AuthenticatedData(Box::new(self.content.decode_as()?))
+            }
+            rfc5911::ID_CT_AUTH_ENVELOPED_DATA => {
+                Content::AuthEnvelopedData(Box::new(self.content.decode_as()?))
+            }
+            _ => Content::Other(self.content.clone()),
+        })
+    }
+}
+
+/// Content of a [`ContentInfo`], as returned by [`ContentInfo::decode_content`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Content {
+    /// `id-data` content.
+    Data(OctetString),
+
+    /// `id-signedData` content.
+    SignedData(Box<SignedData>),
+
+    /// `id-envelopedData` content.
+    EnvelopedData(Box<EnvelopedData>),
+
+    /// `id-digestedData` content.
+    DigestedData(Box<DigestedData>),
+
+    /// `id-encryptedData` content.
+    EncryptedData(Box<EncryptedData>),
+
+    /// `id-ct-authData` content.
+    AuthenticatedData(Box<AuthenticatedData>),
+
+    /// `id-ct-authEnvelopedData` content.
+    AuthEnvelopedData(Box<AuthEnvelopedData>),
+
+    /// Content of any other type.
+    Other(Any),
+}
+
+impl ContentInfo {
+    /// Extract the certificates from a certs-only `SignedData` message, a.k.a
+    /// `.p7b` file, as built by [`SignedData::certs_only`].
+    ///
+    /// Returns an error if this isn't a `SignedData` message or if it has
+    /// signers. Certificates in other formats are skipped.
+    pub fn try_into_certificates(self) -> der::Result<Vec<Certificate>> {
+        if self.content_type != rfc5911::ID_SIGNED_DATA {
+            return Err(Tag::ObjectIdentifier.value_error());
+        }
+
+        let signed_data = self.content.decode_as::<SignedData>()?;
+
+        if !signed_data.signer_infos.0.is_empty() {
+            return Err(Tag::Set.value_error());
+        }
+
+        Ok(signed_data.x509_certificates().cloned().collect())
+    }
+}
+
+#[cfg(feature = "pem")]
+impl PemLabel for ContentInfo {
+    const PEM_LABEL: &'static str = "PKCS7";
+}
+
 /// Convert a Certificate to a certs-only SignedData message
 impl TryFrom<Certificate> for ContentInfo {
     type Error = der::Error;
 
     fn try_from(cert: Certificate) -> der::Result<Self> {
-        let mut certs = CertificateSet(Default::default());
-        certs.0.insert(CertificateChoices::Certificate(cert))?;
-
-        // include empty CRLs field instead of omitting it to match OpenSSL's behavior
-        let sd = SignedData {
-            version: CmsVersion::V1,
-            digest_algorithms: SetOfVec::default(),
-            encap_content_info: EncapsulatedContentInfo {
-                econtent_type: const_oid::db::rfc5911::ID_DATA,
-                econtent: None,
-            },
-            certificates: Some(certs),
-            crls: Some(RevocationInfoChoices(Default::default())),
-            signer_infos: SignerInfos(Default::default()),
-        };
-
-        let signed_data = sd.to_der()?;
-        let content = AnyRef::try_from(signed_data.as_slice())?;
-
-        Ok(ContentInfo {
-            content_type: const_oid::db::rfc5911::ID_SIGNED_DATA,
-            content: Any::from(content),
-        })
+        SignedData::certs_only([cert])
     }
 }
 
@@ -91,30 +157,6 @@ impl TryFrom<PkiPath> for ContentInfo {
     type Error = der::Error;
 
     fn try_from(pki_path: PkiPath) -> der::Result<Self> {
-        let mut certs = CertificateSet(Default::default());
-        for cert in pki_path {
-            certs.0.insert(CertificateChoices::Certificate(cert))?;
-        }
-
-        // include empty CRLs field instead of omitting it to match OpenSSL's behavior
-        let sd = SignedData {
-            version: CmsVersion::V1,
-            digest_algorithms: SetOfVec::default(),
-            encap_content_info: EncapsulatedContentInfo {
-                econtent_type: const_oid::db::rfc5911::ID_DATA,
-                econtent: None,
-            },
-            certificates: Some(certs),
-            crls: Some(RevocationInfoChoices(Default::default())),
-            signer_infos: SignerInfos(Default::default()),
-        };
-
-        let signed_data = sd.to_der()?;
-        let content = AnyRef::try_from(signed_data.as_slice())?;
-
-        Ok(ContentInfo {
-            content_type: const_oid::db::rfc5911::ID_SIGNED_DATA,
-            content: Any::from(content),
-        })
+        SignedData::certs_only(pki_path)
     }
 }
@@ -0,0 +1,313 @@
+#![cfg(feature = "decrypt")]
+
+//! Content decryption
+//!
+//! Decrypts the content of [`EnvelopedData`], [`EncryptedData`] and
+//! [`AuthEnvelopedData`] messages with a content-encryption key:
+//!
+//! - AES-CBC for `EnvelopedData` and `EncryptedData` ([RFC 3565])
+//! - AES-GCM for `AuthEnvelopedData` ([RFC 5084])
+//!
+//! With the `rsa` feature, the content-encryption key of RSA recipients
+//! (`KeyTransRecipientInfo` with RSAES-PKCS1-v1_5 or RSAES-OAEP) can be
+//! unwrapped with [`RecipientInfos::decrypt_key`].
+//!
+//! [RFC 3565]: https://www.rfc-editor.org/rfc/rfc3565
+//! [RFC 5084]: https://www.rfc-editor.org/rfc/rfc5084
+
+use crate::authenveloped_data::{AuthEnvelopedData, GcmParameters};
+use crate::encrypted_data::EncryptedData;
+use crate::enveloped_data::{EncryptedContentInfo, EnvelopedData};
+use aes::{Aes128, Aes192, Aes256};
+use aes_gcm::aead::consts::{U12, U13, U14, U15, U16};
+use aes_gcm::{AeadInPlace, AesGcm, KeyInit, Nonce, Tag, TagSize};
+use alloc::vec::Vec;
+use cipher::block_padding::Pkcs7;
+use cipher::{BlockCipherDecrypt, BlockCipherEncrypt, BlockModeDecrypt, BlockSizeUser, KeyIvInit};
+use const_oid::db::rfc5911;
+use core::fmt;
+use der::asn1::{ObjectIdentifier, OctetStringRef};
+use der::Encode;
+
+#[cfg(feature = "rsa")]
+use {
+    crate::cert::CertificateId,
+    crate::enveloped_data::{
+        KeyTransRecipientInfo, RecipientIdentifier, RecipientInfo, RecipientInfos,
+    },
+    const_oid::db::rfc5912,
+    der::{Tag as Asn1Tag, Tagged},
+    rsa::{pkcs1::RsaOaepParams, Oaep, Pkcs1v15Encrypt, RsaPrivateKey},
+    x509_cert::Certificate,
+    zeroize::Zeroizing,
+};
+
+/// Error type
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// ASN.1 DER-related errors.
+    Asn1(der::Error),
+
+    /// Content or key encryption algorithm which isn't supported.
+    UnsupportedAlgorithm(ObjectIdentifier),
+
+    /// Algorithm parameters which aren't supported, e.g. an AES-GCM tag length.
+    UnsupportedParameters(ObjectIdentifier),
+
+    /// The encrypted content is absent.
+    Content,
+
+    /// The key has the wrong size for the algorithm.
+    KeySize,
+
+    /// No recipient matches the given certificate.
+    RecipientNotFound,
+
+    /// Decryption failed, e.g. because of a wrong key.
+    Decryption,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Asn1(err) => write!(f, "ASN.1 error: {}", err),
+            Error::UnsupportedAlgorithm(oid) => write!(f, "unsupported algorithm: {}", oid),
+            Error::UnsupportedParameters(oid) => {
+                write!(f, "unsupported parameters for algorithm: {}", oid)
+            }
+            Error::Content => write!(f, "missing encrypted content"),
+            Error::KeySize => write!(f, "invalid key size"),
+            Error::RecipientNotFound => write!(f, "recipient not found"),
+            Error::Decryption => write!(f, "decryption failed"),
+        }
+    }
+}
+
+impl From<der::Error> for Error {
+    fn from(err: der::Error) -> Error {
+        Error::Asn1(err)
+    }
+}
+
+type Result<T> = core::result::Result<T, Error>;
+
+impl EncryptedContentInfo {
+    /// Decrypt the AES-CBC encrypted content with the content-encryption
+    /// `key`.
+    pub fn decrypt(&self, key: &[u8]) -> Result<Vec<u8>> {
+        let ciphertext = self.encrypted_content()?;
+        let iv = self
+            .content_enc_alg
+            .parameters
+            .as_ref()
+            .ok_or(Error::UnsupportedAlgorithm(self.content_enc_alg.oid))?
+            .decode_as::<OctetStringRef<'_>>()?;
+
+        match self.content_enc_alg.oid {
+            rfc5911::ID_AES_128_CBC => cbc_decrypt::<Aes128>(key, iv.as_bytes(), ciphertext),
+            rfc5911::ID_AES_192_CBC => cbc_decrypt::<Aes192>(key, iv.as_bytes(), ciphertext),
+            rfc5911::ID_AES_256_CBC => cbc_decrypt::<Aes256>(key, iv.as_bytes(), ciphertext),
+            oid => Err(Error::UnsupportedAlgorithm(oid)),
+        }
+    }
+
+    fn encrypted_content(&self) -> Result<&[u8]> {
+        self.encrypted_content
+            .as_ref()
+            .map(|content| content.as_bytes())
+            .ok_or(Error::Content)
+    }
+}
+
+impl EnvelopedData {
+    /// Decrypt the content with the content-encryption `key`.
+    ///
+    /// See [`EncryptedContentInfo::decrypt`].
+    pub fn decrypt(&self, key: &[u8]) -> Result<Vec<u8>> {
+        self.encrypted_content.decrypt(key)
+    }
+}
+
+impl EncryptedData {
+    /// Decrypt the content with the content-encryption `key`.
+    ///
+    /// See [`EncryptedContentInfo::decrypt`].
+    pub fn decrypt(&self, key: &[u8]) -> Result<Vec<u8>> {
+        self.enc_content_info.decrypt(key)
+    }
+}
+
+impl AuthEnvelopedData {
+    /// Decrypt and authenticate the AES-GCM encrypted content, along with
+    /// the authenticated attributes, with the content-encryption `key`.
+    pub fn decrypt(&self, key: &[u8]) -> Result<Vec<u8>> {
+        let info = &self.auth_encrypted_content_info;
+        let mut buffer = info.encrypted_content()?.to_vec();
+        let params = info
+            .content_enc_alg
+            .parameters
+            .as_ref()
+            .ok_or(Error::UnsupportedAlgorithm(info.content_enc_alg.oid))?
+            .decode_as::<GcmParameters>()?;
+
+        // RFC 5083 § 2.2: the authenticated attributes are DER encoded with
+        // the SET OF tag.
+        let aad = match &self.auth_attrs {
+            Some(auth_attrs) => auth_attrs.to_der()?,
+            None => Vec::new(),
+        };
+
+        let nonce = Nonce::<U12>::from(params.nonce);
+        let mac = self.mac.as_bytes();
+
+        match info.content_enc_alg.oid {
+            oid @ rfc5911::ID_AES_128_GCM => {
+                gcm_decrypt_dyn::<Aes128>(oid, &params, key, &nonce, &aad, &mut buffer, mac)?
+            }
+            oid @ rfc5911::ID_AES_192_GCM => {
+                gcm_decrypt_dyn::<Aes192>(oid, &params, key, &nonce, &aad, &mut buffer, mac)?
+            }
+            oid @ rfc5911::ID_AES_256_GCM => {
+                gcm_decrypt_dyn::<Aes256>(oid, &params, key, &nonce, &aad, &mut buffer, mac)?
+            }
+            oid => return Err(Error::UnsupportedAlgorithm(oid)),
+        }
+
+        Ok(buffer)
+    }
+}
+
+#[cfg(feature = "rsa")]
+impl RecipientInfos {
+    /// Unwrap the content-encryption key of the `KeyTransRecipientInfo`
+    /// identifying `cert`, with the RSA private key of `cert`.
+    pub fn decrypt_key(
+        &self,
+        cert: &Certificate,
+        private_key: &RsaPrivateKey,
+    ) -> Result<Zeroizing<Vec<u8>>> {
+        self.0
+            .iter()
+            .find_map(|recipient_info| match recipient_info {
+                RecipientInfo::Ktri(ktri) if ktri.rid.matches(cert) => Some(ktri),
+                _ => None,
+            })
+            .ok_or(Error::RecipientNotFound)?
+            .decrypt_key(private_key)
+    }
+}
+
+#[cfg(feature = "rsa")]
+impl KeyTransRecipientInfo {
+    /// Unwrap the content-encryption key with an RSA private key.
+    ///
+    /// Supports RSAES-PKCS1-v1_5 and RSAES-OAEP with MGF1 using the OAEP
+    /// hash and an empty label.
+    pub fn decrypt_key(&self, private_key: &RsaPrivateKey) -> Result<Zeroizing<Vec<u8>>> {
+        let enc_key = self.enc_key.as_bytes();
+        let key = match self.key_enc_alg.oid {
+            rfc5912::RSA_ENCRYPTION => private_key.decrypt(Pkcs1v15Encrypt, enc_key),
+            rfc5912::ID_RSAES_OAEP => {
+                let params = match &self.key_enc_alg.parameters {
+                    Some(params) => params.decode_as::<RsaOaepParams<'_>>()?,
+                    None => RsaOaepParams::default(),
+                };
+
+                let mgf_hash = params.mask_gen.parameters.map(|hash| hash.oid);
+                let empty_label = params.p_source.oid == rfc5912::ID_P_SPECIFIED
+                    && params.p_source.parameters.is_some_and(|label| {
+                        label.tag() == Asn1Tag::OctetString && label.value().is_empty()
+                    });
+                if params.mask_gen.oid != rfc5912::ID_MGF_1
+                    || mgf_hash != Some(params.hash.oid)
+                    || !empty_label
+                {
+                    return Err(Error::UnsupportedAlgorithm(self.key_enc_alg.oid));
+                }
+
+                let padding = match params.hash.oid {
+                    rfc5912::ID_SHA_1 => Oaep::new::<sha1::Sha1>(),
+                    rfc5912::ID_SHA_224 => Oaep::new::<sha2::Sha224>(),
+                    rfc5912::ID_SHA_256 => Oaep::new::<sha2::Sha256>(),
+                    rfc5912::ID_SHA_384 => Oaep::new::<sha2::Sha384>(),
+                    rfc5912::ID_SHA_512 => Oaep::new::<sha2::Sha512>(),
+                    oid => return Err(Error::UnsupportedAlgorithm(oid)),
+                };
+                private_key.decrypt(padding, enc_key)
+            }
+            oid => return Err(Error::UnsupportedAlgorithm(oid)),
+        };
+
+        key.map(Zeroizing::new).map_err(|_| Error::Decryption)
+    }
+}
+
+#[cfg(feature = "rsa")]
+impl RecipientIdentifier {
+    /// Whether `cert` is the certificate identified by this
+    /// `RecipientIdentifier`.
+    pub fn matches(&self, cert: &Certificate) -> bool {
+        match self {
+            RecipientIdentifier::IssuerAndSerialNumber(isn) => CertificateId::from(isn),
+            RecipientIdentifier::SubjectKeyIdentifier(ski) => CertificateId::from(ski),
+        }
+        .matches(cert)
+    }
+}
+
+fn cbc_decrypt<C>(key: &[u8], iv: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>>
+where
+    C: BlockCipherDecrypt + KeyInit,
+{
+    cbc::Decryptor::<C>::new_from_slices(key, iv)
+        .map_err(|_| Error::KeySize)?
+        .decrypt_padded_vec::<Pkcs7>(ciphertext)
+        .map_err(|_| Error::Decryption)
+}
+
+/// Decrypt with AES-GCM, using the tag length given in `params`.
+///
+/// Returns [`Error::UnsupportedParameters`] for tag lengths other than 12 to
+/// 16 bytes.
+fn gcm_decrypt_dyn<C>(
+    oid: ObjectIdentifier,
+    params: &GcmParameters,
+    key: &[u8],
+    nonce: &Nonce<U12>,
+    aad: &[u8],
+    buffer: &mut [u8],
+    mac: &[u8],
+) -> Result<()>
+where
+    C: BlockSizeUser<BlockSize = U16> + BlockCipherEncrypt + KeyInit,
+{
+    match params.icv_len {
+        12 => gcm_decrypt::<C, U12>(key, nonce, aad, buffer, mac),
+        13 => gcm_decrypt::<C, U13>(key, nonce, aad, buffer, mac),
+        14 => gcm_decrypt::<C, U14>(key, nonce, aad, buffer, mac),
+        15 => gcm_decrypt::<C, U15>(key, nonce, aad, buffer, mac),
+        16 => gcm_decrypt::<C, U16>(key, nonce, aad, buffer, mac),
+        _ => Err(Error::UnsupportedParameters(oid)),
+    }
+}
+
+fn gcm_decrypt<C, T>(
+    key: &[u8],
+    nonce: &Nonce<U12>,
+    aad: &[u8],
+    buffer: &mut [u8],
+    mac: &[u8],
+) -> Result<()>
+where
+    C: BlockSizeUser<BlockSize = U16> + BlockCipherEncrypt + KeyInit,
+    T: TagSize,
+{
+    let gcm = AesGcm::<C, U12, T>::new_from_slice(key).map_err(|_| Error::KeySize)?;
+    let tag = Tag::<T>::try_from(mac).map_err(|_| Error::Decryption)?;
+    gcm.decrypt_in_place_detached(nonce, aad, buffer, &tag)
+        .map_err(|_| Error::Decryption)
+}
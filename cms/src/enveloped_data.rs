@@ -5,9 +5,16 @@ use crate::content_info::CmsVersion;
 use crate::revocation::RevocationInfoChoices;
 use crate::signed_data::CertificateSet;
 
+use alloc::vec::Vec;
 use core::cmp::Ordering;
-use der::asn1::{BitString, GeneralizedTime, ObjectIdentifier, OctetString, SetOfVec};
-use der::{Any, Choice, Sequence, ValueOrd};
+use der::asn1::{
+    BitString, ContextSpecificRef, GeneralizedTime, ObjectIdentifier, OctetString, OctetStringRef,
+    SetOfVec,
+};
+use der::{
+    Any, Choice, Decode, DecodeValue, Encode, EncodeValue, Header, Length, Reader, Sequence, Tag,
+    TagMode, TagNumber, ValueOrd, Writer,
+};
 use spki::AlgorithmIdentifierOwned;
 use x509_cert::attr::{Attribute, Attributes};
 use x509_cert::ext::pkix::SubjectKeyIdentifier;
@@ -89,10 +96,10 @@ pub struct RecipientInfos(pub SetOfVec<RecipientInfo>);
 impl_newtype!(RecipientInfos, SetOfVec<RecipientInfo>);
 
 #[cfg(feature = "std")]
-impl TryFrom<std::vec::Vec<RecipientInfo>> for RecipientInfos {
+impl TryFrom<Vec<RecipientInfo>> for RecipientInfos {
     type Error = der::Error;
 
-    fn try_from(vec: std::vec::Vec<RecipientInfo>) -> der::Result<RecipientInfos> {
+    fn try_from(vec: Vec<RecipientInfo>) -> der::Result<RecipientInfos> {
         Ok(RecipientInfos(SetOfVec::try_from(vec)?))
     }
 }
@@ -106,16 +113,96 @@ impl TryFrom<std::vec::Vec<RecipientInfo>> for RecipientInfos {
 ///       encryptedContent [0] IMPLICIT EncryptedContent OPTIONAL }
 /// ```
 ///
+/// A constructed `encryptedContent`, as produced by streaming BER encoders
+/// and kept by [`der::Document::from_ber`], is decoded by concatenating its
+/// segments. It is always encoded as a primitive OCTET STRING.
+///
 /// [RFC 5652 Section 6.1]: https://www.rfc-editor.org/rfc/rfc5652#section-6.1
-#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[allow(missing_docs)]
 pub struct EncryptedContentInfo {
     pub content_type: ObjectIdentifier,
     pub content_enc_alg: AlgorithmIdentifierOwned,
-    #[asn1(context_specific = "0", tag_mode = "IMPLICIT", optional = "true")]
     pub encrypted_content: Option<OctetString>,
 }
 
+impl EncryptedContentInfo {
+    fn context_specific_encrypted_content(&self) -> Option<ContextSpecificRef<'_, OctetString>> {
+        self.encrypted_content
+            .as_ref()
+            .map(|value| ContextSpecificRef {
+                tag_number: TagNumber::N0,
+                tag_mode: TagMode::Implicit,
+                value,
+            })
+    }
+}
+
+impl<'a> DecodeValue<'a> for EncryptedContentInfo {
+    type Error = der::Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> der::Result<Self> {
+        reader.read_nested(header.length, |reader| {
+            let content_type = reader.decode()?;
+            let content_enc_alg = reader.decode()?;
+            if reader.is_finished() {
+                return Ok(Self {
+                    content_type,
+                    content_enc_alg,
+                    encrypted_content: None,
+                });
+            }
+
+            let header = Header::decode(reader)?;
+            let encrypted_content = match header.tag {
+                Tag::ContextSpecific {
+                    constructed: false,
+                    number: TagNumber::N0,
+                } => OctetString::decode_value(reader, header)?,
+                Tag::ContextSpecific {
+                    constructed: true,
+                    number: TagNumber::N0,
+                } => reader.read_nested(header.length, |reader| {
+                    let mut bytes = Vec::new();
+                    while !reader.is_finished() {
+                        bytes.extend_from_slice(OctetStringRef::decode(reader)?.as_bytes());
+                    }
+                    OctetString::new(bytes)
+                })?,
+                tag => {
+                    return Err(tag.unexpected_error(Some(Tag::ContextSpecific {
+                        constructed: false,
+                        number: TagNumber::N0,
+                    })))
+                }
+            };
+
+            Ok(Self {
+                content_type,
+                content_enc_alg,
+                encrypted_content: Some(encrypted_content),
+            })
+        })
+    }
+}
+
+impl EncodeValue for EncryptedContentInfo {
+    fn value_len(&self) -> der::Result<Length> {
+        self.content_type.encoded_len()?
+            + self.content_enc_alg.encoded_len()?
+            + self.context_specific_encrypted_content().encoded_len()?
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
+        self.content_type.encode(writer)?;
+        self.content_enc_alg.encode(writer)?;
+        self.context_specific_encrypted_content().encode(writer)?;
+        Ok(())
+    }
+}
+
+impl Sequence<'_> for EncryptedContentInfo {}
+
 /// The `RecipientInfo` type is defined in [RFC 5652 Section 6.2].
 ///
 /// ```text
@@ -268,7 +355,7 @@ pub struct OriginatorPublicKey {
 /// ```
 ///
 /// [RFC 5652 Section 6.2.2]: https://www.rfc-editor.org/rfc/rfc5652#section-6.2.2
-pub type RecipientEncryptedKeys = alloc::vec::Vec<RecipientEncryptedKey>;
+pub type RecipientEncryptedKeys = Vec<RecipientEncryptedKey>;
 
 /// The `RecipientEncryptedKey` type is defined in [RFC 5652 Section 6.2.2].
 ///
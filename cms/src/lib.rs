@@ -15,12 +15,36 @@
 
 //! # `p7b` support
 //!
-//! This crate can be used to convert an X.509 certificate into a certs-only
+//! This crate can be used to convert X.509 certificates into a certs-only
 //! [`signed_data::SignedData`] message, a.k.a `.p7b` file.
 //!
-//! Use a [`TryFrom`] conversion between [`cert::x509::Certificate`] and
-//! [`content_info::ContentInfo`] to generate the data structures, then use
-//! `to_der` to serialize it.
+//! Use [`signed_data::SignedData::certs_only`], or a [`TryFrom`] conversion
+//! between [`cert::x509::Certificate`] and [`content_info::ContentInfo`], to
+//! generate the data structures, then use `to_der` to serialize it. With the
+//! `pem` feature, `to_pem` produces a `-----BEGIN PKCS7-----` document.
+//!
+//! [`content_info::ContentInfo::try_into_certificates`] extracts the
+//! certificates back from such a message.
+//!
+//! # BER-encoded messages
+//!
+//! Messages produced by streaming encoders, e.g. `openssl cms -stream`, use
+//! BER indefinite lengths and constructed `OCTET STRING`s. Convert them with
+//! [`der::Document::from_ber`] before decoding a [`content_info::ContentInfo`],
+//! whose content can then be decoded with
+//! [`content_info::ContentInfo::decode_content`].
+//!
+//! # Signature verification
+//!
+//! With the `verify` feature, [`signed_data::SignedData::verify`] checks the
+//! signatures of a message against the signer certificates it contains.
+//!
+//! # Decryption
+//!
+//! With the `decrypt` feature, the content of `EnvelopedData`,
+//! `EncryptedData` and `AuthEnvelopedData` messages can be decrypted with
+//! their content-encryption key, see [`decrypt`]. The `rsa` feature adds
+//! unwrapping of the key for RSA key transport recipients.
 
 extern crate alloc;
 
@@ -34,6 +58,7 @@ pub mod builder;
 pub mod cert;
 pub mod compressed_data;
 pub mod content_info;
+pub mod decrypt;
 pub mod digested_data;
 pub mod encrypted_data;
 pub mod enveloped_data;
@@ -41,3 +66,4 @@ pub mod kemri;
 pub mod revocation;
 pub mod signed_data;
 pub mod timestamped_data;
+pub mod verify;
@@ -1,9 +1,10 @@
 //! SignedData-related types
 
 use crate::cert::{CertificateChoices, IssuerAndSerialNumber};
-use crate::content_info::CmsVersion;
+use crate::content_info::{CmsVersion, ContentInfo};
 use crate::revocation::RevocationInfoChoices;
 
+use const_oid::db::rfc5911;
 use core::cmp::Ordering;
 use der::asn1::{ObjectIdentifier, OctetString, SetOfVec};
 use der::{Any, Choice, DerOrd, Sequence, ValueOrd};
@@ -11,6 +12,7 @@ use spki::AlgorithmIdentifierOwned;
 use x509_cert::attr::Attributes;
 use x509_cert::ext::pkix::SubjectKeyIdentifier;
 use x509_cert::impl_newtype;
+use x509_cert::Certificate;
 
 /// The `SignedData` type is defined in [RFC 5652 Section 5.1].
 ///
@@ -39,6 +41,50 @@ pub struct SignedData {
     pub signer_infos: SignerInfos,
 }
 
+impl SignedData {
+    /// Build a degenerate certs-only `SignedData` message, a.k.a `.p7b` file,
+    /// wrapped in a [`ContentInfo`].
+    ///
+    /// The message has no content, digest algorithms or signers, and an
+    /// empty `crls` field to match OpenSSL's output.
+    pub fn certs_only(certs: impl IntoIterator<Item = Certificate>) -> der::Result<ContentInfo> {
+        let mut cert_set = CertificateSet(Default::default());
+        for cert in certs {
+            cert_set.0.insert(CertificateChoices::Certificate(cert))?;
+        }
+
+        let signed_data = SignedData {
+            version: CmsVersion::V1,
+            digest_algorithms: SetOfVec::default(),
+            encap_content_info: EncapsulatedContentInfo {
+                econtent_type: rfc5911::ID_DATA,
+                econtent: None,
+            },
+            certificates: Some(cert_set),
+            crls: Some(RevocationInfoChoices(Default::default())),
+            signer_infos: SignerInfos(Default::default()),
+        };
+
+        Ok(ContentInfo {
+            content_type: rfc5911::ID_SIGNED_DATA,
+            content: Any::encode_from(&signed_data)?,
+        })
+    }
+
+    /// Iterate over the X.509 certificates in the `certificates` field.
+    ///
+    /// Other certificate formats are skipped.
+    pub fn x509_certificates(&self) -> impl Iterator<Item = &Certificate> {
+        self.certificates
+            .iter()
+            .flat_map(|certs| certs.0.iter())
+            .filter_map(|choice| match choice {
+                CertificateChoices::Certificate(cert) => Some(cert),
+                CertificateChoices::Other(_) => None,
+            })
+    }
+}
+
 /// The `DigestAlgorithmIdentifiers` type is defined in [RFC 5652 Section 5.1].
 ///
 /// ```text
@@ -0,0 +1,403 @@
+#![cfg(feature = "verify")]
+
+//! SignedData signature verification
+//!
+//! Verifies the signatures of the [`SignerInfo`]s of a [`SignedData`] message
+//! as described in [RFC 5652 Section 5.6]. Only the signatures are checked:
+//! validating the signer certificate chain is left to an X.509 path
+//! validator.
+//!
+//! Supported signature algorithms are RSASSA-PKCS1-v1_5, RSASSA-PSS with
+//! MGF1 and ECDSA over P-256, using SHA-1, SHA-2 or SHA-3 digests. Other
+//! algorithms, including ECDSA over P-384 and Ed25519, are reported as
+//! [`Error::UnsupportedAlgorithm`].
+//!
+//! [RFC 5652 Section 5.6]: https://datatracker.ietf.org/doc/html/rfc5652#section-5.6
+
+use crate::attr::MessageDigest;
+use crate::cert::CertificateId;
+use crate::signed_data::{SignedAttributes, SignedData, SignerIdentifier, SignerInfo};
+use alloc::vec::Vec;
+use const_oid::db::{fips202, rfc5911, rfc5912, rfc8410};
+use core::fmt;
+use der::asn1::ObjectIdentifier;
+use der::{Any, Decode, Encode};
+use p256::ecdsa::signature::hazmat::PrehashVerifier as _;
+use rsa::pkcs1::{DecodeRsaPublicKey, RsaPssParams};
+use rsa::{Pkcs1v15Sign, Pss, RsaPublicKey};
+use sha2::Digest;
+use spki::{AlgorithmIdentifierOwned, SubjectPublicKeyInfoOwned};
+use x509_cert::Certificate;
+
+/// Error type
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// ASN.1 DER-related errors.
+    Asn1(der::Error),
+
+    /// Public key errors propagated from the [`spki::Error`] type.
+    PublicKey(spki::Error),
+
+    /// Digest or signature algorithm which isn't supported.
+    UnsupportedAlgorithm(ObjectIdentifier),
+
+    /// Neither encapsulated nor detached content, or both, were provided.
+    Content,
+
+    /// A signed attribute is missing or has more than one value.
+    SignedAttribute(ObjectIdentifier),
+
+    /// The digest algorithm isn't the one of the signature algorithm, e.g.
+    /// SHA-256 with `ecdsa-with-SHA384`.
+    DigestAlgorithmMismatch,
+
+    /// The `contentType` signed attribute doesn't match `eContentType`.
+    ContentTypeMismatch,
+
+    /// The `messageDigest` signed attribute doesn't match the content.
+    MessageDigestMismatch,
+
+    /// No `SignerInfo` at the requested index.
+    SignerNotFound,
+
+    /// The signer certificate isn't part of the message.
+    CertificateNotFound,
+
+    /// The signature is invalid.
+    Signature,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Asn1(err) => write!(f, "ASN.1 error: {}", err),
+            Error::PublicKey(err) => write!(f, "public key error: {}", err),
+            Error::UnsupportedAlgorithm(oid) => write!(f, "unsupported algorithm: {}", oid),
+            Error::Content => write!(f, "expected either encapsulated or detached content"),
+            Error::SignedAttribute(oid) => write!(f, "invalid signed attribute: {}", oid),
+            Error::DigestAlgorithmMismatch => write!(f, "digest algorithm mismatch"),
+            Error::ContentTypeMismatch => write!(f, "content type mismatch"),
+            Error::MessageDigestMismatch => write!(f, "message digest mismatch"),
+            Error::SignerNotFound => write!(f, "signer not found"),
+            Error::CertificateNotFound => write!(f, "signer certificate not found"),
+            Error::Signature => write!(f, "signature verification failed"),
+        }
+    }
+}
+
+impl From<der::Error> for Error {
+    fn from(err: der::Error) -> Error {
+        Error::Asn1(err)
+    }
+}
+
+impl From<spki::Error> for Error {
+    fn from(err: spki::Error) -> Error {
+        Error::PublicKey(err)
+    }
+}
+
+type Result<T> = core::result::Result<T, Error>;
+
+/// A [`SignerInfo`] whose signature was successfully verified.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifiedSigner<'a> {
+    /// Index of the signer in `signerInfos`.
+    pub index: usize,
+
+    /// The verified signer.
+    pub signer_info: &'a SignerInfo,
+
+    /// The certificate of the signer, found in `certificates`.
+    pub certificate: &'a Certificate,
+}
+
+impl SignedData {
+    /// Verify the signatures of all the signers of this message.
+    ///
+    /// `detached_content` must be given if, and only if, the message has no
+    /// encapsulated content. The signer certificates must be included in the
+    /// message.
+    pub fn verify(&self, detached_content: Option<&[u8]>) -> Result<Vec<VerifiedSigner<'_>>> {
+        if self.signer_infos.0.is_empty() {
+            return Err(Error::SignerNotFound);
+        }
+
+        (0..self.signer_infos.0.len())
+            .map(|index| self.verify_signer(index, detached_content))
+            .collect()
+    }
+
+    /// Verify the signature of the signer at `index` in `signerInfos`.
+    ///
+    /// See [`SignedData::verify`].
+    pub fn verify_signer(
+        &self,
+        index: usize,
+        detached_content: Option<&[u8]>,
+    ) -> Result<VerifiedSigner<'_>> {
+        let signer_info = self
+            .signer_infos
+            .0
+            .get(index)
+            .ok_or(Error::SignerNotFound)?;
+        let certificate = self
+            .x509_certificates()
+            .find(|cert| signer_info.sid.matches(cert))
+            .ok_or(Error::CertificateNotFound)?;
+
+        let content = match (&self.encap_content_info.econtent, detached_content) {
+            (Some(econtent), None) => econtent.value(),
+            (None, Some(content)) => content,
+            _ => return Err(Error::Content),
+        };
+
+        signer_info.verify(
+            self.encap_content_info.econtent_type,
+            content,
+            certificate.tbs_certificate().subject_public_key_info(),
+        )?;
+
+        Ok(VerifiedSigner {
+            index,
+            signer_info,
+            certificate,
+        })
+    }
+}
+
+impl SignerInfo {
+    /// Verify this signer's signature over `content` with `public_key`.
+    ///
+    /// `content` is the value of the `eContent` OCTET STRING, or the detached
+    /// content, whose type is `econtent_type`.
+    ///
+    /// When signed attributes are present, their `contentType` and
+    /// `messageDigest` are checked against the content, and the signature
+    /// covers the DER encoding of the attributes with an explicit SET OF tag.
+    /// Otherwise, the signature covers the content itself.
+    ///
+    /// Returns [`Error::DigestAlgorithmMismatch`] if the signature algorithm
+    /// specifies a different digest algorithm than `digestAlgorithm`.
+    pub fn verify(
+        &self,
+        econtent_type: ObjectIdentifier,
+        content: &[u8],
+        public_key: &SubjectPublicKeyInfoOwned,
+    ) -> Result<()> {
+        check_digest_algorithm(&self.signature_algorithm, &self.digest_alg)?;
+
+        let signed_attrs_der;
+        let message = match &self.signed_attrs {
+            None => content,
+            Some(signed_attrs) => {
+                let content_digest = digest(&self.digest_alg, content)?;
+
+                let content_type: ObjectIdentifier =
+                    signed_attribute(signed_attrs, rfc5911::ID_CONTENT_TYPE)?.decode_as()?;
+                if content_type != econtent_type {
+                    return Err(Error::ContentTypeMismatch);
+                }
+
+                let message_digest: MessageDigest =
+                    signed_attribute(signed_attrs, rfc5911::ID_MESSAGE_DIGEST)?.decode_as()?;
+                if message_digest.as_bytes() != content_digest.as_slice() {
+                    return Err(Error::MessageDigestMismatch);
+                }
+
+                // RFC 5652 § 5.4: the `[0] IMPLICIT` tag of `signedAttrs` is
+                // replaced by the SET OF tag, which `Attributes` encodes.
+                signed_attrs_der = signed_attrs.to_der()?;
+                &signed_attrs_der
+            }
+        };
+
+        verify_prehash(
+            public_key,
+            &self.signature_algorithm,
+            &self.digest_alg,
+            &digest(&self.digest_alg, message)?,
+            self.signature.as_bytes(),
+        )
+    }
+}
+
+impl SignerIdentifier {
+    /// Whether `cert` is the certificate identified by this `SignerIdentifier`.
+    pub fn matches(&self, cert: &Certificate) -> bool {
+        match self {
+            SignerIdentifier::IssuerAndSerialNumber(isn) => CertificateId::from(isn),
+            SignerIdentifier::SubjectKeyIdentifier(ski) => CertificateId::from(ski),
+        }
+        .matches(cert)
+    }
+}
+
+/// Get the value of the single-valued signed attribute `oid`.
+fn signed_attribute(signed_attrs: &SignedAttributes, oid: ObjectIdentifier) -> Result<&Any> {
+    let mut attrs = signed_attrs.iter().filter(|attr| attr.oid == oid);
+    match (attrs.next(), attrs.next()) {
+        (Some(attr), None) if attr.values.len() == 1 => {
+            attr.values.get(0).ok_or(Error::SignedAttribute(oid))
+        }
+        _ => Err(Error::SignedAttribute(oid)),
+    }
+}
+
+/// Run `$body` with the digest type `$digest` identified by `$oid`.
+macro_rules! with_digest {
+    ($oid:expr, $digest:ident => $body:expr) => {
+        match $oid {
+            rfc5912::ID_SHA_1 => {
+                type $digest = sha1::Sha1;
+                $body
+            }
+            rfc5912::ID_SHA_224 => {
+                type $digest = sha2::Sha224;
+                $body
+            }
+            rfc5912::ID_SHA_256 => {
+                type $digest = sha2::Sha256;
+                $body
+            }
+            rfc5912::ID_SHA_384 => {
+                type $digest = sha2::Sha384;
+                $body
+            }
+            rfc5912::ID_SHA_512 => {
+                type $digest = sha2::Sha512;
+                $body
+            }
+            fips202::ID_SHA_3_224 => {
+                type $digest = sha3::Sha3_224;
+                $body
+            }
+            fips202::ID_SHA_3_256 => {
+                type $digest = sha3::Sha3_256;
+                $body
+            }
+            fips202::ID_SHA_3_384 => {
+                type $digest = sha3::Sha3_384;
+                $body
+            }
+            fips202::ID_SHA_3_512 => {
+                type $digest = sha3::Sha3_512;
+                $body
+            }
+            oid => Err(Error::UnsupportedAlgorithm(oid)),
+        }
+    };
+}
+
+/// Check that `digest_alg` is the digest algorithm of `signature_alg`, for
+/// signature algorithms which specify one.
+fn check_digest_algorithm(
+    signature_alg: &AlgorithmIdentifierOwned,
+    digest_alg: &AlgorithmIdentifierOwned,
+) -> Result<()> {
+    let expected = match signature_alg.oid {
+        rfc5912::SHA_1_WITH_RSA_ENCRYPTION => rfc5912::ID_SHA_1,
+        rfc5912::SHA_224_WITH_RSA_ENCRYPTION | rfc5912::ECDSA_WITH_SHA_224 => rfc5912::ID_SHA_224,
+        rfc5912::SHA_256_WITH_RSA_ENCRYPTION | rfc5912::ECDSA_WITH_SHA_256 => rfc5912::ID_SHA_256,
+        rfc5912::SHA_384_WITH_RSA_ENCRYPTION | rfc5912::ECDSA_WITH_SHA_384 => rfc5912::ID_SHA_384,
+        rfc5912::SHA_512_WITH_RSA_ENCRYPTION | rfc5912::ECDSA_WITH_SHA_512 => rfc5912::ID_SHA_512,
+        // RFC 8419 § 3.1: SHA-512 MUST be used with Ed25519
+        rfc8410::ID_ED_25519 => rfc5912::ID_SHA_512,
+        _ => return Ok(()),
+    };
+
+    if digest_alg.oid != expected {
+        return Err(Error::DigestAlgorithmMismatch);
+    }
+
+    Ok(())
+}
+
+fn digest(digest_alg: &AlgorithmIdentifierOwned, data: &[u8]) -> Result<Vec<u8>> {
+    with_digest!(digest_alg.oid, D => Ok(D::digest(data).to_vec()))
+}
+
+/// Verify `signature` over the digest `hashed`, computed with `digest_alg`.
+fn verify_prehash(
+    public_key: &SubjectPublicKeyInfoOwned,
+    signature_alg: &AlgorithmIdentifierOwned,
+    digest_alg: &AlgorithmIdentifierOwned,
+    hashed: &[u8],
+    signature: &[u8],
+) -> Result<()> {
+    match signature_alg.oid {
+        rfc5912::RSA_ENCRYPTION
+        | rfc5912::SHA_1_WITH_RSA_ENCRYPTION
+        | rfc5912::SHA_224_WITH_RSA_ENCRYPTION
+        | rfc5912::SHA_256_WITH_RSA_ENCRYPTION
+        | rfc5912::SHA_384_WITH_RSA_ENCRYPTION
+        | rfc5912::SHA_512_WITH_RSA_ENCRYPTION => {
+            let scheme = with_digest!(digest_alg.oid, D => Ok(Pkcs1v15Sign::new::<D>()))?;
+            rsa_public_key(public_key)?
+                .verify(scheme, hashed, signature)
+                .map_err(|_| Error::Signature)
+        }
+        rfc5912::ID_RSASSA_PSS => {
+            let params = signature_alg
+                .parameters
+                .as_ref()
+                .ok_or(spki::Error::AlgorithmParametersMissing)?
+                .to_der()?;
+            let params = RsaPssParams::from_der(&params)?;
+
+            // Only MGF1 with the message digest is supported
+            let mgf_digest = params.mask_gen.parameters.map(|digest| digest.oid);
+            if params.hash.oid != digest_alg.oid
+                || params.mask_gen.oid != rfc5912::ID_MGF_1
+                || mgf_digest != Some(digest_alg.oid)
+            {
+                return Err(Error::UnsupportedAlgorithm(signature_alg.oid));
+            }
+
+            let salt_len = params.salt_len.into();
+            let scheme = with_digest!(digest_alg.oid, D => Ok(Pss::new_with_salt::<D>(salt_len)))?;
+            rsa_public_key(public_key)?
+                .verify(scheme, hashed, signature)
+                .map_err(|_| Error::Signature)
+        }
+        rfc5912::ID_EC_PUBLIC_KEY
+        | rfc5912::ECDSA_WITH_SHA_224
+        | rfc5912::ECDSA_WITH_SHA_256
+        | rfc5912::ECDSA_WITH_SHA_384
+        | rfc5912::ECDSA_WITH_SHA_512 => {
+            public_key
+                .algorithm
+                .assert_algorithm_oid(rfc5912::ID_EC_PUBLIC_KEY)?;
+            let sec1_bytes = public_key.subject_public_key.raw_bytes();
+
+            match public_key.algorithm.parameters_oid()? {
+                rfc5912::SECP_256_R_1 => {
+                    let verifying_key = p256::ecdsa::VerifyingKey::from_sec1_bytes(sec1_bytes)
+                        .map_err(|_| spki::Error::KeyMalformed)?;
+                    let signature = p256::ecdsa::Signature::from_der(signature)
+                        .map_err(|_| Error::Signature)?;
+                    verifying_key
+                        .verify_prehash(hashed, &signature)
+                        .map_err(|_| Error::Signature)
+                }
+                curve => Err(Error::UnsupportedAlgorithm(curve)),
+            }
+        }
+        oid => Err(Error::UnsupportedAlgorithm(oid)),
+    }
+}
+
+/// Decode an `rsaEncryption` or `id-RSASSA-PSS` public key.
+fn rsa_public_key(public_key: &SubjectPublicKeyInfoOwned) -> Result<RsaPublicKey> {
+    let oid = public_key.algorithm.oid;
+    if oid != rfc5912::RSA_ENCRYPTION && oid != rfc5912::ID_RSASSA_PSS {
+        return Err(spki::Error::OidUnknown { oid }.into());
+    }
+
+    RsaPublicKey::from_pkcs1_der(public_key.subject_public_key.raw_bytes())
+        .map_err(|_| spki::Error::KeyMalformed.into())
+}
@@ -0,0 +1,173 @@
+//! Content decryption tests
+
+#![cfg(feature = "decrypt")]
+
+use cms::content_info::{Content, ContentInfo};
+use cms::decrypt::Error;
+use der::{Decode, Document, Encode};
+use hex_literal::hex;
+
+#[cfg(feature = "rsa")]
+use {
+    cms::authenveloped_data::GcmParameters,
+    der::DecodePem,
+    rsa::{pkcs1::DecodeRsaPrivateKey, RsaPrivateKey},
+    x509_cert::Certificate,
+};
+
+const DATA: &[u8] = include_bytes!("examples/data.txt");
+
+fn content(der: &[u8]) -> Content {
+    ContentInfo::from_der(der)
+        .unwrap()
+        .decode_content()
+        .unwrap()
+}
+
+#[cfg(feature = "rsa")]
+fn recipient() -> (Certificate, RsaPrivateKey) {
+    let cert = Certificate::from_pem(include_bytes!("examples/cert.pem")).unwrap();
+    let key = RsaPrivateKey::from_pkcs1_pem(include_str!("examples/rsa_sk.pkcs1.pem")).unwrap();
+    (cert, key)
+}
+
+#[test]
+fn decrypt_encrypted_data_aes192_cbc() {
+    // see examples_notes.txt
+    let Content::EncryptedData(ed) = content(include_bytes!("examples/encd_aes192_cbc.der")) else {
+        panic!("expected EncryptedData");
+    };
+
+    let key = hex!("000102030405060708090a0b0c0d0e0f1011121314151617");
+    assert_eq!(ed.decrypt(&key).unwrap(), DATA);
+    assert_eq!(ed.decrypt(&key[..16]), Err(Error::KeySize));
+
+    let mut wrong_key = key;
+    wrong_key[0] ^= 1;
+    assert_ne!(ed.decrypt(&wrong_key).ok().as_deref(), Some(DATA));
+}
+
+#[cfg(feature = "rsa")]
+#[test]
+fn decrypt_enveloped_data_rsa_aes256_cbc() {
+    let Content::EnvelopedData(ed) = content(include_bytes!("examples/ed_rsa_aes256_cbc.der"))
+    else {
+        panic!("expected EnvelopedData");
+    };
+
+    let (cert, private_key) = recipient();
+    let key = ed.recip_infos.decrypt_key(&cert, &private_key).unwrap();
+    assert_eq!(key.len(), 32);
+    assert_eq!(ed.decrypt(&key).unwrap(), DATA);
+
+    let other_cert = Certificate::from_pem(include_bytes!("examples/p256-cert.pem")).unwrap();
+    assert_eq!(
+        ed.recip_infos.decrypt_key(&other_cert, &private_key),
+        Err(Error::RecipientNotFound)
+    );
+}
+
+#[cfg(feature = "rsa")]
+#[test]
+fn decrypt_enveloped_data_rsa_oaep_aes128_cbc() {
+    let Content::EnvelopedData(ed) = content(include_bytes!("examples/ed_rsa_oaep_aes128_cbc.der"))
+    else {
+        panic!("expected EnvelopedData");
+    };
+
+    let (cert, private_key) = recipient();
+    let key = ed.recip_infos.decrypt_key(&cert, &private_key).unwrap();
+    assert_eq!(key.len(), 16);
+    assert_eq!(ed.decrypt(&key).unwrap(), DATA);
+}
+
+#[cfg(feature = "rsa")]
+#[test]
+fn decrypt_enveloped_data_ber() {
+    let ber = include_bytes!("examples/ed_rsa_aes256_cbc_ber.der");
+    assert!(ContentInfo::from_der(ber).is_err());
+
+    // the constructed `encryptedContent` is re-encoded as a primitive one
+    let doc = Document::from_ber(ber).unwrap();
+    let ci = ContentInfo::from_der(doc.as_bytes()).unwrap();
+    assert_eq!(ci.to_der().unwrap(), doc.as_bytes());
+
+    let Content::EnvelopedData(ed) = ci.decode_content().unwrap() else {
+        panic!("expected EnvelopedData");
+    };
+    assert_eq!(
+        ed.encrypted_content
+            .encrypted_content
+            .as_ref()
+            .unwrap()
+            .as_bytes()
+            .len(),
+        448
+    );
+
+    let (cert, private_key) = recipient();
+    let key = ed.recip_infos.decrypt_key(&cert, &private_key).unwrap();
+    assert_eq!(ed.decrypt(&key).unwrap(), DATA);
+}
+
+#[cfg(feature = "rsa")]
+#[test]
+fn decrypt_auth_enveloped_data_rsa_aes256_gcm() {
+    let Content::AuthEnvelopedData(mut aed) =
+        content(include_bytes!("examples/aed_rsa_aes256_gcm.der"))
+    else {
+        panic!("expected AuthEnvelopedData");
+    };
+
+    let (cert, private_key) = recipient();
+    let key = aed.recip_infos.decrypt_key(&cert, &private_key).unwrap();
+    assert_eq!(aed.decrypt(&key).unwrap(), DATA);
+
+    let mut mac = aed.mac.as_bytes().to_vec();
+    mac[0] ^= 1;
+    aed.mac = der::asn1::OctetString::new(mac).unwrap();
+    assert_eq!(aed.decrypt(&key), Err(Error::Decryption));
+
+    // `aes-ICVlen` is restricted to 12 to 16 bytes
+    let alg = &mut aed.auth_encrypted_content_info.content_enc_alg;
+    let mut params: GcmParameters = alg.parameters.as_ref().unwrap().decode_as().unwrap();
+    params.icv_len = 8;
+    alg.parameters = Some(der::Any::encode_from(&params).unwrap());
+    assert!(matches!(aed.decrypt(&key), Err(Error::Asn1(_))));
+}
+
+#[cfg(all(feature = "builder", feature = "rsa"))]
+#[test]
+fn decrypt_built_enveloped_data() {
+    use cms::builder::{
+        ContentEncryptionAlgorithm, EnvelopedDataBuilder, KeyEncryptionInfo,
+        KeyTransRecipientInfoBuilder,
+    };
+    use cms::cert::IssuerAndSerialNumber;
+    use cms::enveloped_data::RecipientIdentifier;
+    use rand::rngs::OsRng;
+
+    let mut rng = OsRng;
+    let (cert, private_key) = recipient();
+    let rid = RecipientIdentifier::IssuerAndSerialNumber(IssuerAndSerialNumber {
+        issuer: cert.tbs_certificate().issuer().clone(),
+        serial_number: cert.tbs_certificate().serial_number().clone(),
+    });
+    let recipient_info_builder = KeyTransRecipientInfoBuilder::new(
+        rid,
+        KeyEncryptionInfo::Rsa(private_key.to_public_key()),
+        &mut rng,
+    )
+    .unwrap();
+
+    let mut builder =
+        EnvelopedDataBuilder::new(None, DATA, ContentEncryptionAlgorithm::Aes256Cbc, None).unwrap();
+    let ed = builder
+        .add_recipient_info(recipient_info_builder)
+        .unwrap()
+        .build_with_rng(&mut OsRng)
+        .unwrap();
+
+    let key = ed.recip_infos.decrypt_key(&cert, &private_key).unwrap();
+    assert_eq!(ed.decrypt(&key).unwrap(), DATA);
+}
@@ -1,8 +1,9 @@
 //! SignedData tests
 
-use cms::content_info::ContentInfo;
+use cms::content_info::{Content, ContentInfo};
 use cms::signed_data::{SignedData, SignerInfos};
-use der::{AnyRef, Decode, DecodePem, Encode, ErrorKind, Tag};
+use der::asn1::OctetStringRef;
+use der::{AnyRef, Decode, DecodePem, Document, Encode, ErrorKind, Tag};
 use x509_cert::Certificate;
 
 #[test]
@@ -143,3 +144,114 @@ fn certs_to_p7b() {
     let p7b_buf2 = p7b_ee.to_der().unwrap();
     assert_eq!(p7b_buf, p7b_buf2.as_slice());
 }
+
+fn signed_data(ci: &ContentInfo) -> SignedData {
+    match ci.decode_content().unwrap() {
+        Content::SignedData(sd) => *sd,
+        content => panic!("unexpected content: {content:?}"),
+    }
+}
+
+#[test]
+fn decode_ber_signed_data() {
+    // streamed (BER, indefinite-length) and DER encodings of the same content
+    let ber = include_bytes!("examples/cms_ber.bin");
+    let der = include_bytes!("examples/cms_der.bin");
+
+    assert!(ContentInfo::from_der(ber).is_err());
+    let ci = Document::from_ber(ber)
+        .unwrap()
+        .decode_msg::<ContentInfo>()
+        .unwrap();
+    let sd = signed_data(&ci);
+    let der_sd = signed_data(&ContentInfo::from_der(der).unwrap());
+    assert_eq!(sd.digest_algorithms, der_sd.digest_algorithms);
+    assert_eq!(sd.encap_content_info, der_sd.encap_content_info);
+    assert_eq!(sd.signer_infos.0.len(), 1);
+
+    let econtent = sd.encap_content_info.econtent.as_ref().unwrap();
+    let econtent = econtent.decode_as::<OctetStringRef<'_>>().unwrap();
+    assert_eq!(econtent.as_bytes().len(), 10034);
+    assert!(econtent.as_bytes().starts_with(b"Inizio contenuto."));
+}
+
+#[test]
+fn decode_detached_signed_data() {
+    // see examples_notes.txt
+    let ci = ContentInfo::from_der(include_bytes!("examples/sd_detached.der")).unwrap();
+    let sd = signed_data(&ci);
+    assert_eq!(
+        sd.encap_content_info.econtent_type,
+        const_oid::db::rfc5911::ID_DATA
+    );
+    assert!(sd.encap_content_info.econtent.is_none());
+
+    let signer_info = sd.signer_infos.0.get(0).unwrap();
+    assert_eq!(signer_info.signed_attrs.as_ref().unwrap().len(), 4);
+    assert!(signer_info.unsigned_attrs.is_none());
+
+    let cert = Certificate::from_pem(include_bytes!("examples/cert.pem")).unwrap();
+    assert_eq!(sd.x509_certificates().collect::<Vec<_>>(), [&cert]);
+}
+
+#[test]
+fn x509_certificates() {
+    let p7b = include_bytes!("examples/pkits.p7b");
+    let sd = signed_data(&ContentInfo::from_der(p7b).unwrap());
+
+    let ee_cert = Certificate::from_der(include_bytes!("examples/ValidCertificatePathTest1EE.crt"));
+    let ca_cert = Certificate::from_der(include_bytes!("examples/GoodCACert.crt"));
+    let certs = sd.x509_certificates().collect::<Vec<_>>();
+    assert_eq!(certs.len(), 2);
+    assert!(certs.contains(&&ee_cert.unwrap()));
+    assert!(certs.contains(&&ca_cert.unwrap()));
+
+    // the certificate set is re-encoded as-is
+    let ci = ContentInfo {
+        content_type: const_oid::db::rfc5911::ID_SIGNED_DATA,
+        content: AnyRef::try_from(sd.to_der().unwrap().as_slice())
+            .unwrap()
+            .into(),
+    };
+    assert_eq!(ci.to_der().unwrap(), p7b);
+}
+
+#[test]
+fn certs_only() {
+    let p7b = include_bytes!("examples/pkits.p7b");
+    let ee_cert = Certificate::from_der(include_bytes!("examples/ValidCertificatePathTest1EE.crt"));
+    let ca_cert = Certificate::from_der(include_bytes!("examples/GoodCACert.crt"));
+    let certs = [ee_cert.unwrap(), ca_cert.unwrap()];
+
+    let ci = SignedData::certs_only(certs.clone()).unwrap();
+    assert_eq!(ci.to_der().unwrap(), p7b);
+
+    let mut extracted = ContentInfo::from_der(p7b)
+        .unwrap()
+        .try_into_certificates()
+        .unwrap();
+    extracted.sort_by_key(|cert| cert.to_der().unwrap());
+    let mut expected = certs.to_vec();
+    expected.sort_by_key(|cert| cert.to_der().unwrap());
+    assert_eq!(extracted, expected);
+
+    // messages with signers aren't certs-only
+    let ci = ContentInfo::from_der(include_bytes!("examples/sd_detached.der")).unwrap();
+    assert!(ci.try_into_certificates().is_err());
+
+    let ci = ContentInfo::from_der(include_bytes!("examples/digested_data.bin")).unwrap();
+    assert!(ci.try_into_certificates().is_err());
+}
+
+#[cfg(feature = "pem")]
+#[test]
+fn certs_only_pem() {
+    use der::{pem::LineEnding, EncodePem};
+
+    // PEM encapsulation of pkits.p7b
+    let pem = include_str!("examples/pkits.p7b.pem");
+    let ci = ContentInfo::from_pem(pem).unwrap();
+    assert_eq!(ci.to_der().unwrap(), include_bytes!("examples/pkits.p7b"));
+    assert_eq!(ci.to_pem(LineEnding::LF).unwrap(), pem);
+    assert_eq!(ci.try_into_certificates().unwrap().len(), 2);
+}
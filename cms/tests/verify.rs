@@ -0,0 +1,156 @@
+//! SignedData verification tests
+
+#![cfg(feature = "verify")]
+
+use cms::content_info::{Content, ContentInfo};
+use cms::signed_data::{SignedData, SignerIdentifier, SignerInfo, SignerInfos};
+use cms::verify::Error;
+use der::{Decode, DecodePem};
+use x509_cert::Certificate;
+
+const DATA: &[u8] = include_bytes!("examples/data.txt");
+
+fn signed_data(der: &[u8]) -> SignedData {
+    match ContentInfo::from_der(der)
+        .unwrap()
+        .decode_content()
+        .unwrap()
+    {
+        Content::SignedData(sd) => *sd,
+        content => panic!("unexpected content: {content:?}"),
+    }
+}
+
+#[test]
+fn verify_rsa_detached() {
+    // see examples_notes.txt
+    let sd = signed_data(include_bytes!("examples/sd_detached.der"));
+    let verified = sd.verify(Some(DATA)).unwrap();
+    assert_eq!(verified.len(), 1);
+    assert_eq!(verified[0].index, 0);
+    assert_eq!(verified[0].signer_info, sd.signer_infos.0.get(0).unwrap());
+    assert_eq!(
+        verified[0].certificate,
+        &Certificate::from_pem(include_bytes!("examples/cert.pem")).unwrap()
+    );
+
+    assert_eq!(sd.verify(None), Err(Error::Content));
+    assert_eq!(
+        sd.verify(Some(b"tampered")),
+        Err(Error::MessageDigestMismatch)
+    );
+    assert_eq!(sd.verify_signer(1, Some(DATA)), Err(Error::SignerNotFound));
+}
+
+#[test]
+fn verify_rsa_pss() {
+    let sd = signed_data(include_bytes!("examples/sd_rsa_pss.der"));
+    assert_eq!(
+        sd.signer_infos.0.get(0).unwrap().signature_algorithm.oid,
+        const_oid::db::rfc5912::ID_RSASSA_PSS
+    );
+    assert_eq!(sd.verify(None).unwrap().len(), 1);
+    assert_eq!(sd.verify(Some(DATA)), Err(Error::Content));
+}
+
+#[test]
+fn verify_rsa_without_signed_attributes() {
+    let der = include_bytes!("examples/sd_rsa_noattr.der");
+    let sd = signed_data(der);
+    assert!(sd.signer_infos.0.get(0).unwrap().signed_attrs.is_none());
+    assert_eq!(sd.verify(None).unwrap().len(), 1);
+
+    // the signature is the last field of the message
+    let mut tampered = der.to_vec();
+    *tampered.last_mut().unwrap() ^= 1;
+    assert_eq!(signed_data(&tampered).verify(None), Err(Error::Signature));
+}
+
+#[test]
+fn verify_p256_subject_key_identifier() {
+    let sd = signed_data(include_bytes!("examples/sd_p256_keyid.der"));
+    let verified = sd.verify(None).unwrap();
+    assert!(matches!(
+        verified[0].signer_info.sid,
+        SignerIdentifier::SubjectKeyIdentifier(_)
+    ));
+    assert_eq!(
+        verified[0].certificate,
+        &Certificate::from_pem(include_bytes!("examples/p256-cert.pem")).unwrap()
+    );
+
+    let mut tampered = sd.clone();
+    let signer_info = tampered.signer_infos.0.get(0).unwrap().clone();
+    let mut signature = signer_info.signature.as_bytes().to_vec();
+    let last = signature.len() - 1;
+    signature[last] ^= 1;
+    let signer_info = SignerInfo {
+        signature: der::asn1::OctetString::new(signature).unwrap(),
+        ..signer_info
+    };
+    tampered.signer_infos = SignerInfos(vec![signer_info].try_into().unwrap());
+    assert_eq!(tampered.verify(None), Err(Error::Signature));
+}
+
+#[test]
+fn verify_unsupported() {
+    let sd = signed_data(include_bytes!("examples/sd_ed25519.der"));
+    assert_eq!(
+        sd.verify(None),
+        Err(Error::UnsupportedAlgorithm(
+            const_oid::db::rfc8410::ID_ED_25519
+        ))
+    );
+
+    let sd = signed_data(include_bytes!("examples/sd_p384.der"));
+    assert_eq!(
+        sd.verify(None),
+        Err(Error::UnsupportedAlgorithm(
+            const_oid::db::rfc5912::SECP_384_R_1
+        ))
+    );
+}
+
+#[test]
+fn verify_digest_algorithm_mismatch() {
+    use const_oid::db::rfc5912::{
+        ECDSA_WITH_SHA_384, ID_SHA_256, ID_SHA_384, RSA_ENCRYPTION, SHA_256_WITH_RSA_ENCRYPTION,
+    };
+    use const_oid::db::rfc8410::ID_ED_25519;
+
+    let with_algorithms = |der, signature_alg, digest_alg| {
+        let mut sd = signed_data(der);
+        let mut signer_info = sd.signer_infos.0.get(0).unwrap().clone();
+        signer_info.signature_algorithm.oid = signature_alg;
+        signer_info.digest_alg.oid = digest_alg;
+        sd.signer_infos = SignerInfos(vec![signer_info].try_into().unwrap());
+        sd
+    };
+
+    // `rsaEncryption` doesn't specify a digest algorithm
+    let sd_detached = include_bytes!("examples/sd_detached.der");
+    let sd = with_algorithms(sd_detached, SHA_256_WITH_RSA_ENCRYPTION, ID_SHA_256);
+    assert_eq!(sd.verify(Some(DATA)).unwrap().len(), 1);
+    let sd = with_algorithms(sd_detached, SHA_256_WITH_RSA_ENCRYPTION, ID_SHA_384);
+    assert_eq!(sd.verify(Some(DATA)), Err(Error::DigestAlgorithmMismatch));
+    let sd = with_algorithms(sd_detached, RSA_ENCRYPTION, ID_SHA_384);
+    assert_eq!(sd.verify(Some(DATA)), Err(Error::MessageDigestMismatch));
+
+    let sd_p384 = include_bytes!("examples/sd_p384.der");
+    let sd = with_algorithms(sd_p384, ECDSA_WITH_SHA_384, ID_SHA_256);
+    assert_eq!(sd.verify(None), Err(Error::DigestAlgorithmMismatch));
+
+    let sd_ed25519 = include_bytes!("examples/sd_ed25519.der");
+    let sd = with_algorithms(sd_ed25519, ID_ED_25519, ID_SHA_384);
+    assert_eq!(sd.verify(None), Err(Error::DigestAlgorithmMismatch));
+}
+
+#[test]
+fn verify_missing_certificate() {
+    let mut sd = signed_data(include_bytes!("examples/sd_p256_keyid.der"));
+    sd.certificates = None;
+    assert_eq!(sd.verify(None), Err(Error::CertificateNotFound));
+
+    let sd = signed_data(include_bytes!("examples/pkits.p7b"));
+    assert_eq!(sd.verify(None), Err(Error::SignerNotFound));
+}
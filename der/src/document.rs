@@ -1,6 +1,11 @@
 //! ASN.1 DER-encoded documents stored on the heap.
 
-use crate::{Decode, Encode, Error, FixedTag, Header, Length, Reader, SliceReader, Tag, Writer};
+mod ber;
+
+use crate::{
+    Decode, Encode, EncodingRules, Error, FixedTag, Header, Length, Reader, SliceReader, Tag,
+    Writer,
+};
 use alloc::vec::Vec;
 use core::fmt::{self, Debug};
 
@@ -75,6 +80,25 @@ impl Document {
         msg.to_der()?.try_into()
     }
 
+    /// Convert a BER-encoded `SEQUENCE` into a DER [`Document`].
+    ///
+    /// Indefinite lengths are replaced with definite ones, lengths are
+    /// re-encoded in their minimal form, constructed `BIT STRING`s, `OCTET
+    /// STRING`s (as emitted by streaming CMS encoders) and character strings
+    /// are concatenated into primitive ones, and the elements of `SET`s are
+    /// sorted by their encodings.
+    ///
+    /// Implicitly tagged constructed strings are kept as-is, and may still be
+    /// rejected when decoding the resulting document.
+    pub fn from_ber(ber_bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = SliceReader::new_with_encoding_rules(ber_bytes, EncodingRules::Ber)?;
+        let mut der_bytes = Vec::with_capacity(ber_bytes.len());
+        ber::convert(&mut reader, &mut der_bytes, 0)?;
+        reader.finish(())?;
+
+        der_bytes.try_into()
+    }
+
     /// Decode ASN.1 DER document from PEM.
     ///
     /// Returns the PEM label and decoded [`Document`] on success.
@@ -202,6 +226,14 @@ impl TryFrom<Vec<u8>> for Document {
 /// This type provides additional hardening such as ensuring that the contents
 /// are zeroized-on-drop, and also using more restrictive file permissions when
 /// writing files to disk.
+///
+/// Files are written atomically: the data is first written to a temporary file
+/// in the same directory, which is then renamed over the destination. On Unix,
+/// that temporary file is created with `0600` permissions. If the destination
+/// is a symbolic link, the link itself is replaced rather than written through.
+///
+/// See [`SecretFileOptions`] for additional checks and guarantees when reading
+/// and writing files.
 #[cfg(feature = "zeroize")]
 #[derive(Clone)]
 pub struct SecretDocument(Document);
@@ -252,19 +284,50 @@ impl SecretDocument {
     /// Read ASN.1 DER document from a file.
     #[cfg(feature = "std")]
     pub fn read_der_file(path: impl AsRef<Path>) -> Result<Self, Error> {
-        Document::read_der_file(path).map(Self)
+        Self::read_der_file_with_options(path, SecretFileOptions::default())
+    }
+
+    /// Read ASN.1 DER document from a file, using the given [`SecretFileOptions`].
+    #[cfg(feature = "std")]
+    pub fn read_der_file_with_options(
+        path: impl AsRef<Path>,
+        options: SecretFileOptions,
+    ) -> Result<Self, Error> {
+        Self::try_from(read_secret_file(path, options)?.as_slice())
     }
 
     /// Write ASN.1 DER document to a file.
     #[cfg(feature = "std")]
     pub fn write_der_file(&self, path: impl AsRef<Path>) -> Result<(), Error> {
-        write_secret_file(path, self.as_bytes())
+        self.write_der_file_with_options(path, SecretFileOptions::default())
+    }
+
+    /// Write ASN.1 DER document to a file, using the given [`SecretFileOptions`].
+    #[cfg(feature = "std")]
+    pub fn write_der_file_with_options(
+        &self,
+        path: impl AsRef<Path>,
+        options: SecretFileOptions,
+    ) -> Result<(), Error> {
+        write_secret_file(path, self.as_bytes(), options)
     }
 
     /// Read PEM-encoded ASN.1 DER document from a file.
     #[cfg(all(feature = "pem", feature = "std"))]
     pub fn read_pem_file(path: impl AsRef<Path>) -> Result<(String, Self), Error> {
-        Document::read_pem_file(path).map(|(label, doc)| (label, Self(doc)))
+        Self::read_pem_file_with_options(path, SecretFileOptions::default())
+    }
+
+    /// Read PEM-encoded ASN.1 DER document from a file, using the given
+    /// [`SecretFileOptions`].
+    #[cfg(all(feature = "pem", feature = "std"))]
+    pub fn read_pem_file_with_options(
+        path: impl AsRef<Path>,
+        options: SecretFileOptions,
+    ) -> Result<(String, Self), Error> {
+        let pem = read_secret_file(path, options)?;
+        let pem = core::str::from_utf8(&pem).map_err(|_| pem::Error::CharacterEncoding)?;
+        Self::from_pem(pem).map(|(label, doc)| (label.to_owned(), doc))
     }
 
     /// Write PEM-encoded ASN.1 DER document to a file.
@@ -275,7 +338,20 @@ impl SecretDocument {
         label: &'static str,
         line_ending: pem::LineEnding,
     ) -> Result<(), Error> {
-        write_secret_file(path, self.to_pem(label, line_ending)?.as_bytes())
+        self.write_pem_file_with_options(path, label, line_ending, SecretFileOptions::default())
+    }
+
+    /// Write PEM-encoded ASN.1 DER document to a file, using the given
+    /// [`SecretFileOptions`].
+    #[cfg(all(feature = "pem", feature = "std"))]
+    pub fn write_pem_file_with_options(
+        &self,
+        path: impl AsRef<Path>,
+        label: &'static str,
+        line_ending: pem::LineEnding,
+        options: SecretFileOptions,
+    ) -> Result<(), Error> {
+        write_secret_file(path, self.to_pem(label, line_ending)?.as_bytes(), options)
     }
 }
 #[cfg(feature = "zeroize")]
@@ -330,31 +406,173 @@ fn decode_sequence<'a>(decoder: &mut SliceReader<'a>) -> Result<&'a [u8], Error>
     decoder.read_slice(len)
 }
 
-/// Write a file containing secret data to the filesystem, restricting the
-/// file permissions so it's only readable by the owner
+/// Options for reading and writing files containing secret data with
+/// [`SecretDocument`].
+///
+/// ```
+/// let options = der::SecretFileOptions::new().sync(true).owner_only(true);
+/// assert!(options.sync && options.owner_only);
+/// ```
+#[cfg(all(feature = "std", feature = "zeroize"))]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct SecretFileOptions {
+    /// When writing, flush the file contents to disk before renaming it to its
+    /// destination, and on Unix flush the directory after the rename.
+    pub sync: bool,
+
+    /// When reading, reject files which can be accessed by users other than
+    /// their owner, with [`ErrorKind::PermissionDenied`][crate::ErrorKind::PermissionDenied].
+    ///
+    /// Only checked on Unix.
+    pub owner_only: bool,
+}
+
+#[cfg(all(feature = "std", feature = "zeroize"))]
+impl SecretFileOptions {
+    /// Create the default options, under which none of the additional checks
+    /// and guarantees apply.
+    pub const fn new() -> Self {
+        Self {
+            sync: false,
+            owner_only: false,
+        }
+    }
+
+    /// Set whether to flush written files to disk, see the `sync` field.
+    pub const fn sync(mut self, sync: bool) -> Self {
+        self.sync = sync;
+        self
+    }
+
+    /// Set whether to reject files accessible by other users when reading,
+    /// see the `owner_only` field.
+    pub const fn owner_only(mut self, owner_only: bool) -> Self {
+        self.owner_only = owner_only;
+        self
+    }
+}
+
+/// Read a file containing secret data from the filesystem.
+#[cfg(all(feature = "std", feature = "zeroize"))]
+fn read_secret_file(
+    path: impl AsRef<Path>,
+    options: SecretFileOptions,
+) -> Result<Zeroizing<Vec<u8>>, Error> {
+    use std::io::Read;
+
+    let mut file = fs::File::open(path)?;
+    let metadata = file.metadata()?;
+
+    #[cfg(unix)]
+    if options.owner_only {
+        use std::os::unix::fs::PermissionsExt;
+
+        if metadata.permissions().mode() & 0o077 != 0 {
+            return Err(std::io::Error::from(std::io::ErrorKind::PermissionDenied).into());
+        }
+    }
+
+    #[cfg(not(unix))]
+    let _ = options;
+
+    // Allocate upfront so no unzeroized copy is left behind by reallocations.
+    let capacity = usize::try_from(metadata.len())?.saturating_add(1);
+    let mut data = Zeroizing::new(Vec::with_capacity(capacity));
+    file.read_to_end(&mut data)?;
+    Ok(data)
+}
+
+/// Write a file containing secret data to the filesystem.
+///
+/// The data is written to a temporary file in the same directory which is then
+/// renamed to `path`, so `path` never contains partially written data. The
+/// rename replaces a symbolic link at `path` instead of following it.
+#[cfg(all(feature = "std", feature = "zeroize"))]
+fn write_secret_file(
+    path: impl AsRef<Path>,
+    data: &[u8],
+    options: SecretFileOptions,
+) -> Result<(), Error> {
+    use std::io::Write;
+
+    let path = path.as_ref();
+    let tmp_path = secret_tmp_path(path)?;
+
+    let result = create_secret_file(&tmp_path).and_then(|mut file| {
+        file.write_all(data)?;
+
+        if options.sync {
+            file.sync_all()?;
+        }
+
+        drop(file);
+        fs::rename(&tmp_path, path)
+    });
+
+    if let Err(err) = result {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(err.into());
+    }
+
+    #[cfg(unix)]
+    if options.sync {
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+
+        fs::File::open(dir)?.sync_all()?;
+    }
+
+    Ok(())
+}
+
+/// Get a path for a temporary file next to `path`, which is unique within the
+/// current process.
+#[cfg(all(feature = "std", feature = "zeroize"))]
+fn secret_tmp_path(path: &Path) -> std::io::Result<std::path::PathBuf> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::InvalidInput))?;
+
+    let mut tmp_name = std::ffi::OsString::from(".");
+    tmp_name.push(file_name);
+    tmp_name.push(alloc::format!(
+        ".{}.{}.tmp",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    Ok(path.with_file_name(tmp_name))
+}
+
+/// Create a new file for secret data, restricting the file permissions so it's
+/// only readable by the owner.
 #[cfg(all(unix, feature = "std", feature = "zeroize"))]
-fn write_secret_file(path: impl AsRef<Path>, data: &[u8]) -> Result<(), Error> {
-    use std::{io::Write, os::unix::fs::OpenOptionsExt};
+fn create_secret_file(path: &Path) -> std::io::Result<fs::File> {
+    use std::os::unix::fs::OpenOptionsExt;
 
     /// File permissions for secret data
-    #[cfg(unix)]
     const SECRET_FILE_PERMS: u32 = 0o600;
 
     fs::OpenOptions::new()
-        .create(true)
         .write(true)
-        .truncate(true)
+        .create_new(true)
         .mode(SECRET_FILE_PERMS)
         .open(path)
-        .and_then(|mut file| file.write_all(data))?;
-
-    Ok(())
 }
 
-/// Write a file containing secret data to the filesystem
+/// Create a new file for secret data.
 // TODO(tarcieri): permissions hardening on Windows
 #[cfg(all(not(unix), feature = "std", feature = "zeroize"))]
-fn write_secret_file(path: impl AsRef<Path>, data: &[u8]) -> Result<(), Error> {
-    fs::write(path, data)?;
-    Ok(())
+fn create_secret_file(path: &Path) -> std::io::Result<fs::File> {
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
 }
@@ -0,0 +1,234 @@
+//! Conversion of BER-encoded messages to DER.
+
+use crate::{
+    Decode, Encode, ErrorKind, Header, IndefiniteLength, Length, Reader, Result, SliceReader, Tag,
+};
+use alloc::vec::Vec;
+
+/// Maximum nesting depth of constructed values.
+const MAX_DEPTH: usize = 64;
+
+/// Constructed bit of the identifier octet.
+const CONSTRUCTED_FLAG: u8 = 0x20;
+
+/// End-of-contents marker terminating an indefinite-length value.
+const END_OF_CONTENTS: [u8; 2] = [0, 0];
+
+/// Convert the BER-encoded TLV read from `reader` to DER, appending it to `output`.
+pub(super) fn convert<'a, R: Reader<'a>>(
+    reader: &mut R,
+    output: &mut Vec<u8>,
+    depth: usize,
+) -> Result<()> {
+    if depth > MAX_DEPTH {
+        return Err(reader.error(ErrorKind::Overflow));
+    }
+
+    let string_tag = reader.peek_byte().and_then(constructed_string_tag);
+    let tag = match string_tag {
+        Some(tag) => {
+            reader.read_byte()?;
+            tag
+        }
+        None => Tag::decode(reader)?,
+    };
+    let length = IndefiniteLength::decode(reader)?;
+
+    if !tag.is_constructed() && string_tag.is_none() {
+        let value = reader.read_slice(length.try_into()?)?;
+        return write_tlv(output, tag, value);
+    }
+
+    let mut contents = Vec::new();
+
+    match Option::<Length>::from(length) {
+        Some(length) => reader.read_nested(length, |reader| -> Result<()> {
+            while !reader.is_finished() {
+                convert(reader, &mut contents, depth + 1)?;
+            }
+
+            Ok(())
+        })?,
+        None => loop {
+            let mut marker = [0u8; END_OF_CONTENTS.len()];
+            reader.peek_into(&mut marker)?;
+
+            if marker == END_OF_CONTENTS {
+                reader.read_slice(Length::try_from(END_OF_CONTENTS.len())?)?;
+                break;
+            }
+
+            convert(reader, &mut contents, depth + 1)?;
+        },
+    }
+
+    match string_tag {
+        Some(tag) => write_tlv(output, tag, &concatenate(tag, &contents)?),
+        None if tag == Tag::Set => {
+            // DER requires the elements of a `SET OF` to be sorted by their encodings
+            let mut elements = split(&contents)?;
+            elements.sort_unstable();
+            write_tlv(output, tag, &elements.concat())
+        }
+        None => write_tlv(output, tag, &contents),
+    }
+}
+
+/// Get the tag of the primitive encoding of a constructed string from its
+/// identifier octet (e.g. `0x24` for an `OCTET STRING`), which [`Tag`] can't
+/// represent.
+fn constructed_string_tag(byte: u8) -> Option<Tag> {
+    // universal class and constructed
+    if byte & 0xE0 != CONSTRUCTED_FLAG {
+        return None;
+    }
+
+    Tag::try_from(byte & !CONSTRUCTED_FLAG).ok().filter(|tag| {
+        matches!(
+            tag,
+            Tag::BitString
+                | Tag::OctetString
+                | Tag::Utf8String
+                | Tag::NumericString
+                | Tag::PrintableString
+                | Tag::TeletexString
+                | Tag::VideotexString
+                | Tag::Ia5String
+                | Tag::VisibleString
+                | Tag::GeneralString
+                | Tag::BmpString
+        )
+    })
+}
+
+/// Concatenate the segments of a constructed string with the given `tag`,
+/// which have already been converted to primitive encodings, as DER requires
+/// the primitive encoding.
+fn concatenate(tag: Tag, contents: &[u8]) -> Result<Vec<u8>> {
+    let mut segments = SliceReader::new(contents)?;
+    let mut value = Vec::with_capacity(contents.len() + 1);
+    let mut unused_bits = 0;
+
+    while !segments.is_finished() {
+        // only the last segment of a `BIT STRING` may have unused bits
+        if unused_bits != 0 {
+            return Err(Tag::BitString.value_error());
+        }
+
+        let header = Header::decode(&mut segments)?;
+        header.tag.assert_eq(tag)?;
+        let segment = segments.read_slice(header.length)?;
+
+        if tag == Tag::BitString {
+            let (&bits, bytes) = segment
+                .split_first()
+                .ok_or_else(|| Tag::BitString.length_error())?;
+            unused_bits = bits;
+            value.extend_from_slice(bytes);
+        } else {
+            value.extend_from_slice(segment);
+        }
+    }
+
+    if tag == Tag::BitString {
+        value.insert(0, unused_bits);
+    }
+
+    Ok(value)
+}
+
+/// Split the DER-encoded `contents` of a constructed value into its elements.
+fn split(contents: &[u8]) -> Result<Vec<&[u8]>> {
+    let mut reader = SliceReader::new(contents)?;
+    let mut elements = Vec::new();
+
+    while !reader.is_finished() {
+        let start = usize::try_from(reader.position())?;
+        let header = Header::decode(&mut reader)?;
+        reader.read_slice(header.length)?;
+        elements.push(&contents[start..usize::try_from(reader.position())?]);
+    }
+
+    Ok(elements)
+}
+
+/// Append the DER encoding of a value with the given `tag` and `value` octets to `output`.
+fn write_tlv(output: &mut Vec<u8>, tag: Tag, value: &[u8]) -> Result<()> {
+    let mut buf = [0u8; Header::MAX_SIZE];
+    output.extend_from_slice(Header::new(tag, value.len())?.encode_to_slice(&mut buf)?);
+    output.extend_from_slice(value);
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use crate::{Document, ErrorKind, Tag};
+    use hex_literal::hex;
+
+    #[test]
+    fn indefinite_length() {
+        let doc = Document::from_ber(&hex!("3080020105 30800401AA0000 0000")).unwrap();
+        assert_eq!(doc.as_bytes(), hex!("3008020105 30030401AA"));
+    }
+
+    #[test]
+    fn constructed_octet_string() {
+        let doc =
+            Document::from_ber(&hex!("3080A080 2480 04020102 040103 0000 0000 0000")).unwrap();
+        assert_eq!(doc.as_bytes(), hex!("3007A005 0403010203"));
+    }
+
+    #[test]
+    fn constructed_bit_string() {
+        let doc = Document::from_ber(&hex!("3080 2380 03020001 030204F0 0000 0000")).unwrap();
+        assert_eq!(doc.as_bytes(), hex!("3005 03030401F0"));
+
+        // Only the last segment may have unused bits
+        let err = Document::from_ber(&hex!("3080 2380 030204F0 03020001 0000 0000")).unwrap_err();
+        assert_eq!(err.kind(), Tag::BitString.value_error().kind());
+    }
+
+    #[test]
+    fn constructed_character_string() {
+        let doc = Document::from_ber(&hex!("300A 2C08 0C024142 0C024344")).unwrap();
+        assert_eq!(doc.as_bytes(), hex!("3006 0C0441424344"));
+
+        // Segments must have the same type as the string
+        let err = Document::from_ber(&hex!("300A 2C08 0C024142 04024344")).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::TagUnexpected { .. }));
+    }
+
+    #[test]
+    fn set_of_sorted() {
+        let doc = Document::from_ber(&hex!("3080 3180 020102 020101 0500 0000 0000")).unwrap();
+        assert_eq!(doc.as_bytes(), hex!("300A 3108 020101 020102 0500"));
+    }
+
+    #[test]
+    fn non_minimal_length() {
+        let doc = Document::from_ber(&hex!("308103020101")).unwrap();
+        assert_eq!(doc.as_bytes(), hex!("3003020101"));
+    }
+
+    #[test]
+    fn der_unchanged() {
+        let der = hex!("3007A005 0403010203");
+        assert_eq!(Document::from_ber(&der).unwrap().as_bytes(), der);
+    }
+
+    #[test]
+    fn invalid() {
+        // Primitive values can't have an indefinite length
+        let err = Document::from_ber(&hex!("30800480010000")).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::IndefiniteLength);
+
+        // Missing end-of-contents
+        let err = Document::from_ber(&hex!("3080020105")).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::Incomplete { .. }));
+
+        // Trailing data
+        let err = Document::from_ber(&hex!("300302010500")).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::TrailingData { .. }));
+    }
+}
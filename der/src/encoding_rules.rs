@@ -7,6 +7,9 @@
 /// of the Basic Encoding Rules (BER) which supports the minimum amount of additional productions
 /// beyond DER needed to interoperate with other implementations of cryptography-oriented formats
 /// which utilize BER, e.g. CMS, PKCS#8.
+///
+/// Among these productions, BER lengths may be encoded with more octets than necessary
+/// (X.690 Section 8.1.3.5), which DER rejects (X.690 Section 10.1).
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, PartialOrd, Ord)]
 pub enum EncodingRules {
     /// Basic Encoding Rules.
@@ -58,7 +58,9 @@ impl<'a> Decode<'a> for Header {
             }
         })?;
 
-        Ok(Self { tag, length })
+        let header = Self { tag, length };
+        reader.check_header(&header)?;
+        Ok(header)
     }
 }
 
@@ -1,6 +1,8 @@
 //! Length calculations for encoded ASN.1 DER values
 
-use crate::{Decode, DerOrd, Encode, Error, ErrorKind, Reader, Result, SliceWriter, Writer};
+use crate::{
+    Decode, DerOrd, Encode, EncodingRules, Error, ErrorKind, Reader, Result, SliceWriter, Writer,
+};
 use core::{
     cmp::Ordering,
     fmt,
@@ -229,8 +231,10 @@ impl<'a> Decode<'a> for Length {
                 let length = Length::try_from(decoded_len)?;
 
                 // X.690 Section 10.1: DER lengths must be encoded with a minimum
-                // number of octets
-                if length.initial_octet() == Some(tag) {
+                // number of octets, whereas BER allows any number (Section 8.1.3.5)
+                if length.initial_octet() == Some(tag)
+                    || reader.encoding_rules() == EncodingRules::Ber
+                {
                     Ok(length)
                 } else {
                     Err(ErrorKind::Overlength.into())
@@ -449,6 +453,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn decode_non_minimal() {
+        for bytes in [&[0x81, 0x7F][..], &[0x82, 0x00, 0xFF]] {
+            assert_eq!(
+                Length::from_der(bytes).unwrap_err().kind(),
+                ErrorKind::Overlength
+            );
+        }
+
+        assert_eq!(
+            Length::from(0x7Fu8),
+            Length::from_ber(&[0x81, 0x7F]).unwrap()
+        );
+
+        assert_eq!(
+            Length::from(0xFFu8),
+            Length::from_ber(&[0x82, 0x00, 0xFF]).unwrap()
+        );
+    }
+
     #[test]
     fn encode() {
         let mut buffer = [0u8; 4];
@@ -375,7 +375,7 @@ pub use crate::{
     header::Header,
     length::{IndefiniteLength, Length},
     ord::{DerOrd, ValueOrd},
-    reader::{slice::SliceReader, Reader},
+    reader::{bounded::BoundedReader, slice::SliceReader, Reader},
     tag::{Class, FixedTag, Tag, TagMode, TagNumber, Tagged},
     writer::{slice::SliceWriter, Writer},
 };
@@ -407,6 +407,9 @@ pub use zeroize;
 #[cfg(all(feature = "alloc", feature = "zeroize"))]
 pub use crate::document::SecretDocument;
 
+#[cfg(all(feature = "std", feature = "zeroize"))]
+pub use crate::document::SecretFileOptions;
+
 pub(crate) use crate::{arrayvec::ArrayVec, bytes_ref::BytesRef, str_ref::StrRef};
 #[cfg(feature = "alloc")]
 pub(crate) use crate::{bytes_owned::BytesOwned, str_owned::StrOwned};
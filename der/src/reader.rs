@@ -1,5 +1,6 @@
 //! Reader trait.
 
+pub(crate) mod bounded;
 #[cfg(feature = "pem")]
 pub(crate) mod pem;
 pub(crate) mod slice;
@@ -86,6 +87,16 @@ pub trait Reader<'r>: Sized {
         }
     }
 
+    /// Check a [`Header`] which has just been decoded from this reader.
+    ///
+    /// Called by [`Header::decode`]. The default implementation accepts any
+    /// header, leaving truncated values to be caught when they are read.
+    /// [`BoundedReader`][`crate::BoundedReader`] overrides it to reject values
+    /// which claim more bytes than remain in the input.
+    fn check_header(&mut self, _header: &Header) -> Result<(), Error> {
+        Ok(())
+    }
+
     /// Have we read all of the input data?
     fn is_finished(&self) -> bool {
         self.remaining_len().is_zero()
@@ -0,0 +1,211 @@
+//! Bounded reader.
+
+use crate::{EncodingRules, Error, ErrorKind, Header, Length, Reader};
+
+/// [`Reader`] wrapper which bounds the input to a known length.
+///
+/// Headers decoded through this reader are checked against the remaining
+/// input: a value which claims more bytes than remain, either in the input or
+/// in the enclosing value, is rejected with [`ErrorKind::Length`] as soon as
+/// its header is read, rather than once its contents are (partially) read.
+///
+/// Nested values are read with the inner reader's [`Reader::read_nested`] and
+/// bounded on top of it, which requires `R` to be [`Clone`].
+#[derive(Clone, Debug)]
+pub struct BoundedReader<R> {
+    /// Inner reader.
+    inner: R,
+
+    /// Position at which the input, or the value being read, ends.
+    end: Length,
+}
+
+impl<'r, R: Reader<'r> + Clone> BoundedReader<R> {
+    /// Create a new bounded reader covering the entire input of `reader`.
+    pub fn new(reader: R) -> Self {
+        let end = reader.input_len();
+        Self { inner: reader, end }
+    }
+
+    /// Create a new bounded reader covering the next `len` bytes of `reader`,
+    /// e.g. the contents of a length-prefixed blob.
+    pub fn with_limit(mut reader: R, len: Length) -> Result<Self, Error> {
+        let end = (reader.position() + len)?;
+
+        if end > reader.input_len() {
+            return Err(reader.error(ErrorKind::Incomplete {
+                expected_len: end,
+                actual_len: reader.input_len(),
+            }));
+        }
+
+        Ok(Self { inner: reader, end })
+    }
+
+    /// Get the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Return an error if fewer than `len` bytes remain.
+    fn check_remaining(&mut self, len: Length) -> Result<(), Error> {
+        if len > self.remaining_len() {
+            let expected_len = (self.position() + len)?;
+            let actual_len = self.end;
+            return Err(self.error(ErrorKind::Incomplete {
+                expected_len,
+                actual_len,
+            }));
+        }
+
+        Ok(())
+    }
+}
+
+impl<'r, R: Reader<'r> + Clone> Reader<'r> for BoundedReader<R> {
+    fn encoding_rules(&self) -> EncodingRules {
+        self.inner.encoding_rules()
+    }
+
+    fn input_len(&self) -> Length {
+        self.end
+    }
+
+    fn peek_into(&self, buf: &mut [u8]) -> crate::Result<()> {
+        if Length::try_from(buf.len())? > self.remaining_len() {
+            return Err(Error::incomplete(self.end));
+        }
+
+        self.inner.peek_into(buf)
+    }
+
+    fn position(&self) -> Length {
+        self.inner.position()
+    }
+
+    fn read_nested<T, F, E>(&mut self, len: Length, f: F) -> Result<T, E>
+    where
+        E: From<Error>,
+        F: FnOnce(&mut Self) -> Result<T, E>,
+    {
+        self.check_remaining(len)?;
+        let end = (self.position() + len)?;
+
+        // The inner reader handles the nested input, e.g. rejecting trailing
+        // data, while the bound applies to the values read within it.
+        self.inner.read_nested(len, |inner| {
+            let mut nested = Self {
+                inner: inner.clone(),
+                end,
+            };
+            let ret = f(&mut nested);
+            *inner = nested.inner;
+            ret
+        })
+    }
+
+    fn read_slice(&mut self, len: Length) -> Result<&'r [u8], Error> {
+        self.check_remaining(len)?;
+        self.inner.read_slice(len)
+    }
+
+    fn error(&mut self, kind: ErrorKind) -> Error {
+        self.inner.error(kind)
+    }
+
+    fn offset(&self) -> Length {
+        self.inner.offset()
+    }
+
+    fn check_header(&mut self, header: &Header) -> Result<(), Error> {
+        if header.length > self.remaining_len() {
+            return Err(self.error(ErrorKind::Length { tag: header.tag }));
+        }
+
+        self.inner.check_header(header)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::BoundedReader;
+    use crate::{asn1::AnyRef, Decode, ErrorKind, Length, Reader, SliceReader, Tag, Tagged};
+    use hex_literal::hex;
+
+    #[test]
+    fn decode() {
+        // SEQUENCE { INTEGER: 1 }, NULL
+        const EXAMPLE_MSG: &[u8] = &hex!("30030201010500");
+
+        let mut reader = BoundedReader::new(SliceReader::new(EXAMPLE_MSG).unwrap());
+        let seq = AnyRef::decode(&mut reader).unwrap();
+        assert_eq!(seq.tag(), Tag::Sequence);
+        let null = AnyRef::decode(&mut reader).unwrap();
+        assert_eq!(null.tag(), Tag::Null);
+        reader.finish(()).unwrap();
+
+        let mut reader =
+            BoundedReader::with_limit(SliceReader::new(EXAMPLE_MSG).unwrap(), 5u8.into()).unwrap();
+        AnyRef::decode(&mut reader).unwrap();
+        assert!(reader.is_finished());
+        assert_eq!(reader.into_inner().remaining_len(), Length::from(2u8));
+    }
+
+    #[test]
+    fn declared_length_exceeds_input() {
+        // SEQUENCE claiming 16 bytes, containing INTEGER: 0, then nothing
+        const EXAMPLE_MSG: &[u8] = &hex!("3010020100");
+
+        let mut reader = BoundedReader::new(SliceReader::new(EXAMPLE_MSG).unwrap());
+        let err = AnyRef::decode(&mut reader).unwrap_err();
+        assert_eq!(err.position(), Some(Length::from(2u8)));
+        assert_eq!(err.kind(), ErrorKind::Length { tag: Tag::Sequence });
+
+        // without the bound, the error only surfaces when reading the value
+        let err = AnyRef::from_der(EXAMPLE_MSG).unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::Incomplete { .. }));
+    }
+
+    #[test]
+    fn declared_length_exceeds_limit() {
+        // SEQUENCE { INTEGER: 1 }, NULL
+        const EXAMPLE_MSG: &[u8] = &hex!("30030201010500");
+
+        let mut reader =
+            BoundedReader::with_limit(SliceReader::new(EXAMPLE_MSG).unwrap(), 4u8.into()).unwrap();
+        let err = AnyRef::decode(&mut reader).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Length { tag: Tag::Sequence });
+
+        let err = BoundedReader::with_limit(SliceReader::new(EXAMPLE_MSG).unwrap(), 8u8.into())
+            .unwrap_err();
+        assert!(matches!(err.kind(), ErrorKind::Incomplete { .. }));
+    }
+
+    #[test]
+    fn nested_length_exceeds_parent() {
+        // SEQUENCE { INTEGER claiming 5 bytes, with only 3 left in the SEQUENCE }, NULL
+        const EXAMPLE_MSG: &[u8] = &hex!("300502050102030500");
+
+        let mut reader = BoundedReader::new(SliceReader::new(EXAMPLE_MSG).unwrap());
+        let err = reader.sequence(AnyRef::decode).unwrap_err();
+        assert_eq!(err.position(), Some(Length::from(4u8)));
+        assert_eq!(err.kind(), ErrorKind::Length { tag: Tag::Integer });
+    }
+
+    #[test]
+    fn nested_trailing_data() {
+        // SEQUENCE { INTEGER: 1, NULL }
+        const EXAMPLE_MSG: &[u8] = &hex!("30050201010500");
+
+        let mut reader = BoundedReader::new(SliceReader::new(EXAMPLE_MSG).unwrap());
+        let err = reader.sequence(AnyRef::decode).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            ErrorKind::TrailingData {
+                decoded: 5u8.into(),
+                remaining: 2u8.into()
+            }
+        );
+    }
+}
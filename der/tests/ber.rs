@@ -0,0 +1,67 @@
+//! Tests for the BER productions accepted by `EncodingRules::Ber`.
+
+use der::{
+    asn1::{AnyRef, OctetStringRef, SequenceOf},
+    Decode, ErrorKind, Header, Length, Tag, Tagged,
+};
+use hex_literal::hex;
+
+/// OCTET STRING `AABB`, with its length encoded in 1, 2 and 4 extra octets.
+const NON_MINIMAL_OCTET_STRINGS: [&[u8]; 3] = [
+    &hex!("048102AABB"),
+    &hex!("04820002AABB"),
+    &hex!("048400000002AABB"),
+];
+
+#[test]
+fn non_minimal_length_rejected_by_der() {
+    for der in NON_MINIMAL_OCTET_STRINGS {
+        let err = OctetStringRef::from_der(der).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            ErrorKind::Length {
+                tag: Tag::OctetString
+            }
+        );
+
+        let err = Header::from_der(&der[..der.len() - 2]).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            ErrorKind::Length {
+                tag: Tag::OctetString
+            }
+        );
+    }
+
+    // the minimal encoding is still accepted
+    let octet_string = OctetStringRef::from_der(&hex!("0402AABB")).unwrap();
+    assert_eq!(octet_string.as_bytes(), hex!("AABB"));
+}
+
+#[test]
+fn non_minimal_length_accepted_by_ber() {
+    for ber in NON_MINIMAL_OCTET_STRINGS {
+        let octet_string = OctetStringRef::from_ber(ber).unwrap();
+        assert_eq!(octet_string.as_bytes(), hex!("AABB"));
+
+        let header = Header::from_ber(&ber[..ber.len() - 2]).unwrap();
+        assert_eq!(header.tag, Tag::OctetString);
+        assert_eq!(header.length, Length::from(2u8));
+    }
+}
+
+#[test]
+fn nested_non_minimal_length() {
+    // SEQUENCE { INTEGER 1, NULL }, with non-minimal lengths for the SEQUENCE
+    // and the INTEGER
+    let ber = hex!("3081 06 028101 01 0500");
+
+    let err = SequenceOf::<AnyRef<'_>, 2>::from_der(&ber).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Length { tag: Tag::Sequence });
+
+    let seq = SequenceOf::<AnyRef<'_>, 2>::from_ber(&ber).unwrap();
+    let mut elements = seq.iter();
+    assert_eq!(elements.next().unwrap().value(), hex!("01"));
+    assert_eq!(elements.next().unwrap().tag(), Tag::Null);
+    assert!(elements.next().is_none());
+}
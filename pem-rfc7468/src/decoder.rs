@@ -167,11 +167,12 @@ impl<'i> io::Read for Decoder<'i> {
 /// on the potentially secret Base64-encoded data encapsulated between
 /// the two boundaries.
 ///
-/// It only supports a single encapsulated message at present. Future work
-/// could potentially include extending it provide an iterator over a series
-/// of encapsulated messages.
+/// It only supports a single encapsulated message: see [`Documents`] for
+/// inputs containing a series of them.
+///
+/// [`Documents`]: crate::Documents
 #[derive(Copy, Clone, Debug)]
-struct Encapsulation<'a> {
+pub(crate) struct Encapsulation<'a> {
     /// Type label extracted from the pre/post-encapsulation boundaries.
     ///
     /// From RFC 7468 Section 2:
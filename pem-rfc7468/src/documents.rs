@@ -0,0 +1,241 @@
+//! Support for inputs containing several concatenated PEM documents, such as
+//! certificate bundles.
+
+use crate::{
+    decoder::Encapsulation, grammar, DocumentError, Error, POST_ENCAPSULATION_BOUNDARY,
+    PRE_ENCAPSULATION_BOUNDARY,
+};
+use core::ops::Range;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/// Iterator over the PEM documents contained in an input, e.g. a bundle of
+/// certificates.
+///
+/// Yields the type label of each document along with the range of the input
+/// it occupies, from the start of the pre-encapsulation boundary to the end of
+/// the post-encapsulation boundary (including its trailing newline, if any),
+/// which can then be passed to [`decode`][`crate::decode`]. The Base64 data
+/// itself is not decoded, so no buffers are needed.
+///
+/// Any text before, between, or after the documents is skipped, so long as it
+/// doesn't contain NUL bytes (see [`Error::Preamble`]). The post-encapsulation
+/// boundary of the last document may lack a trailing newline.
+///
+/// Errors are reported with the index of the offending document and the line
+/// it starts at, after which the iterator is exhausted.
+#[derive(Clone, Debug)]
+pub struct Documents<'i> {
+    /// Complete input.
+    input: &'i [u8],
+
+    /// Position in the input at which the search for the next document starts.
+    position: usize,
+
+    /// Line number of `position`, starting at 1.
+    line: usize,
+
+    /// Index of the next document.
+    index: usize,
+
+    /// Has the end of the input been reached or an error been encountered?
+    finished: bool,
+}
+
+impl<'i> Documents<'i> {
+    /// Create a new iterator over the PEM documents in the given input.
+    pub fn new(input: &'i [u8]) -> Self {
+        Self {
+            input,
+            position: 0,
+            line: 1,
+            index: 0,
+            finished: false,
+        }
+    }
+
+    /// Locate the next document, returning its label and range in the input.
+    fn next_document(&mut self) -> Result<Option<(&'i str, Range<usize>)>, Error> {
+        let rest = self.input.get(self.position..).unwrap_or_default();
+
+        let begin = match find_line(rest, PRE_ENCAPSULATION_BOUNDARY) {
+            Some(begin) => begin,
+            None if rest.contains(&grammar::CHAR_NUL) => return Err(Error::Preamble),
+            None => return Ok(None),
+        };
+
+        let (text, rest) = rest.split_at(begin);
+
+        if text.contains(&grammar::CHAR_NUL) {
+            return Err(Error::Preamble);
+        }
+
+        self.line = self
+            .line
+            .checked_add(count_lines(text))
+            .ok_or(Error::Length)?;
+
+        // Locate the post-encapsulation boundary, which must precede any further
+        // pre-encapsulation boundary
+        let body_start = line_len(rest);
+        let body = rest.get(body_start..).unwrap_or_default();
+        let end =
+            find_line(body, POST_ENCAPSULATION_BOUNDARY).ok_or(Error::PostEncapsulationBoundary)?;
+
+        if find_line(body, PRE_ENCAPSULATION_BOUNDARY).is_some_and(|next| next < end) {
+            return Err(Error::PostEncapsulationBoundary);
+        }
+
+        let len = body
+            .get(end..)
+            .map(line_len)
+            .and_then(|end_len| end_len.checked_add(end))
+            .and_then(|len| len.checked_add(body_start))
+            .ok_or(Error::Length)?;
+
+        let document = rest.get(..len).ok_or(Error::Length)?;
+        let label = Encapsulation::try_from(document)?.label();
+
+        let start = self.position.checked_add(begin).ok_or(Error::Length)?;
+        let range = start..start.checked_add(len).ok_or(Error::Length)?;
+        Ok(Some((label, range)))
+    }
+}
+
+impl<'i> Iterator for Documents<'i> {
+    type Item = Result<(&'i str, Range<usize>), DocumentError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        match self.next_document() {
+            Ok(Some((label, range))) => {
+                let document = self.input.get(range.clone()).unwrap_or_default();
+                let line = self.line;
+
+                self.position = range.end;
+                self.line = line.saturating_add(count_lines(document));
+                self.index = self.index.saturating_add(1);
+                Some(Ok((label, range)))
+            }
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(error) => {
+                self.finished = true;
+                Some(Err(DocumentError::new(self.index, self.line, error)))
+            }
+        }
+    }
+}
+
+/// Decode all of the PEM documents contained in an input according to
+/// RFC 7468's "Strict" grammar.
+///
+/// Returns an iterator which yields the label and decoded contents of each
+/// document in turn. See [`Documents`] for how the input is split.
+#[cfg(feature = "alloc")]
+pub fn decode_multiple(input: &[u8]) -> DecodeMultiple<'_> {
+    DecodeMultiple {
+        documents: Documents::new(input),
+    }
+}
+
+/// Iterator over the decoded PEM documents contained in an input.
+///
+/// Returned by [`decode_multiple`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct DecodeMultiple<'i> {
+    /// Documents in the input.
+    documents: Documents<'i>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'i> Iterator for DecodeMultiple<'i> {
+    type Item = Result<(&'i str, Vec<u8>), DocumentError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.documents.index;
+        let (_, range) = match self.documents.next()? {
+            Ok(document) => document,
+            Err(err) => return Some(Err(err)),
+        };
+
+        let document = self.documents.input.get(range).unwrap_or_default();
+
+        match crate::decode_vec(document) {
+            Ok(decoded) => Some(Ok(decoded)),
+            Err(error) => {
+                self.documents.finished = true;
+
+                // `Documents` has already advanced past the document
+                let line = self.documents.line.saturating_sub(count_lines(document));
+                Some(Err(DocumentError::new(index, line, error)))
+            }
+        }
+    }
+}
+
+/// Find the first line of `data` which starts with `prefix`, returning its
+/// position.
+fn find_line(data: &[u8], prefix: &[u8]) -> Option<usize> {
+    let mut start = 0usize;
+
+    loop {
+        let line = data.get(start..)?;
+
+        if line.starts_with(prefix) {
+            return Some(start);
+        }
+
+        let eol = line
+            .iter()
+            .position(|&c| matches!(c, grammar::CHAR_CR | grammar::CHAR_LF))?;
+        start = start.checked_add(eol)?.checked_add(1)?;
+    }
+}
+
+/// Get the length of the first line of `data` including its line ending, or the
+/// length of `data` if it doesn't contain any.
+fn line_len(data: &[u8]) -> usize {
+    match data
+        .iter()
+        .position(|&c| matches!(c, grammar::CHAR_CR | grammar::CHAR_LF))
+    {
+        Some(eol) => {
+            let eol_len = match data.get(eol..) {
+                Some([grammar::CHAR_CR, grammar::CHAR_LF, ..]) => 2,
+                _ => 1,
+            };
+            eol.saturating_add(eol_len)
+        }
+        None => data.len(),
+    }
+}
+
+/// Count the line endings (CRLF, LF, or CR) in `data`.
+fn count_lines(data: &[u8]) -> usize {
+    let mut count = 0usize;
+    let mut rest = data;
+
+    while !rest.is_empty() {
+        let len = line_len(rest);
+
+        if rest
+            .get(..len)
+            .and_then(<[u8]>::last)
+            .is_some_and(|&c| matches!(c, grammar::CHAR_CR | grammar::CHAR_LF))
+        {
+            count = count.saturating_add(1);
+        }
+
+        rest = rest.get(len..).unwrap_or_default();
+    }
+
+    count
+}
@@ -72,6 +72,67 @@ impl fmt::Display for Error {
 
 impl core::error::Error for Error {}
 
+/// Error in one of the documents of an input containing several PEM
+/// documents.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct DocumentError {
+    /// Index of the document, starting at 0.
+    index: usize,
+
+    /// Line at which the document starts, starting at 1.
+    line: usize,
+
+    /// Error in the document.
+    error: Error,
+}
+
+impl DocumentError {
+    /// Create a new [`DocumentError`].
+    pub(crate) fn new(index: usize, line: usize, error: Error) -> Self {
+        Self { index, line, error }
+    }
+
+    /// Get the index of the document in the input, starting at 0.
+    pub fn index(self) -> usize {
+        self.index
+    }
+
+    /// Get the line at which the document starts, starting at 1.
+    ///
+    /// When the error concerns the text preceding a document, this is the
+    /// line at which that text starts.
+    pub fn line(self) -> usize {
+        self.line
+    }
+
+    /// Get the error in the document.
+    pub fn error(self) -> Error {
+        self.error
+    }
+}
+
+impl fmt::Display for DocumentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (document {} at line {})",
+            self.error, self.index, self.line
+        )
+    }
+}
+
+impl core::error::Error for DocumentError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl From<DocumentError> for Error {
+    fn from(err: DocumentError) -> Error {
+        err.error
+    }
+}
+
 impl From<base64ct::Error> for Error {
     fn from(err: base64ct::Error) -> Error {
         Error::Base64(err)
@@ -58,19 +58,30 @@ extern crate alloc;
 extern crate std;
 
 mod decoder;
+mod documents;
 mod encoder;
 mod error;
 mod grammar;
+#[cfg(feature = "std")]
+mod stream;
 
 pub use crate::{
     decoder::{decode, decode_label, detect_base64_line_width, Decoder},
+    documents::Documents,
     encoder::{encapsulated_len, encapsulated_len_wrapped, encode, encoded_len, Encoder},
-    error::{Error, Result},
+    error::{DocumentError, Error, Result},
 };
 pub use base64ct::LineEnding;
 
 #[cfg(feature = "alloc")]
-pub use crate::{decoder::decode_vec, encoder::encode_string};
+pub use crate::{
+    decoder::decode_vec,
+    documents::{decode_multiple, DecodeMultiple},
+    encoder::encode_string,
+};
+
+#[cfg(feature = "std")]
+pub use crate::stream::{StreamDecoder, StreamEncoder};
 
 /// The pre-encapsulation boundary appears before the encapsulated text.
 ///
@@ -0,0 +1,510 @@
+//! Streaming PEM decoder and encoder operating on [`std::io`] types.
+//!
+//! Unlike [`Decoder`][`crate::Decoder`] and [`Encoder`][`crate::Encoder`],
+//! which operate on in-memory buffers holding the entire document, these
+//! types process the encapsulated text one line at a time, which makes them
+//! suitable for large inputs such as certificate bundles or CRLs.
+
+use crate::{
+    grammar, Error, LineEnding, Result, BASE64_WRAP_WIDTH, ENCAPSULATION_BOUNDARY_DELIMITER,
+    POST_ENCAPSULATION_BOUNDARY, PRE_ENCAPSULATION_BOUNDARY,
+};
+use alloc::{string::String, vec::Vec};
+use base64ct::{Base64, Encoding};
+use std::io::{self, BufRead};
+
+/// Maximum length of a line in the preamble or of a pre-encapsulation
+/// boundary which is retained while searching for the `-----BEGIN` line.
+///
+/// Longer preamble lines are skipped without being buffered.
+const MAX_BOUNDARY_LEN: usize = 256;
+
+/// Number of input bytes Base64 encoded at a time by [`StreamEncoder`].
+const ENCODE_CHUNK_LEN: usize = 48;
+
+/// Streaming PEM decoder.
+///
+/// Reads a PEM document from an [`io::BufRead`] according to RFC 7468's
+/// "Strict" grammar, yielding the decoded bytes through its [`io::Read`] impl.
+///
+/// The preamble and the pre-encapsulation boundary are consumed when the
+/// decoder is constructed. The encapsulated text is then decoded one line at a
+/// time as it is read, validating the line width of the Base64 body along the
+/// way. Reading stops at the post-encapsulation boundary, which must carry the
+/// same label as the pre-encapsulation boundary; any data which follows it is
+/// left unread in the inner reader.
+///
+/// Errors are reported as [`io::Error`]s, which wrap an [`Error`] when the
+/// problem lies in the PEM document itself.
+pub struct StreamDecoder<R> {
+    /// Inner reader.
+    reader: R,
+
+    /// PEM type label.
+    type_label: String,
+
+    /// Width of the Base64 lines.
+    line_width: usize,
+
+    /// Next line of encapsulated text, read ahead of time in order to detect
+    /// whether the current line is the last one.
+    line: Vec<u8>,
+
+    /// Base64 data which hasn't been decoded yet as it doesn't form a
+    /// complete 4-character block.
+    base64: Vec<u8>,
+
+    /// Decoded data which hasn't been read yet.
+    decoded: Vec<u8>,
+
+    /// Position of the next unread byte in `decoded`.
+    position: usize,
+
+    /// Has the post-encapsulation boundary been reached?
+    finished: bool,
+}
+
+impl<R: BufRead> StreamDecoder<R> {
+    /// Create a new [`StreamDecoder`] with the default options.
+    ///
+    /// Uses the default 64-character line wrapping.
+    ///
+    /// Wrap the input in an [`io::BufReader`] when reading from an unbuffered
+    /// source such as a [`std::fs::File`].
+    pub fn new(reader: R) -> io::Result<Self> {
+        Self::new_wrapped(reader, BASE64_WRAP_WIDTH)
+    }
+
+    /// Create a new [`StreamDecoder`] which expects the Base64 body to be
+    /// wrapped at the given line width.
+    pub fn new_wrapped(mut reader: R, line_width: usize) -> io::Result<Self> {
+        if line_width < 4 {
+            return Err(Error::Length.into());
+        }
+
+        let mut line = Vec::new();
+
+        // Skip the "preamble": optional text occurring before the pre-encapsulation boundary
+        loop {
+            match read_line(&mut reader, &mut line, MAX_BOUNDARY_LEN, true)? {
+                None => return Err(Error::PreEncapsulationBoundary.into()),
+                Some(len) if len <= MAX_BOUNDARY_LEN => {
+                    if line.starts_with(PRE_ENCAPSULATION_BOUNDARY) {
+                        break;
+                    }
+                }
+                Some(_) if line.starts_with(PRE_ENCAPSULATION_BOUNDARY) => {
+                    return Err(Error::Label.into())
+                }
+                Some(_) => (),
+            }
+        }
+
+        let type_label = line
+            .strip_prefix(PRE_ENCAPSULATION_BOUNDARY)
+            .and_then(|rest| rest.strip_suffix(ENCAPSULATION_BOUNDARY_DELIMITER))
+            .ok_or(Error::PreEncapsulationBoundary)
+            .and_then(|label| {
+                grammar::validate_label(label)?;
+                String::from_utf8(label.to_vec()).map_err(|_| Error::Label)
+            })?;
+
+        // Read ahead the first line of encapsulated text
+        let line_limit = line_width.max(end_line_len(&type_label)?);
+
+        match read_line(&mut reader, &mut line, line_limit, false)? {
+            Some(len) if len <= line_limit => (),
+            Some(_) => return Err(Error::Base64(base64ct::Error::InvalidLength).into()),
+            None => return Err(Error::PostEncapsulationBoundary.into()),
+        }
+
+        if line.starts_with(POST_ENCAPSULATION_BOUNDARY) {
+            return Err(Error::EncapsulatedText.into());
+        }
+
+        Ok(Self {
+            reader,
+            type_label,
+            line_width,
+            line,
+            base64: Vec::new(),
+            decoded: Vec::new(),
+            position: 0,
+            finished: false,
+        })
+    }
+
+    /// Get the PEM type label for the input document.
+    pub fn type_label(&self) -> &str {
+        &self.type_label
+    }
+
+    /// Has the post-encapsulation boundary been reached and all of the
+    /// decoded data been read?
+    pub fn is_finished(&self) -> bool {
+        self.finished && self.position == self.decoded.len()
+    }
+
+    /// Get the inner reader.
+    ///
+    /// Once the decoder is finished, it is positioned immediately after the
+    /// post-encapsulation boundary, e.g. at the start of the next document.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Decode the next line of encapsulated text.
+    fn decode_line(&mut self) -> io::Result<()> {
+        let line_limit = self.line_width.max(end_line_len(&self.type_label)?);
+        let mut next_line = Vec::new();
+
+        let is_last = match read_line(&mut self.reader, &mut next_line, line_limit, false)? {
+            // Base64 never contains '-', so this only reveals where the encapsulated text ends
+            Some(len) if len <= line_limit => next_line.starts_with(POST_ENCAPSULATION_BOUNDARY),
+            Some(_) => return Err(Error::Base64(base64ct::Error::InvalidLength).into()),
+            None => return Err(Error::PostEncapsulationBoundary.into()),
+        };
+
+        // Every line but the last must be exactly `line_width` characters
+        let line_len_ok = if is_last {
+            self.line.len() <= self.line_width
+        } else {
+            self.line.len() == self.line_width
+        };
+
+        if !line_len_ok {
+            return Err(check_for_headers(
+                &self.line,
+                Error::Base64(base64ct::Error::InvalidLength),
+            )
+            .into());
+        }
+
+        self.base64.extend_from_slice(&self.line);
+
+        // Only decode complete blocks until the last line has been reached
+        let base64_len = if is_last {
+            self.base64.len()
+        } else {
+            self.base64.len() & !3
+        };
+
+        let decoded_len = base64_len
+            .checked_div(4)
+            .and_then(|blocks| blocks.checked_mul(3))
+            .ok_or(Error::Length)?;
+
+        self.decoded.resize(decoded_len, 0);
+        self.position = 0;
+
+        let block = self.base64.get(..base64_len).ok_or(Error::Length)?;
+        let decoded_len = match Base64::decode(block, &mut self.decoded) {
+            // Padding may only appear in the last line
+            Ok(decoded) if is_last || !block.ends_with(b"=") => decoded.len(),
+            Ok(_) => return Err(Error::Base64(base64ct::Error::InvalidEncoding).into()),
+            Err(err) => return Err(check_for_headers(&self.line, err.into()).into()),
+        };
+
+        self.decoded.truncate(decoded_len);
+        self.base64.drain(..base64_len);
+
+        if is_last {
+            let label = next_line
+                .strip_prefix(POST_ENCAPSULATION_BOUNDARY)
+                .and_then(|rest| rest.strip_suffix(ENCAPSULATION_BOUNDARY_DELIMITER));
+
+            if label != Some(self.type_label.as_bytes()) {
+                return Err(Error::PostEncapsulationBoundary.into());
+            }
+
+            self.finished = true;
+        } else {
+            self.line = next_line;
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: BufRead> io::Read for StreamDecoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.position == self.decoded.len() {
+            if self.finished || buf.is_empty() {
+                return Ok(0);
+            }
+
+            self.decode_line()?;
+        }
+
+        let remaining = self.decoded.get(self.position..).unwrap_or_default();
+        let len = remaining.len().min(buf.len());
+        buf[..len].copy_from_slice(&remaining[..len]);
+        self.position = self.position.checked_add(len).ok_or(Error::Length)?;
+        Ok(len)
+    }
+}
+
+/// Streaming PEM encoder.
+///
+/// Writes a PEM document to an [`io::Write`] according to RFC 7468's "Strict"
+/// grammar, Base64 encoding the data written through its [`io::Write`] impl
+/// and wrapping it into lines as it goes.
+///
+/// The pre-encapsulation boundary is written when the encoder is constructed.
+/// [`StreamEncoder::finish`] MUST be called once all of the data has been
+/// written in order to emit the final line and the post-encapsulation boundary.
+pub struct StreamEncoder<W> {
+    /// Inner writer.
+    writer: W,
+
+    /// PEM type label.
+    type_label: String,
+
+    /// Width at which the Base64 body is wrapped.
+    line_width: usize,
+
+    /// Line ending used to wrap Base64.
+    line_ending: LineEnding,
+
+    /// Input which hasn't been encoded yet as it doesn't form a complete
+    /// 3-byte block.
+    pending: [u8; 3],
+
+    /// Number of bytes in `pending`.
+    pending_len: usize,
+
+    /// Encoded characters of the current line.
+    line: Vec<u8>,
+
+    /// Has any encapsulated text been written?
+    started: bool,
+}
+
+impl<W: io::Write> StreamEncoder<W> {
+    /// Create a new [`StreamEncoder`] with the default options, writing the
+    /// pre-encapsulation boundary to `writer`.
+    ///
+    /// Uses the default 64-character line wrapping.
+    pub fn new(type_label: &str, line_ending: LineEnding, writer: W) -> io::Result<Self> {
+        Self::new_wrapped(type_label, BASE64_WRAP_WIDTH, line_ending, writer)
+    }
+
+    /// Create a new [`StreamEncoder`] which wraps at the given line width.
+    ///
+    /// Note that per [RFC7468 § 2] encoding PEM with any other wrap width besides
+    /// 64 is technically non-compliant.
+    ///
+    /// [RFC7468 § 2]: https://datatracker.ietf.org/doc/html/rfc7468#section-2
+    pub fn new_wrapped(
+        type_label: &str,
+        line_width: usize,
+        line_ending: LineEnding,
+        mut writer: W,
+    ) -> io::Result<Self> {
+        grammar::validate_label(type_label.as_bytes())?;
+
+        if line_width < 4 {
+            return Err(Error::Length.into());
+        }
+
+        for boundary_part in [
+            PRE_ENCAPSULATION_BOUNDARY,
+            type_label.as_bytes(),
+            ENCAPSULATION_BOUNDARY_DELIMITER,
+            line_ending.as_bytes(),
+        ] {
+            writer.write_all(boundary_part)?;
+        }
+
+        Ok(Self {
+            writer,
+            type_label: type_label.into(),
+            line_width,
+            line_ending,
+            pending: [0; 3],
+            pending_len: 0,
+            line: Vec::with_capacity(line_width),
+            started: false,
+        })
+    }
+
+    /// Get the PEM type label used for this document.
+    pub fn type_label(&self) -> &str {
+        &self.type_label
+    }
+
+    /// Finish encoding PEM, writing the remaining encapsulated text and the
+    /// post-encapsulation boundary.
+    ///
+    /// On success, returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        let pending = self.pending.get(..self.pending_len).unwrap_or_default();
+        let mut encoded = [0u8; 4];
+        let encoded = Base64::encode(pending, &mut encoded).map_err(Error::from)?;
+        self.write_encoded(encoded.as_bytes())?;
+
+        // Write the last line, unless it was already written out in full
+        if !self.line.is_empty() || !self.started {
+            self.writer.write_all(&self.line)?;
+            self.writer.write_all(self.line_ending.as_bytes())?;
+        }
+
+        for boundary_part in [
+            POST_ENCAPSULATION_BOUNDARY,
+            self.type_label.as_bytes(),
+            ENCAPSULATION_BOUNDARY_DELIMITER,
+            self.line_ending.as_bytes(),
+        ] {
+            self.writer.write_all(boundary_part)?;
+        }
+
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    /// Write Base64 encoded characters, wrapping them into lines.
+    fn write_encoded(&mut self, mut encoded: &[u8]) -> io::Result<()> {
+        while !encoded.is_empty() {
+            let len = self
+                .line_width
+                .saturating_sub(self.line.len())
+                .min(encoded.len());
+            let (chars, rest) = encoded.split_at(len);
+            self.line.extend_from_slice(chars);
+            encoded = rest;
+
+            if self.line.len() == self.line_width {
+                self.writer.write_all(&self.line)?;
+                self.writer.write_all(self.line_ending.as_bytes())?;
+                self.line.clear();
+                self.started = true;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<W: io::Write> io::Write for StreamEncoder<W> {
+    fn write(&mut self, mut buf: &[u8]) -> io::Result<usize> {
+        let input_len = buf.len();
+        let mut encoded = [0u8; ENCODE_CHUNK_LEN / 3 * 4];
+
+        // Complete the pending block
+        if self.pending_len != 0 {
+            let len = 3usize.saturating_sub(self.pending_len).min(buf.len());
+            let (input, rest) = buf.split_at(len);
+            self.pending[self.pending_len..][..len].copy_from_slice(input);
+            self.pending_len = self.pending_len.saturating_add(len);
+            buf = rest;
+
+            if self.pending_len < 3 {
+                return Ok(input_len);
+            }
+
+            let pending = self.pending;
+            let chars = Base64::encode(&pending, &mut encoded).map_err(Error::from)?;
+            self.write_encoded(chars.as_bytes())?;
+            self.pending_len = 0;
+        }
+
+        let mut chunks = buf.chunks_exact(ENCODE_CHUNK_LEN);
+
+        for chunk in &mut chunks {
+            let chars = Base64::encode(chunk, &mut encoded).map_err(Error::from)?;
+            self.write_encoded(chars.as_bytes())?;
+        }
+
+        let remainder = chunks.remainder();
+        let (blocks, rest) =
+            remainder.split_at(remainder.len().saturating_sub(remainder.len() % 3));
+        let chars = Base64::encode(blocks, &mut encoded).map_err(Error::from)?;
+        self.write_encoded(chars.as_bytes())?;
+
+        self.pending[..rest.len()].copy_from_slice(rest);
+        self.pending_len = rest.len();
+        Ok(input_len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Read a line terminated by CRLF, LF, or CR (or the end of the input) into
+/// `line`, retaining at most `limit` bytes of it.
+///
+/// Returns the full length of the line, or `None` at the end of the input.
+///
+/// NUL bytes are rejected when `reject_nul` is set, as is the case for the
+/// preamble.
+fn read_line<R: BufRead>(
+    reader: &mut R,
+    line: &mut Vec<u8>,
+    limit: usize,
+    reject_nul: bool,
+) -> io::Result<Option<usize>> {
+    line.clear();
+    let mut line_len = 0usize;
+    let mut eof = true;
+
+    loop {
+        let buf = reader.fill_buf()?;
+
+        if buf.is_empty() {
+            return Ok(if eof { None } else { Some(line_len) });
+        }
+
+        eof = false;
+
+        let eol = buf
+            .iter()
+            .position(|&c| matches!(c, grammar::CHAR_CR | grammar::CHAR_LF));
+        let (chunk, consumed) = match eol {
+            Some(pos) => (&buf[..pos], pos.saturating_add(1)),
+            None => (buf, buf.len()),
+        };
+
+        if reject_nul && chunk.contains(&grammar::CHAR_NUL) {
+            return Err(Error::Preamble.into());
+        }
+
+        let retained = limit.saturating_sub(line.len()).min(chunk.len());
+        line.extend_from_slice(&chunk[..retained]);
+        line_len = line_len.checked_add(chunk.len()).ok_or(Error::Length)?;
+
+        let is_cr = eol.map(|pos| buf[pos]) == Some(grammar::CHAR_CR);
+        reader.consume(consumed);
+
+        if eol.is_some() {
+            // Treat CRLF as a single line ending
+            if is_cr && reader.fill_buf()?.first() == Some(&grammar::CHAR_LF) {
+                reader.consume(1);
+            }
+
+            return Ok(Some(line_len));
+        }
+    }
+}
+
+/// Compute the length of the post-encapsulation boundary for the given label.
+fn end_line_len(type_label: &str) -> Result<usize> {
+    [
+        POST_ENCAPSULATION_BOUNDARY.len(),
+        type_label.len(),
+        ENCAPSULATION_BOUNDARY_DELIMITER.len(),
+    ]
+    .into_iter()
+    .try_fold(0usize, |acc, len| acc.checked_add(len))
+    .ok_or(Error::Length)
+}
+
+/// Check for PEM headers in a line of encapsulated text, as they are
+/// disallowed by RFC7468.
+fn check_for_headers(line: &[u8], err: Error) -> Error {
+    if line.contains(&grammar::CHAR_COLON) {
+        Error::HeaderDisallowed
+    } else {
+        err
+    }
+}
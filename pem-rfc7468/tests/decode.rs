@@ -125,3 +125,221 @@ fn line_width_detection() {
         70
     );
 }
+
+#[test]
+#[cfg(feature = "std")]
+fn stream_decoder() {
+    use std::io::Read;
+
+    for (pem, label, der) in [
+        (
+            &include_bytes!("examples/pkcs1_with_preceeding_junk.pem")[..],
+            "RSA PRIVATE KEY",
+            &include_bytes!("examples/pkcs1.der")[..],
+        ),
+        (
+            &include_bytes!("examples/pkcs8-enc.pem")[..],
+            "ENCRYPTED PRIVATE KEY",
+            &include_bytes!("examples/pkcs8-enc.der")[..],
+        ),
+    ] {
+        let mut decoder = pem_rfc7468::StreamDecoder::new(pem).unwrap();
+        assert_eq!(decoder.type_label(), label);
+
+        // read in chunks which don't line up with the Base64 lines
+        let mut decoded = Vec::new();
+        let mut buf = [0u8; 7];
+        while !decoder.is_finished() {
+            let len = decoder.read(&mut buf).unwrap();
+            decoded.extend_from_slice(&buf[..len]);
+        }
+        assert_eq!(decoded, der);
+        assert_eq!(decoder.read(&mut buf).unwrap(), 0);
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn stream_decoder_concatenated() {
+    use std::io::Read;
+
+    let pem = [
+        &include_bytes!("examples/pkcs1.pem")[..],
+        &include_bytes!("examples/pkcs8.pem")[..],
+    ]
+    .concat();
+
+    let mut decoder = pem_rfc7468::StreamDecoder::new(&pem[..]).unwrap();
+    let mut decoded = Vec::new();
+    decoder.read_to_end(&mut decoded).unwrap();
+    assert_eq!(decoded, include_bytes!("examples/pkcs1.der"));
+
+    let mut decoder = pem_rfc7468::StreamDecoder::new(decoder.into_inner()).unwrap();
+    assert_eq!(decoder.type_label(), "PRIVATE KEY");
+    decoded.clear();
+    decoder.read_to_end(&mut decoded).unwrap();
+    assert_eq!(decoded, include_bytes!("examples/pkcs8.der"));
+    assert!(decoder.into_inner().is_empty());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn stream_decoder_wrapped() {
+    use std::io::Read;
+
+    let pem = include_bytes!("examples/ssh-id_ed25519.pem");
+    let mut expected = Vec::new();
+    pem_rfc7468::Decoder::new_wrapped(pem, 70)
+        .unwrap()
+        .read_to_end(&mut expected)
+        .unwrap();
+
+    let mut decoder = pem_rfc7468::StreamDecoder::new_wrapped(&pem[..], 70).unwrap();
+    let mut decoded = Vec::new();
+    decoder.read_to_end(&mut decoded).unwrap();
+    assert_eq!(decoded, expected);
+
+    // the default line width doesn't match
+    assert!(pem_rfc7468::StreamDecoder::new(&pem[..]).is_err());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn stream_decoder_errors() {
+    use pem_rfc7468::Error;
+    use std::io::Read;
+
+    fn decode_io_err(pem: &[u8]) -> std::io::Error {
+        match pem_rfc7468::StreamDecoder::new(pem) {
+            Ok(mut decoder) => decoder.read_to_end(&mut Vec::new()).unwrap_err(),
+            Err(err) => err,
+        }
+    }
+
+    fn decode_err(pem: &[u8]) -> Error {
+        *decode_io_err(pem)
+            .into_inner()
+            .unwrap()
+            .downcast::<Error>()
+            .unwrap()
+    }
+
+    let pem = include_str!("examples/pkcs8.pem");
+    assert_eq!(
+        decode_err(pem.replace("END PRIVATE", "END PUBLIC").as_bytes()),
+        Error::PostEncapsulationBoundary
+    );
+    assert_eq!(
+        decode_err(pem.replace("-----END PRIVATE KEY-----\n", "").as_bytes()),
+        Error::PostEncapsulationBoundary
+    );
+    assert_eq!(
+        decode_err(include_bytes!("examples/pkcs1.der")),
+        Error::Preamble
+    );
+    assert_eq!(
+        decode_err(include_bytes!("examples/ssh_rsa_pem_password.pem")),
+        Error::HeaderDisallowed
+    );
+    assert_eq!(
+        decode_err(include_bytes!("examples/chosen_header.pem")),
+        Error::HeaderDisallowed
+    );
+
+    // lines which aren't wrapped at 64 characters
+    let pem = include_str!("examples/pkcs1.pem");
+    let mut lines = pem.lines().collect::<Vec<_>>();
+    let joined = [lines[1], lines[2]].concat();
+    lines.splice(1..3, [&joined[..63], &joined[63..]]);
+    assert_eq!(
+        decode_io_err(lines.join("\n").as_bytes()).kind(),
+        std::io::ErrorKind::InvalidData
+    );
+}
+
+#[test]
+fn documents() {
+    let pem = include_bytes!("examples/bundle.pem");
+    let mut documents = pem_rfc7468::Documents::new(pem);
+    let mut buf = [0u8; 2048];
+
+    let (label, range) = documents.next().unwrap().unwrap();
+    assert_eq!(label, "RSA PRIVATE KEY");
+    assert_eq!(&pem[range.clone()], include_bytes!("examples/pkcs1.pem"));
+    let (_, decoded) = pem_rfc7468::decode(&pem[range], &mut buf).unwrap();
+    assert_eq!(decoded, include_bytes!("examples/pkcs1.der"));
+
+    let (label, range) = documents.next().unwrap().unwrap();
+    assert_eq!(label, "PRIVATE KEY");
+    assert_eq!(&pem[range.clone()], include_bytes!("examples/pkcs8.pem"));
+    assert_eq!(range.end, pem.len());
+
+    assert!(documents.next().is_none());
+    assert_eq!(pem_rfc7468::Documents::new(b"no PEM here\n").count(), 0);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn decode_multiple() {
+    // CRLF line endings, and no trailing newline after the last document
+    let pem = String::from_utf8(include_bytes!("examples/bundle.pem").to_vec())
+        .unwrap()
+        .replace('\n', "\r\n");
+    let pem = [&pem, "# Second copy\r\n", pem.trim_end()].concat();
+
+    let documents = pem_rfc7468::decode_multiple(pem.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(documents.len(), 4);
+
+    for pair in documents.chunks(2) {
+        assert_eq!(pair[0].0, "RSA PRIVATE KEY");
+        assert_eq!(pair[0].1, include_bytes!("examples/pkcs1.der"));
+        assert_eq!(pair[1].0, "PRIVATE KEY");
+        assert_eq!(pair[1].1, include_bytes!("examples/pkcs8.der"));
+    }
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn decode_multiple_errors() {
+    use pem_rfc7468::Error;
+
+    let bundle = include_str!("examples/bundle.pem");
+
+    let decode_err = |pem: &str| {
+        pem_rfc7468::decode_multiple(pem.as_bytes())
+            .find_map(Result::err)
+            .unwrap()
+    };
+
+    // label mismatch in the second document
+    let err = decode_err(&bundle.replace("END PRIVATE KEY", "END PUBLIC KEY"));
+    assert_eq!(err.error(), Error::PostEncapsulationBoundary);
+    assert_eq!((err.index(), err.line()), (1, 33));
+
+    // missing post-encapsulation boundary
+    let err = decode_err(&bundle.replace("-----END RSA PRIVATE KEY-----", ""));
+    assert_eq!(err.error(), Error::PostEncapsulationBoundary);
+    assert_eq!((err.index(), err.line()), (0, 3));
+
+    // headers in the third document
+    let pem = [bundle, include_str!("examples/ssh_rsa_pem_password.pem")].concat();
+    let err = decode_err(&pem);
+    assert_eq!(err.error(), Error::HeaderDisallowed);
+    assert_eq!((err.index(), err.line()), (2, 36));
+    assert_eq!(
+        err.to_string(),
+        "PEM headers disallowed by RFC7468 (document 2 at line 36)"
+    );
+
+    // binary data between documents
+    let pem = [bundle, "\0"].concat();
+    let mut documents = pem_rfc7468::decode_multiple(pem.as_bytes());
+    assert!(documents.next().unwrap().is_ok());
+    assert!(documents.next().unwrap().is_ok());
+    let err = documents.next().unwrap().unwrap_err();
+    assert_eq!(err.error(), Error::Preamble);
+    assert_eq!((err.index(), err.line()), (2, 36));
+    assert!(documents.next().is_none());
+}
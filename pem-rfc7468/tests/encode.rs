@@ -19,3 +19,70 @@ fn pkcs8_example() {
     let encoded = pem_rfc7468::encode_string(label, LineEnding::LF, bytes).unwrap();
     assert_eq!(&encoded, include_str!("examples/pkcs8.pem"));
 }
+
+#[test]
+#[cfg(feature = "std")]
+fn stream_encoder() {
+    use std::io::Write;
+
+    for (label, der, line_ending) in [
+        (
+            "RSA PRIVATE KEY",
+            &include_bytes!("examples/pkcs1.der")[..],
+            LineEnding::LF,
+        ),
+        (
+            "PRIVATE KEY",
+            &include_bytes!("examples/pkcs8.der")[..],
+            LineEnding::CRLF,
+        ),
+        ("EMPTY", &[][..], LineEnding::LF),
+        ("ONE LINE", &[0x42; 48][..], LineEnding::LF),
+    ] {
+        let expected = pem_rfc7468::encode_string(label, line_ending, der).unwrap();
+
+        for chunk_len in [1, 2, 5, 48, 100] {
+            let mut encoder =
+                pem_rfc7468::StreamEncoder::new(label, line_ending, Vec::new()).unwrap();
+            assert_eq!(encoder.type_label(), label);
+            for chunk in der.chunks(chunk_len) {
+                encoder.write_all(chunk).unwrap();
+            }
+            let encoded = encoder.finish().unwrap();
+            assert_eq!(String::from_utf8(encoded).unwrap(), expected);
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn stream_encoder_roundtrip() {
+    use std::io::{Read, Write};
+
+    let der = include_bytes!("examples/pkcs1.der");
+    let mut encoder =
+        pem_rfc7468::StreamEncoder::new_wrapped("RSA PRIVATE KEY", 70, LineEnding::CR, Vec::new())
+            .unwrap();
+    encoder.write_all(der).unwrap();
+    let encoded = encoder.finish().unwrap();
+
+    let mut decoder = pem_rfc7468::StreamDecoder::new_wrapped(&encoded[..], 70).unwrap();
+    let mut decoded = Vec::new();
+    decoder.read_to_end(&mut decoded).unwrap();
+    assert_eq!(decoded, der);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn stream_encoder_invalid_label() {
+    let err = pem_rfc7468::StreamEncoder::new("PRIVATE  KEY", LineEnding::LF, Vec::new())
+        .err()
+        .unwrap();
+    assert_eq!(
+        *err.into_inner()
+            .unwrap()
+            .downcast::<pem_rfc7468::Error>()
+            .unwrap(),
+        pem_rfc7468::Error::Label
+    );
+}
@@ -0,0 +1,104 @@
+//! Conversions between PKCS#1 and PKCS#8 / SPKI documents.
+//!
+//! PKCS#8 private keys and SPKI public keys wrap the PKCS#1 key along with the
+//! `rsaEncryption` algorithm identifier, whose parameters are `NULL`.
+//!
+//! Decoding checks the algorithm identifier, and the PEM functions also
+//! check the label of their input.
+
+use crate::ALGORITHM_OID;
+use der::{asn1::AnyRef, Tag};
+use spki::AlgorithmIdentifierRef;
+
+#[cfg(feature = "alloc")]
+use {
+    crate::{RsaPrivateKey, RsaPublicKey},
+    der::{Decode, Document, SecretDocument},
+    pkcs8::{EncodePrivateKey, EncodePublicKey},
+};
+
+#[cfg(feature = "alloc")]
+use crate::Result;
+
+#[cfg(feature = "pem")]
+use {
+    crate::LineEnding,
+    alloc::string::String,
+    der::{pem::PemLabel, zeroize::Zeroizing},
+};
+
+/// Wrap a PKCS#1 `RSAPrivateKey` into a PKCS#8 `PrivateKeyInfo`.
+#[cfg(feature = "alloc")]
+pub fn private_key_to_pkcs8_der(pkcs1_der: &[u8]) -> Result<SecretDocument> {
+    Ok(RsaPrivateKey::from_der(pkcs1_der)?.to_pkcs8_der()?)
+}
+
+/// Extract a PKCS#1 `RSAPrivateKey` from a PKCS#8 `PrivateKeyInfo`.
+#[cfg(feature = "alloc")]
+pub fn private_key_from_pkcs8_der(pkcs8_der: &[u8]) -> Result<SecretDocument> {
+    let private_key_info = pkcs8::PrivateKeyInfoRef::from_der(pkcs8_der)?;
+    RsaPrivateKey::try_from(private_key_info)?.try_into()
+}
+
+/// Convert a `RSA PRIVATE KEY` PEM document into a `PRIVATE KEY` one.
+#[cfg(feature = "pem")]
+pub fn private_key_to_pkcs8_pem(
+    pkcs1_pem: &str,
+    line_ending: LineEnding,
+) -> Result<Zeroizing<String>> {
+    let (label, doc) = SecretDocument::from_pem(pkcs1_pem)?;
+    RsaPrivateKey::validate_pem_label(label)?;
+    Ok(RsaPrivateKey::from_der(doc.as_bytes())?.to_pkcs8_pem(line_ending)?)
+}
+
+/// Convert a `PRIVATE KEY` PEM document into a `RSA PRIVATE KEY` one.
+#[cfg(feature = "pem")]
+pub fn private_key_from_pkcs8_pem(
+    pkcs8_pem: &str,
+    line_ending: LineEnding,
+) -> Result<Zeroizing<String>> {
+    let (label, doc) = SecretDocument::from_pem(pkcs8_pem)?;
+    pkcs8::PrivateKeyInfoRef::validate_pem_label(label)?;
+    let doc = private_key_from_pkcs8_der(doc.as_bytes())?;
+    Ok(doc.to_pem(RsaPrivateKey::PEM_LABEL, line_ending)?)
+}
+
+/// Wrap a PKCS#1 `RSAPublicKey` into a `SubjectPublicKeyInfo`.
+#[cfg(feature = "alloc")]
+pub fn public_key_to_spki_der(pkcs1_der: &[u8]) -> Result<Document> {
+    Ok(RsaPublicKey::from_der(pkcs1_der)?.to_public_key_der()?)
+}
+
+/// Extract a PKCS#1 `RSAPublicKey` from a `SubjectPublicKeyInfo`.
+#[cfg(feature = "alloc")]
+pub fn public_key_from_spki_der(spki_der: &[u8]) -> Result<Document> {
+    let spki = pkcs8::SubjectPublicKeyInfoRef::from_der(spki_der)?;
+    RsaPublicKey::try_from(spki)?.try_into()
+}
+
+/// Convert a `RSA PUBLIC KEY` PEM document into a `PUBLIC KEY` one.
+#[cfg(feature = "pem")]
+pub fn public_key_to_spki_pem(pkcs1_pem: &str, line_ending: LineEnding) -> Result<String> {
+    let (label, doc) = Document::from_pem(pkcs1_pem)?;
+    RsaPublicKey::validate_pem_label(label)?;
+    Ok(RsaPublicKey::from_der(doc.as_bytes())?.to_public_key_pem(line_ending)?)
+}
+
+/// Convert a `PUBLIC KEY` PEM document into a `RSA PUBLIC KEY` one.
+#[cfg(feature = "pem")]
+pub fn public_key_from_spki_pem(spki_pem: &str, line_ending: LineEnding) -> Result<String> {
+    let (label, doc) = Document::from_pem(spki_pem)?;
+    pkcs8::SubjectPublicKeyInfoRef::validate_pem_label(label)?;
+    let doc = public_key_from_spki_der(doc.as_bytes())?;
+    Ok(doc.to_pem(RsaPublicKey::PEM_LABEL, line_ending)?)
+}
+
+/// Check that `algorithm` is `rsaEncryption` with `NULL` (or absent) parameters.
+pub(crate) fn check_algorithm(algorithm: &AlgorithmIdentifierRef<'_>) -> spki::Result<()> {
+    algorithm.assert_algorithm_oid(ALGORITHM_OID)?;
+
+    match algorithm.parameters {
+        None | Some(AnyRef::NULL) => Ok(()),
+        Some(_) => Err(Tag::Null.value_error().into()),
+    }
+}
@@ -20,6 +20,9 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+#[cfg(feature = "pkcs8")]
+pub mod convert;
+
 mod error;
 mod params;
 mod private_key;
@@ -34,7 +37,7 @@ pub use der::{
 
 pub use crate::{
     error::{Error, Result},
-    params::{RsaOaepParams, RsaPssParams, TrailerField},
+    params::{RsaOaepParams, RsaPssParams, TrailerField, RSAES_OAEP_OID, RSASSA_PSS_OID},
     private_key::RsaPrivateKey,
     public_key::RsaPublicKey,
     traits::{DecodeRsaPrivateKey, DecodeRsaPublicKey},
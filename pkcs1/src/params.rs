@@ -9,7 +9,19 @@ use der::{
 };
 use spki::{AlgorithmIdentifier, AlgorithmIdentifierRef};
 
+#[cfg(feature = "alloc")]
+use {der::asn1::Any, spki::AlgorithmIdentifierOwned};
+
+/// `id-RSASSA-PSS` Object Identifier (OID)
+pub const RSASSA_PSS_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.10");
+
+/// `id-RSAES-OAEP` Object Identifier (OID)
+pub const RSAES_OAEP_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.7");
+
 const OID_SHA_1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.14.3.2.26");
+const OID_SHA_256: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.1");
+const OID_SHA_384: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.2");
+const OID_SHA_512: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.3");
 const OID_MGF_1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.8");
 const OID_PSPECIFIED: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.9");
 
@@ -100,18 +112,37 @@ impl<'a> RsaPssParams<'a> {
     where
         D: AssociatedOid,
     {
+        Self::from_hash_oid(D::OID, salt_len)
+    }
+
+    /// RSASSA-PSS parameters for SHA-256 with MGF1-SHA-256 and a 32-byte salt,
+    /// as recommended by [RFC 4055 Section 3.1].
+    ///
+    /// [RFC 4055 Section 3.1]: https://datatracker.ietf.org/doc/html/rfc4055#section-3.1
+    pub fn sha256() -> Self {
+        Self::from_hash_oid(OID_SHA_256, 32)
+    }
+
+    /// RSASSA-PSS parameters for SHA-384 with MGF1-SHA-384 and a 48-byte salt,
+    /// as recommended by [RFC 4055 Section 3.1].
+    ///
+    /// [RFC 4055 Section 3.1]: https://datatracker.ietf.org/doc/html/rfc4055#section-3.1
+    pub fn sha384() -> Self {
+        Self::from_hash_oid(OID_SHA_384, 48)
+    }
+
+    /// RSASSA-PSS parameters for SHA-512 with MGF1-SHA-512 and a 64-byte salt,
+    /// as recommended by [RFC 4055 Section 3.1].
+    ///
+    /// [RFC 4055 Section 3.1]: https://datatracker.ietf.org/doc/html/rfc4055#section-3.1
+    pub fn sha512() -> Self {
+        Self::from_hash_oid(OID_SHA_512, 64)
+    }
+
+    fn from_hash_oid(oid: ObjectIdentifier, salt_len: u8) -> Self {
         Self {
-            hash: AlgorithmIdentifierRef {
-                oid: D::OID,
-                parameters: Some(AnyRef::NULL),
-            },
-            mask_gen: AlgorithmIdentifier {
-                oid: OID_MGF_1,
-                parameters: Some(AlgorithmIdentifierRef {
-                    oid: D::OID,
-                    parameters: Some(AnyRef::NULL),
-                }),
-            },
+            hash: hash_algorithm_identifier(oid),
+            mask_gen: mgf1_algorithm_identifier(oid),
             salt_len,
             trailer_field: Default::default(),
         }
@@ -229,6 +260,28 @@ impl<'a> TryFrom<&'a [u8]> for RsaPssParams<'a> {
     }
 }
 
+impl<'a> TryFrom<AlgorithmIdentifierRef<'a>> for RsaPssParams<'a> {
+    type Error = Error;
+
+    /// Decode the parameters of an `id-RSASSA-PSS` algorithm identifier.
+    fn try_from(alg: AlgorithmIdentifierRef<'a>) -> Result<Self> {
+        algorithm_parameters(alg, RSASSA_PSS_OID)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<&RsaPssParams<'_>> for AlgorithmIdentifierOwned {
+    type Error = Error;
+
+    /// Encode the parameters as an `id-RSASSA-PSS` algorithm identifier.
+    fn try_from(params: &RsaPssParams<'_>) -> Result<Self> {
+        Ok(Self {
+            oid: RSASSA_PSS_OID,
+            parameters: Some(Any::encode_from(params)?),
+        })
+    }
+}
+
 /// Default Mask Generation Function (MGF): SHA-1.
 fn default_mgf1_sha1<'a>() -> AlgorithmIdentifier<AlgorithmIdentifierRef<'a>> {
     AlgorithmIdentifier::<AlgorithmIdentifierRef<'a>> {
@@ -278,19 +331,29 @@ impl<'a> RsaOaepParams<'a> {
     where
         D: AssociatedOid,
     {
+        Self::from_hash_oid(D::OID, pspecicied_algorithm_identifier(label))
+    }
+
+    /// RSAES-OAEP parameters for SHA-256 with MGF1-SHA-256 and an empty label.
+    pub fn sha256() -> Self {
+        Self::from_hash_oid(OID_SHA_256, default_pempty_string())
+    }
+
+    /// RSAES-OAEP parameters for SHA-384 with MGF1-SHA-384 and an empty label.
+    pub fn sha384() -> Self {
+        Self::from_hash_oid(OID_SHA_384, default_pempty_string())
+    }
+
+    /// RSAES-OAEP parameters for SHA-512 with MGF1-SHA-512 and an empty label.
+    pub fn sha512() -> Self {
+        Self::from_hash_oid(OID_SHA_512, default_pempty_string())
+    }
+
+    fn from_hash_oid(oid: ObjectIdentifier, p_source: AlgorithmIdentifierRef<'a>) -> Self {
         Self {
-            hash: AlgorithmIdentifierRef {
-                oid: D::OID,
-                parameters: Some(AnyRef::NULL),
-            },
-            mask_gen: AlgorithmIdentifier {
-                oid: OID_MGF_1,
-                parameters: Some(AlgorithmIdentifierRef {
-                    oid: D::OID,
-                    parameters: Some(AnyRef::NULL),
-                }),
-            },
-            p_source: pspecicied_algorithm_identifier(label),
+            hash: hash_algorithm_identifier(oid),
+            mask_gen: mgf1_algorithm_identifier(oid),
+            p_source,
         }
     }
 
@@ -389,6 +452,28 @@ impl<'a> TryFrom<&'a [u8]> for RsaOaepParams<'a> {
     }
 }
 
+impl<'a> TryFrom<AlgorithmIdentifierRef<'a>> for RsaOaepParams<'a> {
+    type Error = Error;
+
+    /// Decode the parameters of an `id-RSAES-OAEP` algorithm identifier.
+    fn try_from(alg: AlgorithmIdentifierRef<'a>) -> Result<Self> {
+        algorithm_parameters(alg, RSAES_OAEP_OID)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl TryFrom<&RsaOaepParams<'_>> for AlgorithmIdentifierOwned {
+    type Error = Error;
+
+    /// Encode the parameters as an `id-RSAES-OAEP` algorithm identifier.
+    fn try_from(params: &RsaOaepParams<'_>) -> Result<Self> {
+        Ok(Self {
+            oid: RSAES_OAEP_OID,
+            parameters: Some(Any::encode_from(params)?),
+        })
+    }
+}
+
 fn pspecicied_algorithm_identifier(label: &impl AsRef<[u8]>) -> AlgorithmIdentifierRef<'_> {
     AlgorithmIdentifierRef {
         oid: OID_PSPECIFIED,
@@ -402,3 +487,40 @@ fn pspecicied_algorithm_identifier(label: &impl AsRef<[u8]>) -> AlgorithmIdentif
 fn default_pempty_string<'a>() -> AlgorithmIdentifierRef<'a> {
     pspecicied_algorithm_identifier(&[])
 }
+
+/// Hash algorithm identifier with `NULL` parameters, as required by
+/// [RFC 4055 Section 2.1].
+///
+/// [RFC 4055 Section 2.1]: https://datatracker.ietf.org/doc/html/rfc4055#section-2.1
+fn hash_algorithm_identifier<'a>(oid: ObjectIdentifier) -> AlgorithmIdentifierRef<'a> {
+    AlgorithmIdentifierRef {
+        oid,
+        parameters: Some(AnyRef::NULL),
+    }
+}
+
+/// MGF1 algorithm identifier using the given hash.
+fn mgf1_algorithm_identifier<'a>(
+    oid: ObjectIdentifier,
+) -> AlgorithmIdentifier<AlgorithmIdentifierRef<'a>> {
+    AlgorithmIdentifier {
+        oid: OID_MGF_1,
+        parameters: Some(hash_algorithm_identifier(oid)),
+    }
+}
+
+/// Decode the parameters of an algorithm identifier with the given OID.
+///
+/// The parameters are mandatory: when absent from a public key's algorithm
+/// identifier, the key isn't restricted to specific parameters.
+fn algorithm_parameters<'a, T>(alg: AlgorithmIdentifierRef<'a>, oid: ObjectIdentifier) -> Result<T>
+where
+    T: Sequence<'a> + DecodeValue<'a, Error = der::Error> + 'a,
+{
+    if alg.oid != oid {
+        return Err(der::Error::from(der::ErrorKind::OidUnknown { oid: alg.oid }).into());
+    }
+
+    let params = alg.parameters.ok_or(Tag::Sequence.value_error())?;
+    Ok(params.decode_as()?)
+}
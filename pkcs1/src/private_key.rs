@@ -116,6 +116,12 @@ impl<'a> DecodeValue<'a> for RsaPrivateKey<'a> {
                 return Err(reader.error(der::ErrorKind::Value { tag: Tag::Integer }));
             }
 
+            // `OtherPrimeInfos` is `SEQUENCE SIZE(1..MAX)`.
+            #[cfg(feature = "alloc")]
+            if result.other_prime_infos.as_ref().is_some_and(Vec::is_empty) {
+                return Err(reader.error(der::ErrorKind::Value { tag: Tag::Sequence }));
+            }
+
             Ok(result)
         })
     }
@@ -208,6 +214,27 @@ impl TryFrom<&RsaPrivateKey<'_>> for SecretDocument {
     }
 }
 
+#[cfg(feature = "pkcs8")]
+impl<'a> TryFrom<pkcs8::PrivateKeyInfoRef<'a>> for RsaPrivateKey<'a> {
+    type Error = pkcs8::Error;
+
+    /// Extract the PKCS#1 key from a PKCS#8 `rsaEncryption` private key.
+    fn try_from(private_key_info: pkcs8::PrivateKeyInfoRef<'a>) -> pkcs8::Result<Self> {
+        crate::convert::check_algorithm(&private_key_info.algorithm)?;
+        Ok(Self::try_from(private_key_info.private_key)?)
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "pkcs8"))]
+impl pkcs8::EncodePrivateKey for RsaPrivateKey<'_> {
+    /// Wrap this key in a PKCS#8 `rsaEncryption` private key.
+    fn to_pkcs8_der(&self) -> pkcs8::Result<SecretDocument> {
+        let private_key = SecretDocument::try_from(self)?;
+        let private_key = OctetStringRef::new(private_key.as_bytes())?;
+        pkcs8::PrivateKeyInfoRef::new(crate::ALGORITHM_ID, private_key).try_into()
+    }
+}
+
 #[cfg(feature = "pem")]
 impl PemLabel for RsaPrivateKey<'_> {
     const PEM_LABEL: &'static str = "RSA PRIVATE KEY";
@@ -85,6 +85,36 @@ impl TryFrom<&RsaPublicKey<'_>> for Document {
     }
 }
 
+#[cfg(feature = "pkcs8")]
+impl<'a> TryFrom<pkcs8::SubjectPublicKeyInfoRef<'a>> for RsaPublicKey<'a> {
+    type Error = spki::Error;
+
+    /// Extract the PKCS#1 key from an `rsaEncryption` `SubjectPublicKeyInfo`.
+    fn try_from(spki: pkcs8::SubjectPublicKeyInfoRef<'a>) -> spki::Result<Self> {
+        crate::convert::check_algorithm(&spki.algorithm)?;
+        let public_key = spki
+            .subject_public_key
+            .as_bytes()
+            .ok_or(spki::Error::KeyMalformed)?;
+        Ok(Self::from_der(public_key)?)
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "pkcs8"))]
+impl pkcs8::EncodePublicKey for RsaPublicKey<'_> {
+    /// Wrap this key in an `rsaEncryption` `SubjectPublicKeyInfo`.
+    fn to_public_key_der(&self) -> spki::Result<Document> {
+        let public_key = self.to_der()?;
+        let subject_public_key = der::asn1::BitStringRef::from_bytes(&public_key)?;
+
+        pkcs8::SubjectPublicKeyInfoRef {
+            algorithm: crate::ALGORITHM_ID,
+            subject_public_key,
+        }
+        .try_into()
+    }
+}
+
 #[cfg(feature = "pem")]
 impl PemLabel for RsaPublicKey<'_> {
     const PEM_LABEL: &'static str = "RSA PUBLIC KEY";
@@ -19,7 +19,7 @@ use {
 };
 
 #[cfg(feature = "std")]
-use std::path::Path;
+use {der::SecretFileOptions, std::path::Path};
 
 #[cfg(all(feature = "alloc", feature = "pkcs8"))]
 use der::Decode;
@@ -51,13 +51,34 @@ pub trait DecodeRsaPrivateKey: Sized {
     /// filesystem (binary format).
     #[cfg(feature = "std")]
     fn read_pkcs1_der_file(path: impl AsRef<Path>) -> Result<Self> {
-        Self::from_pkcs1_der(SecretDocument::read_der_file(path)?.as_bytes())
+        Self::read_pkcs1_der_file_with_options(path, SecretFileOptions::default())
+    }
+
+    /// Load PKCS#1 private key from an ASN.1 DER-encoded file on the local
+    /// filesystem (binary format), using the given [`SecretFileOptions`].
+    #[cfg(feature = "std")]
+    fn read_pkcs1_der_file_with_options(
+        path: impl AsRef<Path>,
+        options: SecretFileOptions,
+    ) -> Result<Self> {
+        let doc = SecretDocument::read_der_file_with_options(path, options)?;
+        Self::from_pkcs1_der(doc.as_bytes())
     }
 
     /// Load PKCS#1 private key from a PEM-encoded file on the local filesystem.
     #[cfg(all(feature = "pem", feature = "std"))]
     fn read_pkcs1_pem_file(path: impl AsRef<Path>) -> Result<Self> {
-        let (label, doc) = SecretDocument::read_pem_file(path)?;
+        Self::read_pkcs1_pem_file_with_options(path, SecretFileOptions::default())
+    }
+
+    /// Load PKCS#1 private key from a PEM-encoded file on the local filesystem,
+    /// using the given [`SecretFileOptions`].
+    #[cfg(all(feature = "pem", feature = "std"))]
+    fn read_pkcs1_pem_file_with_options(
+        path: impl AsRef<Path>,
+        options: SecretFileOptions,
+    ) -> Result<Self> {
+        let (label, doc) = SecretDocument::read_pem_file_with_options(path, options)?;
         RsaPrivateKey::validate_pem_label(&label)?;
         Self::from_pkcs1_der(doc.as_bytes())
     }
@@ -116,14 +137,39 @@ pub trait EncodeRsaPrivateKey {
     /// Write ASN.1 DER-encoded PKCS#1 private key to the given path.
     #[cfg(feature = "std")]
     fn write_pkcs1_der_file(&self, path: impl AsRef<Path>) -> Result<()> {
-        Ok(self.to_pkcs1_der()?.write_der_file(path)?)
+        self.write_pkcs1_der_file_with_options(path, SecretFileOptions::default())
+    }
+
+    /// Write ASN.1 DER-encoded PKCS#1 private key to the given path, using the
+    /// given [`SecretFileOptions`].
+    #[cfg(feature = "std")]
+    fn write_pkcs1_der_file_with_options(
+        &self,
+        path: impl AsRef<Path>,
+        options: SecretFileOptions,
+    ) -> Result<()> {
+        Ok(self
+            .to_pkcs1_der()?
+            .write_der_file_with_options(path, options)?)
     }
 
     /// Write ASN.1 PEM-encoded PKCS#1 private key to the given path.
     #[cfg(all(feature = "pem", feature = "std"))]
     fn write_pkcs1_pem_file(&self, path: impl AsRef<Path>, line_ending: LineEnding) -> Result<()> {
+        self.write_pkcs1_pem_file_with_options(path, line_ending, SecretFileOptions::default())
+    }
+
+    /// Write ASN.1 PEM-encoded PKCS#1 private key to the given path, using the
+    /// given [`SecretFileOptions`].
+    #[cfg(all(feature = "pem", feature = "std"))]
+    fn write_pkcs1_pem_file_with_options(
+        &self,
+        path: impl AsRef<Path>,
+        line_ending: LineEnding,
+        options: SecretFileOptions,
+    ) -> Result<()> {
         let doc = self.to_pkcs1_der()?;
-        Ok(doc.write_pem_file(path, RsaPrivateKey::PEM_LABEL, line_ending)?)
+        Ok(doc.write_pem_file_with_options(path, RsaPrivateKey::PEM_LABEL, line_ending, options)?)
     }
 }
 
@@ -164,6 +210,7 @@ where
         Ok(Self::try_from(pkcs8::PrivateKeyInfoRef {
             algorithm: ALGORITHM_ID,
             private_key,
+            attributes: None,
             public_key: None,
         })?)
     }
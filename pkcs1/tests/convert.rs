@@ -0,0 +1,182 @@
+//! PKCS#1 <-> PKCS#8 / SPKI conversion tests.
+
+#![cfg(feature = "pkcs8")]
+
+use der::{
+    asn1::{AnyRef, OctetStringRef},
+    Decode,
+};
+use pkcs1::{RsaPrivateKey, RsaPublicKey};
+
+#[cfg(feature = "alloc")]
+use {
+    pkcs1::convert,
+    pkcs8::{EncodePrivateKey, EncodePublicKey},
+};
+
+#[cfg(feature = "pem")]
+use pkcs1::LineEnding;
+
+/// PKCS#1 `RSAPrivateKey` encoded as ASN.1 DER.
+const RSA_2048_PRIV_DER: &[u8] = include_bytes!("examples/rsa2048-priv.der");
+
+/// PKCS#1 `RSAPrivateKey` encoded as PEM.
+#[cfg(feature = "pem")]
+const RSA_2048_PRIV_PEM: &str = include_str!("examples/rsa2048-priv.pem");
+
+/// PKCS#8 `PrivateKeyInfo` encoded as ASN.1 DER.
+///
+/// Generated using:
+///
+/// ```
+/// $ openssl pkcs8 -topk8 -nocrypt -inform der -in rsa2048-priv.der -outform der -out rsa2048-priv-pkcs8.der
+/// ```
+const RSA_2048_PKCS8_DER: &[u8] = include_bytes!("examples/rsa2048-priv-pkcs8.der");
+
+/// PKCS#8 `PrivateKeyInfo` encoded as PEM.
+///
+/// Generated using:
+///
+/// ```
+/// $ openssl pkcs8 -topk8 -nocrypt -inform der -in rsa2048-priv.der -out rsa2048-priv-pkcs8.pem
+/// ```
+#[cfg(feature = "pem")]
+const RSA_2048_PKCS8_PEM: &str = include_str!("examples/rsa2048-priv-pkcs8.pem");
+
+/// PKCS#1 `RSAPublicKey` encoded as ASN.1 DER.
+const RSA_2048_PUB_DER: &[u8] = include_bytes!("examples/rsa2048-pub.der");
+
+/// PKCS#1 `RSAPublicKey` encoded as PEM.
+#[cfg(feature = "pem")]
+const RSA_2048_PUB_PEM: &str = include_str!("examples/rsa2048-pub.pem");
+
+/// `SubjectPublicKeyInfo` encoded as ASN.1 DER.
+///
+/// Generated using:
+///
+/// ```
+/// $ openssl rsa -RSAPublicKey_in -inform der -in rsa2048-pub.der -pubout -outform der -out rsa2048-pub-spki.der
+/// ```
+const RSA_2048_SPKI_DER: &[u8] = include_bytes!("examples/rsa2048-pub-spki.der");
+
+/// `SubjectPublicKeyInfo` encoded as PEM.
+///
+/// Generated using:
+///
+/// ```
+/// $ openssl rsa -RSAPublicKey_in -inform der -in rsa2048-pub.der -pubout -out rsa2048-pub-spki.pem
+/// ```
+#[cfg(feature = "pem")]
+const RSA_2048_SPKI_PEM: &str = include_str!("examples/rsa2048-pub-spki.pem");
+
+#[test]
+fn private_key_from_private_key_info() {
+    let private_key_info = pkcs8::PrivateKeyInfoRef::from_der(RSA_2048_PKCS8_DER).unwrap();
+    let private_key = RsaPrivateKey::try_from(private_key_info).unwrap();
+    assert_eq!(
+        private_key.modulus,
+        RsaPrivateKey::from_der(RSA_2048_PRIV_DER).unwrap().modulus
+    );
+}
+
+#[test]
+fn private_key_from_private_key_info_wrong_algorithm() {
+    let mut private_key_info = pkcs8::PrivateKeyInfoRef::from_der(RSA_2048_PKCS8_DER).unwrap();
+    private_key_info.algorithm.oid = pkcs1::RSASSA_PSS_OID;
+    assert!(RsaPrivateKey::try_from(private_key_info).is_err());
+
+    let mut private_key_info = pkcs8::PrivateKeyInfoRef::from_der(RSA_2048_PKCS8_DER).unwrap();
+    private_key_info.algorithm.parameters = Some(AnyRef::from(OctetStringRef::new(&[]).unwrap()));
+    assert!(RsaPrivateKey::try_from(private_key_info).is_err());
+}
+
+#[test]
+fn public_key_from_spki() {
+    let spki = pkcs8::SubjectPublicKeyInfoRef::from_der(RSA_2048_SPKI_DER).unwrap();
+    let public_key = RsaPublicKey::try_from(spki).unwrap();
+    assert_eq!(
+        public_key,
+        RsaPublicKey::from_der(RSA_2048_PUB_DER).unwrap()
+    );
+}
+
+#[test]
+fn public_key_from_spki_wrong_algorithm() {
+    let mut spki = pkcs8::SubjectPublicKeyInfoRef::from_der(RSA_2048_SPKI_DER).unwrap();
+    spki.algorithm.oid = pkcs1::RSASSA_PSS_OID;
+    assert!(RsaPublicKey::try_from(spki).is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn private_key_to_pkcs8_der() {
+    let private_key = RsaPrivateKey::from_der(RSA_2048_PRIV_DER).unwrap();
+    assert_eq!(
+        private_key.to_pkcs8_der().unwrap().as_bytes(),
+        RSA_2048_PKCS8_DER
+    );
+
+    let doc = convert::private_key_to_pkcs8_der(RSA_2048_PRIV_DER).unwrap();
+    assert_eq!(doc.as_bytes(), RSA_2048_PKCS8_DER);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn private_key_from_pkcs8_der() {
+    let doc = convert::private_key_from_pkcs8_der(RSA_2048_PKCS8_DER).unwrap();
+    assert_eq!(doc.as_bytes(), RSA_2048_PRIV_DER);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn public_key_to_spki_der() {
+    let public_key = RsaPublicKey::from_der(RSA_2048_PUB_DER).unwrap();
+    assert_eq!(
+        public_key.to_public_key_der().unwrap().as_bytes(),
+        RSA_2048_SPKI_DER
+    );
+
+    let doc = convert::public_key_to_spki_der(RSA_2048_PUB_DER).unwrap();
+    assert_eq!(doc.as_bytes(), RSA_2048_SPKI_DER);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn public_key_from_spki_der() {
+    let doc = convert::public_key_from_spki_der(RSA_2048_SPKI_DER).unwrap();
+    assert_eq!(doc.as_bytes(), RSA_2048_PUB_DER);
+}
+
+#[cfg(feature = "pem")]
+#[test]
+fn private_key_pem() {
+    let pem = convert::private_key_to_pkcs8_pem(RSA_2048_PRIV_PEM, LineEnding::LF).unwrap();
+    assert_eq!(&*pem, RSA_2048_PKCS8_PEM);
+
+    let pem = convert::private_key_from_pkcs8_pem(RSA_2048_PKCS8_PEM, LineEnding::LF).unwrap();
+    assert_eq!(&*pem, RSA_2048_PRIV_PEM);
+}
+
+#[cfg(feature = "pem")]
+#[test]
+fn private_key_pem_wrong_label() {
+    assert!(convert::private_key_to_pkcs8_pem(RSA_2048_PKCS8_PEM, LineEnding::LF).is_err());
+    assert!(convert::private_key_from_pkcs8_pem(RSA_2048_PRIV_PEM, LineEnding::LF).is_err());
+}
+
+#[cfg(feature = "pem")]
+#[test]
+fn public_key_pem() {
+    let pem = convert::public_key_to_spki_pem(RSA_2048_PUB_PEM, LineEnding::LF).unwrap();
+    assert_eq!(pem, RSA_2048_SPKI_PEM);
+
+    let pem = convert::public_key_from_spki_pem(RSA_2048_SPKI_PEM, LineEnding::LF).unwrap();
+    assert_eq!(pem, RSA_2048_PUB_PEM);
+}
+
+#[cfg(feature = "pem")]
+#[test]
+fn public_key_pem_wrong_label() {
+    assert!(convert::public_key_to_spki_pem(RSA_2048_SPKI_PEM, LineEnding::LF).is_err());
+    assert!(convert::public_key_from_spki_pem(RSA_2048_PUB_PEM, LineEnding::LF).is_err());
+}
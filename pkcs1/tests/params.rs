@@ -4,16 +4,39 @@ use const_oid::db;
 use der::{
     asn1::{AnyRef, ObjectIdentifier, OctetStringRef},
     oid::AssociatedOid,
-    Encode,
+    Decode, Encode,
 };
 use hex_literal::hex;
-use pkcs1::{RsaOaepParams, RsaPssParams, TrailerField};
+use pkcs1::{RsaOaepParams, RsaPssParams, TrailerField, RSAES_OAEP_OID, RSASSA_PSS_OID};
+use spki::{AlgorithmIdentifierRef, SubjectPublicKeyInfoRef};
+
+#[cfg(feature = "alloc")]
+use spki::AlgorithmIdentifierOwned;
 
 /// Default PSS parameters using all default values (SHA1, MGF1)
 const RSA_PSS_PARAMETERS_DEFAULTS: &[u8] = &hex!("3000");
 /// Example PSS parameters using SHA256 instead of SHA1
 const RSA_PSS_PARAMETERS_SHA2_256: &[u8] = &hex!("3034a00f300d06096086480165030402010500a11c301a06092a864886f70d010108300d06096086480165030402010500a203020120");
 
+/// Self-signed certificate signed with RSASSA-PSS, SHA-256 and a digest-length salt.
+///
+/// Generated using:
+///
+/// ```
+/// $ openssl req -x509 -newkey rsa:2048 -nodes -keyout key.pem -subj /CN=pss -sha256 -sigopt rsa_padding_mode:pss -sigopt rsa_pss_saltlen:-1 -outform der -out rsa2048-pss-sha256-cert.der
+/// ```
+const RSA_PSS_CERT: &[u8] = include_bytes!("examples/rsa2048-pss-sha256-cert.der");
+
+/// `SubjectPublicKeyInfo` of an RSA key restricted to RSASSA-PSS with SHA-256.
+///
+/// Generated using:
+///
+/// ```
+/// $ openssl genpkey -algorithm RSA-PSS -pkeyopt rsa_keygen_bits:2048 -pkeyopt rsa_pss_keygen_md:sha256 -pkeyopt rsa_pss_keygen_mgf1_md:sha256 -pkeyopt rsa_pss_keygen_saltlen:32 -out key.pem
+/// $ openssl pkey -in key.pem -pubout -outform der -out rsa2048-pss-sha256-pub.der
+/// ```
+const RSA_PSS_PUB: &[u8] = include_bytes!("examples/rsa2048-pss-sha256-pub.der");
+
 /// Default OAEP parameters using all default values (SHA1, MGF1, Empty)
 const RSA_OAEP_PARAMETERS_DEFAULTS: &[u8] = &hex!("3000");
 /// Example OAEP parameters using SHA256 instead of SHA1
@@ -116,6 +139,77 @@ fn new_pss_param() {
     );
 }
 
+#[test]
+fn sha2_pss_params() {
+    let mut buf = [0_u8; 256];
+    assert_eq!(
+        RsaPssParams::sha256().encode_to_slice(&mut buf).unwrap(),
+        RSA_PSS_PARAMETERS_SHA2_256
+    );
+
+    let param = RsaPssParams::sha384();
+    assert!(param
+        .hash
+        .assert_algorithm_oid(db::rfc5912::ID_SHA_384)
+        .is_ok());
+    assert_eq!(param.mask_gen.parameters, Some(param.hash));
+    assert_eq!(param.salt_len, 48);
+
+    let param = RsaPssParams::sha512();
+    assert!(param
+        .hash
+        .assert_algorithm_oid(db::rfc5912::ID_SHA_512)
+        .is_ok());
+    assert_eq!(param.mask_gen.parameters, Some(param.hash));
+    assert_eq!(param.salt_len, 64);
+}
+
+#[test]
+fn decode_pss_cert_signature_algorithm() {
+    let signature_algorithm = AnyRef::from_der(RSA_PSS_CERT)
+        .unwrap()
+        .sequence(|reader| {
+            let _tbs_certificate = AnyRef::decode(reader)?;
+            let signature_algorithm = AlgorithmIdentifierRef::decode(reader)?;
+            let _signature = AnyRef::decode(reader)?;
+            Ok::<_, der::Error>(signature_algorithm)
+        })
+        .unwrap();
+
+    let param = RsaPssParams::try_from(signature_algorithm).unwrap();
+    assert_eq!(param, RsaPssParams::sha256());
+}
+
+#[test]
+fn decode_pss_spki_algorithm() {
+    let spki = SubjectPublicKeyInfoRef::try_from(RSA_PSS_PUB).unwrap();
+    let param = RsaPssParams::try_from(spki.algorithm).unwrap();
+    assert_eq!(param, RsaPssParams::sha256());
+}
+
+#[test]
+fn decode_pss_algorithm_wrong_oid() {
+    let alg = AlgorithmIdentifierRef {
+        oid: RSAES_OAEP_OID,
+        parameters: Some(AnyRef::try_from(RSA_PSS_PARAMETERS_SHA2_256).unwrap()),
+    };
+    assert!(RsaPssParams::try_from(alg).is_err());
+
+    let alg = AlgorithmIdentifierRef {
+        oid: RSASSA_PSS_OID,
+        parameters: None,
+    };
+    assert!(RsaPssParams::try_from(alg).is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn encode_pss_algorithm_identifier() {
+    let alg = AlgorithmIdentifierOwned::try_from(&RsaPssParams::sha256()).unwrap();
+    let spki = SubjectPublicKeyInfoRef::try_from(RSA_PSS_PUB).unwrap();
+    assert_eq!(alg.to_der().unwrap(), spki.algorithm.to_der().unwrap());
+}
+
 #[test]
 fn decode_oaep_param() {
     let param = RsaOaepParams::try_from(RSA_OAEP_PARAMETERS_SHA2_256).unwrap();
@@ -221,3 +315,26 @@ fn new_oaep_param() {
         RSA_OAEP_PARAMETERS_SHA2_256
     );
 }
+
+#[test]
+fn sha2_oaep_params() {
+    let mut buf = [0_u8; 256];
+    assert_eq!(
+        RsaOaepParams::sha256().encode_to_slice(&mut buf).unwrap(),
+        RSA_OAEP_PARAMETERS_SHA2_256
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn oaep_algorithm_identifier() {
+    let alg = AlgorithmIdentifierOwned::try_from(&RsaOaepParams::sha256()).unwrap();
+    assert_eq!(alg.oid, RSAES_OAEP_OID);
+
+    let der = alg.to_der().unwrap();
+    let alg = AlgorithmIdentifierRef::from_der(&der).unwrap();
+    assert_eq!(
+        RsaOaepParams::try_from(alg).unwrap(),
+        RsaOaepParams::sha256()
+    );
+}
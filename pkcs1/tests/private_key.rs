@@ -3,6 +3,9 @@
 use hex_literal::hex;
 use pkcs1::{RsaPrivateKey, Version};
 
+#[cfg(feature = "alloc")]
+use der::Encode;
+
 /// RSA-2048 PKCS#1 private key encoded as ASN.1 DER.
 ///
 /// Note: this key is extracted from the corresponding `rsa2048-priv.der`
@@ -82,6 +85,41 @@ fn decode_rsa2048_multi_prime_der() {
     assert_eq!(other_prime_infos[0].coefficient.as_bytes(), hex!("39EA226CABFB317E41A5593B9168D1A0124993B45D9CD14A22BD1557CDCB43D28024AC26ED2C8530B53E9B93A878F428807C5282EBB811399F913017CDF2149013D80CDF73F609D6C692475EB7A123D0E93E6A60FC"));
 }
 
+#[cfg(feature = "alloc")]
+#[test]
+fn encode_rsa2048_multi_prime_der() {
+    let key = RsaPrivateKey::try_from(RSA_2048_MULTI_PRIME_DER_EXAMPLE).unwrap();
+    assert_eq!(key.to_der().unwrap(), RSA_2048_MULTI_PRIME_DER_EXAMPLE);
+}
+
+#[test]
+fn decode_rsa2048_two_prime_with_multi_version() {
+    // `version` is `multi` but `otherPrimeInfos` is absent
+    let mut der = RSA_2048_DER_EXAMPLE.to_vec();
+    assert_eq!(der[4..7], [0x02, 0x01, 0x00]);
+    der[6] = 0x01;
+    assert!(RsaPrivateKey::try_from(der.as_slice()).is_err());
+}
+
+#[test]
+fn decode_rsa2048_multi_prime_with_two_prime_version() {
+    // `version` is `two-prime` but `otherPrimeInfos` is present
+    let mut der = RSA_2048_MULTI_PRIME_DER_EXAMPLE.to_vec();
+    assert_eq!(der[4..7], [0x02, 0x01, 0x01]);
+    der[6] = 0x00;
+    assert!(RsaPrivateKey::try_from(der.as_slice()).is_err());
+}
+
+#[test]
+fn decode_rsa2048_multi_prime_empty_other_prime_infos() {
+    // `otherPrimeInfos` must contain at least one `OtherPrimeInfo`
+    let mut der = RSA_2048_DER_EXAMPLE.to_vec();
+    der[3] += 2;
+    der[6] = 0x01;
+    der.extend_from_slice(&[0x30, 0x00]);
+    assert!(RsaPrivateKey::try_from(der.as_slice()).is_err());
+}
+
 #[test]
 fn private_key_to_public_key() {
     let private_key = RsaPrivateKey::try_from(RSA_2048_DER_EXAMPLE).unwrap();
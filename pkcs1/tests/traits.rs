@@ -98,3 +98,17 @@ fn write_pkcs1_pem_file() {
     let pem = fs::read_to_string(path).unwrap();
     assert_eq!(&pem, RSA_2048_PRIV_PEM_EXAMPLE);
 }
+
+#[cfg(all(feature = "pem", feature = "std"))]
+#[test]
+fn write_pkcs1_pem_file_with_options() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("example.pem");
+    let options = der::SecretFileOptions::new().sync(true).owner_only(true);
+    MockPrivateKey(RSA_2048_PRIV_DER_EXAMPLE.to_vec())
+        .write_pkcs1_pem_file_with_options(&path, LineEnding::LF, options)
+        .unwrap();
+
+    let key = MockPrivateKey::read_pkcs1_pem_file_with_options(&path, options).unwrap();
+    assert_eq!(key.as_ref(), RSA_2048_PRIV_DER_EXAMPLE);
+}
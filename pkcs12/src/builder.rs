@@ -0,0 +1,388 @@
+//! PFX builder
+//!
+//! [`PfxBuilder`] produces a password-protected PFX from a private key, its
+//! certificate and the intermediate certificates of the chain, laid out the
+//! way OpenSSL, Windows, macOS and Java keytool expect:
+//!
+//! - an `EncryptedData` content with a `certBag` per certificate
+//! - a `Data` content with the `pkcs8ShroudedKeyBag` of the private key
+//! - a `macData` over the authenticated safe
+//!
+//! The key bag and the bag of the end-entity certificate share a
+//! `localKeyId` attribute (the SHA-1 hash of the certificate), and an
+//! optional `friendlyName`.
+
+use crate::decrypt::mac_key;
+use crate::digest_info::DigestInfo;
+use crate::pbe_params::EncryptedPrivateKeyInfo;
+use crate::pfx::Version;
+use crate::safe_bag::PrivateKeyInfo;
+use crate::{
+    CertBag, MacData, Pfx, SafeBag, PKCS_12_CERT_BAG_OID, PKCS_12_PKCS8_KEY_BAG_OID,
+    PKCS_12_X509_CERT_OID,
+};
+use alloc::string::String;
+use alloc::{vec, vec::Vec};
+use cms::content_info::{CmsVersion, ContentInfo};
+use cms::encrypted_data::EncryptedData;
+use cms::enveloped_data::EncryptedContentInfo;
+use const_oid::db::{rfc2985, rfc5911, rfc5912};
+use const_oid::ObjectIdentifier;
+use core::fmt;
+use der::asn1::{BmpString, ContextSpecific, OctetString, SetOfVec};
+use der::{Any, AnyRef, Decode, Encode, EncodeValue, TagMode, TagNumber, Tagged};
+use digest::{core_api::BlockSizeUser, Digest, FixedOutputReset};
+use hmac::{digest::KeyInit, Mac, SimpleHmac};
+use rand_core::CryptoRngCore;
+use sha1::Sha1;
+use spki::AlgorithmIdentifierOwned;
+use x509_cert::attr::{Attribute, Attributes};
+use x509_cert::Certificate;
+use zeroize::Zeroizing;
+
+#[cfg(feature = "legacy")]
+use crate::{
+    legacy::LegacyCipher, pbe_params::Pkcs12PbeParams, PKCS_12_PBEWITH_SHAAND40_BIT_RC2_CBC,
+    PKCS_12_PBE_WITH_SHAAND3_KEY_TRIPLE_DES_CBC,
+};
+
+/// Error type
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// ASN.1 DER-related errors.
+    Asn1(der::Error),
+
+    /// PKCS#5 encryption errors.
+    Pkcs5(pkcs5::Error),
+
+    /// The iteration count is zero or doesn't fit in an `INTEGER` of the
+    /// PFX.
+    Iterations,
+
+    /// Encryption or MAC computation failed.
+    Encryption,
+}
+
+impl core::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Asn1(err) => write!(f, "ASN.1 error: {}", err),
+            Error::Pkcs5(err) => write!(f, "PKCS#5 error: {}", err),
+            Error::Iterations => write!(f, "invalid iteration count"),
+            Error::Encryption => write!(f, "encryption failed"),
+        }
+    }
+}
+
+impl From<der::Error> for Error {
+    fn from(err: der::Error) -> Error {
+        Error::Asn1(err)
+    }
+}
+
+impl From<pkcs5::Error> for Error {
+    fn from(err: pkcs5::Error) -> Error {
+        Error::Pkcs5(err)
+    }
+}
+
+type Result<T> = core::result::Result<T, Error>;
+
+/// Encryption of the bags and algorithm of the MAC of a [`Pfx`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Encryption {
+    /// PBES2 with PBKDF2-HMAC-SHA256 and AES-256-CBC, and an HMAC-SHA256
+    /// MAC. This is what OpenSSL 3 produces by default.
+    #[default]
+    Aes256Cbc,
+
+    /// `pbeWithSHAAnd3-KeyTripleDES-CBC` for the private key,
+    /// `pbeWithSHAAnd40BitRC2-CBC` for the certificates and an HMAC-SHA1 MAC,
+    /// for consumers which don't support PBES2 (e.g. Windows before
+    /// Windows 10 1709 and Java before 8u301).
+    ///
+    /// ⚠️ WARNING ⚠️
+    ///
+    /// These schemes offer little protection against brute-force attacks.
+    #[cfg(feature = "legacy")]
+    Legacy,
+}
+
+/// Builder for a password-protected [`Pfx`] holding a private key and its
+/// certificate chain.
+#[derive(Clone, Debug)]
+pub struct PfxBuilder {
+    key: PrivateKeyInfo,
+    certificate: Certificate,
+    chain: Vec<Certificate>,
+    friendly_name: Option<String>,
+    encryption: Encryption,
+    iterations: u32,
+    mac_iterations: u32,
+}
+
+impl PfxBuilder {
+    /// Default iteration count of the key derivation functions, as used by
+    /// OpenSSL.
+    pub const DEFAULT_ITERATIONS: u32 = 2048;
+
+    /// Create a builder for `key` and its end-entity `certificate`.
+    pub fn new(key: PrivateKeyInfo, certificate: Certificate) -> Self {
+        Self {
+            key,
+            certificate,
+            chain: Vec::new(),
+            friendly_name: None,
+            encryption: Encryption::default(),
+            iterations: Self::DEFAULT_ITERATIONS,
+            mac_iterations: Self::DEFAULT_ITERATIONS,
+        }
+    }
+
+    /// Add an intermediate certificate of the chain.
+    pub fn add_certificate(&mut self, certificate: Certificate) -> &mut Self {
+        self.chain.push(certificate);
+        self
+    }
+
+    /// Set the `friendlyName` of the key and end-entity certificate, which
+    /// is shown as alias by keytool and the Windows and macOS key stores.
+    pub fn friendly_name(&mut self, friendly_name: impl Into<String>) -> &mut Self {
+        self.friendly_name = Some(friendly_name.into());
+        self
+    }
+
+    /// Set the encryption of the bags and the MAC algorithm.
+    pub fn encryption(&mut self, encryption: Encryption) -> &mut Self {
+        self.encryption = encryption;
+        self
+    }
+
+    /// Set the iteration count of the key derivation for the encryption of
+    /// the bags.
+    pub fn iterations(&mut self, iterations: u32) -> &mut Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Set the iteration count of the key derivation for the MAC.
+    pub fn mac_iterations(&mut self, mac_iterations: u32) -> &mut Self {
+        self.mac_iterations = mac_iterations;
+        self
+    }
+
+    /// Build the PFX, protected with `password`, drawing the salts and IVs
+    /// from `rng`.
+    pub fn build_with_rng(&self, password: &str, rng: &mut impl CryptoRngCore) -> Result<Pfx> {
+        let local_key_id = OctetString::new(Sha1::digest(self.certificate.to_der()?).to_vec())?;
+        let attributes = Some(self.attributes(&local_key_id)?);
+
+        let mut cert_bags = vec![cert_bag(&self.certificate, attributes.clone())?];
+        for certificate in &self.chain {
+            cert_bags.push(cert_bag(certificate, None)?);
+        }
+
+        let (content_enc_alg, encrypted_content) =
+            self.encrypt(Contents::Certificates, password, &cert_bags.to_der()?, rng)?;
+        let certificates = ContentInfo {
+            content_type: rfc5911::ID_ENCRYPTED_DATA,
+            content: Any::encode_from(&EncryptedData {
+                version: CmsVersion::V0,
+                enc_content_info: EncryptedContentInfo {
+                    content_type: rfc5911::ID_DATA,
+                    content_enc_alg,
+                    encrypted_content: Some(OctetString::new(encrypted_content)?),
+                },
+                unprotected_attrs: None,
+            })?,
+        };
+
+        let key_der = Zeroizing::new(self.key.to_der()?);
+        let (encryption_algorithm, encrypted_data) =
+            self.encrypt(Contents::PrivateKey, password, &key_der, rng)?;
+        let key_bag = SafeBag {
+            bag_id: PKCS_12_PKCS8_KEY_BAG_OID,
+            bag_value: explicit(&EncryptedPrivateKeyInfo {
+                encryption_algorithm,
+                encrypted_data: OctetString::new(encrypted_data)?,
+            })?,
+            bag_attributes: attributes,
+        };
+        let key = data(&vec![key_bag].to_der()?)?;
+
+        let auth_safe = vec![certificates, key].to_der()?;
+        let mac_data = self.mac_data(password, &auth_safe, rng)?;
+
+        Ok(Pfx {
+            version: Version::V3,
+            auth_safe: data(&auth_safe)?,
+            mac_data: Some(mac_data),
+        })
+    }
+
+    /// `friendlyName` and `localKeyId` attributes of the key and end-entity
+    /// certificate bags.
+    fn attributes(&self, local_key_id: &OctetString) -> Result<Attributes> {
+        let mut attributes = vec![attribute(rfc2985::PKCS_9_AT_LOCAL_KEY_ID, local_key_id)?];
+        if let Some(friendly_name) = &self.friendly_name {
+            attributes.push(attribute(
+                rfc2985::PKCS_9_AT_FRIENDLY_NAME,
+                &BmpString::from_utf8(friendly_name)?,
+            )?);
+        }
+
+        Ok(SetOfVec::try_from(attributes)?)
+    }
+
+    /// Encrypt the `contents` in `plaintext`.
+    #[cfg_attr(not(feature = "legacy"), allow(unused_variables))]
+    fn encrypt(
+        &self,
+        contents: Contents,
+        password: &str,
+        plaintext: &[u8],
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<(AlgorithmIdentifierOwned, Vec<u8>)> {
+        to_i32(self.iterations)?;
+
+        match self.encryption {
+            Encryption::Aes256Cbc => {
+                let mut salt = [0u8; 16];
+                let mut iv = [0u8; 16];
+                rng.fill_bytes(&mut salt);
+                rng.fill_bytes(&mut iv);
+
+                let scheme = pkcs5::EncryptionScheme::from(
+                    pkcs5::pbes2::Parameters::pbkdf2_sha256_aes256cbc(self.iterations, &salt, iv)?,
+                );
+                let ciphertext = scheme.encrypt(password, plaintext)?;
+                let algorithm = AlgorithmIdentifierOwned::from_der(&scheme.to_der()?)?;
+                Ok((algorithm, ciphertext))
+            }
+            #[cfg(feature = "legacy")]
+            Encryption::Legacy => {
+                // the combination OpenSSL uses with `-legacy`
+                let oid = match contents {
+                    Contents::Certificates => PKCS_12_PBEWITH_SHAAND40_BIT_RC2_CBC,
+                    Contents::PrivateKey => PKCS_12_PBE_WITH_SHAAND3_KEY_TRIPLE_DES_CBC,
+                };
+                let mut salt = [0u8; 8];
+                rng.fill_bytes(&mut salt);
+
+                let params = Pkcs12PbeParams {
+                    salt: OctetString::new(salt)?,
+                    iterations: to_i32(self.iterations)?,
+                };
+                let ciphertext = LegacyCipher::new(oid, &params, password)?
+                    .encrypt(plaintext)
+                    .ok_or(Error::Encryption)?;
+                let algorithm = AlgorithmIdentifierOwned {
+                    oid,
+                    parameters: Some(Any::encode_from(&params)?),
+                };
+                Ok((algorithm, ciphertext))
+            }
+        }
+    }
+
+    /// MAC over the DER-encoded `auth_safe`.
+    fn mac_data(
+        &self,
+        password: &str,
+        auth_safe: &[u8],
+        rng: &mut impl CryptoRngCore,
+    ) -> Result<MacData> {
+        let mut salt = [0u8; 8];
+        rng.fill_bytes(&mut salt);
+        let iterations = to_i32(self.mac_iterations)?;
+
+        let (oid, digest) = match self.encryption {
+            Encryption::Aes256Cbc => (
+                rfc5912::ID_SHA_256,
+                hmac::<sha2::Sha256>(password, &salt, iterations, auth_safe)?,
+            ),
+            #[cfg(feature = "legacy")]
+            Encryption::Legacy => (
+                rfc5912::ID_SHA_1,
+                hmac::<Sha1>(password, &salt, iterations, auth_safe)?,
+            ),
+        };
+
+        Ok(MacData {
+            mac: DigestInfo {
+                algorithm: AlgorithmIdentifierOwned {
+                    oid,
+                    parameters: Some(AnyRef::NULL.into()),
+                },
+                digest: OctetString::new(digest)?,
+            },
+            mac_salt: OctetString::new(salt)?,
+            iterations,
+        })
+    }
+}
+
+/// Contents encrypted by [`PfxBuilder::encrypt`].
+#[derive(Clone, Copy)]
+enum Contents {
+    Certificates,
+    PrivateKey,
+}
+
+fn to_i32(iterations: u32) -> Result<i32> {
+    match i32::try_from(iterations) {
+        Ok(iterations) if iterations > 0 => Ok(iterations),
+        _ => Err(Error::Iterations),
+    }
+}
+
+fn hmac<D>(password: &str, salt: &[u8], iterations: i32, data: &[u8]) -> Result<Vec<u8>>
+where
+    D: Digest + FixedOutputReset + BlockSizeUser,
+{
+    let key = mac_key::<D>(password, salt, iterations)?;
+    let mut mac =
+        <SimpleHmac<D> as KeyInit>::new_from_slice(&key).map_err(|_| Error::Encryption)?;
+    mac.update(data);
+    Ok(mac.finalize().into_bytes().to_vec())
+}
+
+fn attribute(oid: ObjectIdentifier, value: &(impl EncodeValue + Tagged)) -> Result<Attribute> {
+    Ok(Attribute {
+        oid,
+        values: SetOfVec::try_from(vec![Any::encode_from(value)?])?,
+    })
+}
+
+fn cert_bag(certificate: &Certificate, bag_attributes: Option<Attributes>) -> Result<SafeBag> {
+    Ok(SafeBag {
+        bag_id: PKCS_12_CERT_BAG_OID,
+        bag_value: explicit(&CertBag {
+            cert_id: PKCS_12_X509_CERT_OID,
+            cert_value: OctetString::new(certificate.to_der()?)?,
+        })?,
+        bag_attributes,
+    })
+}
+
+/// DER encoding of `value` as `[0] EXPLICIT`, the `bagValue` of a bag.
+fn explicit(value: &(impl EncodeValue + Tagged)) -> Result<Vec<u8>> {
+    Ok(ContextSpecific {
+        tag_number: TagNumber::N0,
+        tag_mode: TagMode::Explicit,
+        value: Any::encode_from(value)?,
+    }
+    .to_der()?)
+}
+
+/// `Data` content holding `der`.
+fn data(der: &[u8]) -> Result<ContentInfo> {
+    Ok(ContentInfo {
+        content_type: rfc5911::ID_DATA,
+        content: Any::encode_from(&OctetString::new(der)?)?,
+    })
+}
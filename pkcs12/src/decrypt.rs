@@ -0,0 +1,346 @@
+//! PFX decryption
+//!
+//! [`Pfx::decrypt`] verifies the integrity of a password-protected PFX and
+//! extracts the private keys and certificates it contains:
+//!
+//! - `MacData` with HMAC-SHA1 or HMAC-SHA2, keyed with the PKCS#12 KDF
+//!   ([RFC 7292 Appendix B])
+//! - `EncryptedData` contents and `pkcs8ShroudedKeyBag`s encrypted with
+//!   PBES2 ([RFC 8018])
+//!
+//! With the `legacy` feature, contents and key bags encrypted with
+//! `pbeWithSHAAnd3-KeyTripleDES-CBC`, `pbeWithSHAAnd128BitRC2-CBC` or
+//! `pbeWithSHAAnd40BitRC2-CBC` ([RFC 7292 Appendix C]) can be decrypted too.
+//! These schemes are still the default of many exporters, but are weak and
+//! should not be used to protect new archives.
+//!
+//! [RFC 7292 Appendix B]: https://www.rfc-editor.org/rfc/rfc7292#appendix-B
+//! [RFC 7292 Appendix C]: https://www.rfc-editor.org/rfc/rfc7292#appendix-C
+//! [RFC 8018]: https://www.rfc-editor.org/rfc/rfc8018
+
+use crate::kdf::{derive_key_utf8, Pkcs12KeyType};
+use crate::{pbe_params::EncryptedPrivateKeyInfo, safe_bag::PrivateKeyInfo};
+use crate::{
+    AuthenticatedSafe, CertBag, MacData, Pfx, SafeBag, PKCS_12_CERT_BAG_OID, PKCS_12_KEY_BAG_OID,
+    PKCS_12_PBEWITH_SHAAND40_BIT_RC2_CBC, PKCS_12_PBE_WITH_SHAAND128_BIT_RC2_CBC,
+    PKCS_12_PBE_WITH_SHAAND3_KEY_TRIPLE_DES_CBC, PKCS_12_PKCS8_KEY_BAG_OID,
+    PKCS_12_SAFE_CONTENTS_BAG_OID, PKCS_12_X509_CERT_OID,
+};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use cms::encrypted_data::EncryptedData;
+use const_oid::db::{rfc2985, rfc5911, rfc5912};
+use core::fmt;
+use der::asn1::{BmpString, ContextSpecific, ObjectIdentifier, OctetString, OctetStringRef};
+use der::{AnyRef, Decode, DecodeOwned, Document, Encode};
+use digest::{core_api::BlockSizeUser, Digest, FixedOutputReset};
+use hmac::{digest::KeyInit, Mac, SimpleHmac};
+use spki::AlgorithmIdentifierOwned;
+use x509_cert::Certificate;
+use zeroize::Zeroizing;
+
+#[cfg(feature = "legacy")]
+use crate::{legacy::LegacyCipher, pbe_params::Pkcs12PbeParams};
+
+/// Error type
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Error {
+    /// ASN.1 DER-related errors.
+    Asn1(der::Error),
+
+    /// PKCS#5 errors other than a failed decryption.
+    Pkcs5(pkcs5::Error),
+
+    /// MAC, encryption or key derivation algorithm which isn't supported.
+    UnsupportedAlgorithm(ObjectIdentifier),
+
+    /// Content type which isn't supported, e.g. public-key protected
+    /// `EnvelopedData` contents.
+    UnsupportedContentType(ObjectIdentifier),
+
+    /// The PFX has no `macData`.
+    MacNotFound,
+
+    /// The MAC doesn't match, e.g. because of a wrong password.
+    MacVerification,
+
+    /// Decryption failed, e.g. because the encrypted content is missing.
+    Decryption,
+
+    /// Decryption failed because the password is wrong, as reported by
+    /// [`pkcs5::Error::WrongPassword`]. Corrupted ciphertexts fail the same
+    /// way, as the two cases can't be told apart.
+    WrongPassword,
+}
+
+impl core::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Asn1(err) => write!(f, "ASN.1 error: {}", err),
+            Error::Pkcs5(err) => write!(f, "PKCS#5 error: {}", err),
+            Error::UnsupportedAlgorithm(oid) => write!(f, "unsupported algorithm: {}", oid),
+            Error::UnsupportedContentType(oid) => write!(f, "unsupported content type: {}", oid),
+            Error::MacNotFound => write!(f, "missing MAC"),
+            Error::MacVerification => write!(f, "MAC verification failed"),
+            Error::Decryption => write!(f, "decryption failed"),
+            Error::WrongPassword => {
+                write!(f, "decryption failed: wrong password or corrupted data")
+            }
+        }
+    }
+}
+
+impl From<der::Error> for Error {
+    fn from(err: der::Error) -> Error {
+        Error::Asn1(err)
+    }
+}
+
+impl From<pkcs5::Error> for Error {
+    fn from(err: pkcs5::Error) -> Error {
+        match err {
+            pkcs5::Error::DecryptFailed => Error::Decryption,
+            pkcs5::Error::WrongPassword => Error::WrongPassword,
+            pkcs5::Error::UnsupportedAlgorithm { oid } => Error::UnsupportedAlgorithm(oid),
+            err => Error::Pkcs5(err),
+        }
+    }
+}
+
+type Result<T> = core::result::Result<T, Error>;
+
+/// Private key or certificate extracted from a [`Pfx`], along with the
+/// `friendlyName` and `localKeyId` attributes of its bag.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entry<T> {
+    /// The private key or certificate.
+    pub value: T,
+
+    /// The `friendlyName` attribute of the bag.
+    pub friendly_name: Option<String>,
+
+    /// The `localKeyId` attribute of the bag.
+    pub local_key_id: Option<OctetString>,
+}
+
+impl<T> Entry<T> {
+    fn new(value: T, bag: &SafeBag) -> Result<Self> {
+        let mut entry = Entry {
+            value,
+            friendly_name: None,
+            local_key_id: None,
+        };
+
+        for attr in bag.bag_attributes.iter().flat_map(|attrs| attrs.iter()) {
+            let Some(value) = attr.values.get(0) else {
+                continue;
+            };
+
+            match attr.oid {
+                rfc2985::PKCS_9_AT_FRIENDLY_NAME => {
+                    entry.friendly_name = Some(value.decode_as::<BmpString>()?.to_string());
+                }
+                rfc2985::PKCS_9_AT_LOCAL_KEY_ID => {
+                    entry.local_key_id = Some(value.decode_as()?);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(entry)
+    }
+
+    /// Does this entry belong with `other`, e.g. is it the private key of a
+    /// certificate?
+    ///
+    /// Entries are paired by `localKeyId`, or by `friendlyName` if either of
+    /// them has no `localKeyId`.
+    pub fn pairs_with<U>(&self, other: &Entry<U>) -> bool {
+        match (&self.local_key_id, &other.local_key_id) {
+            (Some(id), Some(other_id)) => id == other_id,
+            _ => matches!(
+                (&self.friendly_name, &other.friendly_name),
+                (Some(name), Some(other_name)) if name == other_name
+            ),
+        }
+    }
+}
+
+impl Pfx {
+    /// Verify the `macData` of the authenticated safe with `password`.
+    pub fn verify_mac(&self, password: &str) -> Result<()> {
+        let mac_data = self.mac_data.as_ref().ok_or(Error::MacNotFound)?;
+        let data = self.auth_safe_data()?;
+
+        match mac_data.mac.algorithm.oid {
+            rfc5912::ID_SHA_1 => verify_hmac::<sha1::Sha1>(password, mac_data, data),
+            rfc5912::ID_SHA_224 => verify_hmac::<sha2::Sha224>(password, mac_data, data),
+            rfc5912::ID_SHA_256 => verify_hmac::<sha2::Sha256>(password, mac_data, data),
+            rfc5912::ID_SHA_384 => verify_hmac::<sha2::Sha384>(password, mac_data, data),
+            rfc5912::ID_SHA_512 => verify_hmac::<sha2::Sha512>(password, mac_data, data),
+            oid => Err(Error::UnsupportedAlgorithm(oid)),
+        }
+    }
+
+    /// Decrypt the private keys and certificates of this PFX with
+    /// `password`.
+    ///
+    /// The MAC is verified first, if the PFX has one. Keys and certificates
+    /// are returned in the order of their bags; use [`Entry::pairs_with`]
+    /// to find the certificate of a key. CRL, secret and SDSI certificate
+    /// bags are skipped.
+    #[allow(clippy::type_complexity)]
+    pub fn decrypt(
+        &self,
+        password: &str,
+    ) -> Result<(Vec<Entry<PrivateKeyInfo>>, Vec<Entry<Certificate>>)> {
+        if self.mac_data.is_some() {
+            self.verify_mac(password)?;
+        }
+
+        let mut keys = Vec::new();
+        let mut certificates = Vec::new();
+
+        for content_info in decode_ber::<AuthenticatedSafe<'_>>(self.auth_safe_data()?)? {
+            let safe_contents = match content_info.content_type {
+                rfc5911::ID_DATA => content_info
+                    .content
+                    .decode_as::<OctetString>()?
+                    .into_bytes(),
+                rfc5911::ID_ENCRYPTED_DATA => {
+                    let info = content_info
+                        .content
+                        .decode_as::<EncryptedData>()?
+                        .enc_content_info;
+                    let ciphertext = info.encrypted_content.as_ref().ok_or(Error::Decryption)?;
+                    decrypt_data(&info.content_enc_alg, password, ciphertext.as_bytes())?
+                }
+                oid => return Err(Error::UnsupportedContentType(oid)),
+            };
+
+            decrypt_bags(&safe_contents, password, &mut keys, &mut certificates)?;
+        }
+
+        Ok((keys, certificates))
+    }
+
+    /// The DER-encoded `AuthenticatedSafe`, which is also the data the MAC
+    /// is computed over.
+    fn auth_safe_data(&self) -> Result<&[u8]> {
+        match self.auth_safe.content_type {
+            rfc5911::ID_DATA => Ok(self
+                .auth_safe
+                .content
+                .decode_as::<OctetStringRef<'_>>()?
+                .as_bytes()),
+            oid => Err(Error::UnsupportedContentType(oid)),
+        }
+    }
+}
+
+fn verify_hmac<D>(password: &str, mac_data: &MacData, data: &[u8]) -> Result<()>
+where
+    D: Digest + FixedOutputReset + BlockSizeUser,
+{
+    let key = mac_key::<D>(password, mac_data.mac_salt.as_bytes(), mac_data.iterations)?;
+    let mut mac =
+        <SimpleHmac<D> as KeyInit>::new_from_slice(&key).map_err(|_| Error::MacVerification)?;
+    mac.update(data);
+    mac.verify_slice(mac_data.mac.digest.as_bytes())
+        .map_err(|_| Error::MacVerification)
+}
+
+/// Derive the HMAC key of the `macData` from `password`.
+pub(crate) fn mac_key<D>(
+    password: &str,
+    salt: &[u8],
+    iterations: i32,
+) -> der::Result<Zeroizing<Vec<u8>>>
+where
+    D: Digest + FixedOutputReset + BlockSizeUser,
+{
+    derive_key_utf8::<D>(
+        password,
+        salt,
+        Pkcs12KeyType::Mac,
+        iterations,
+        <D as Digest>::output_size(),
+    )
+    .map(Zeroizing::new)
+}
+
+/// Decode `T` from `ber`, as some exporters use BER with indefinite lengths
+/// inside the authenticated safe.
+fn decode_ber<T: DecodeOwned<Error = der::Error>>(ber: &[u8]) -> Result<T> {
+    Ok(T::from_der(Document::from_ber(ber)?.as_bytes())?)
+}
+
+/// Decrypt the BER-encoded `SafeContents` in `ber` and collect their keys
+/// and certificates, including the ones of nested `safeContentsBag`s.
+fn decrypt_bags(
+    ber: &[u8],
+    password: &str,
+    keys: &mut Vec<Entry<PrivateKeyInfo>>,
+    certificates: &mut Vec<Entry<Certificate>>,
+) -> Result<()> {
+    for bag in decode_ber::<Vec<SafeBag>>(ber)? {
+        let value = ContextSpecific::<AnyRef<'_>>::from_der(&bag.bag_value)?.value;
+
+        match bag.bag_id {
+            PKCS_12_KEY_BAG_OID => keys.push(Entry::new(value.decode_as()?, &bag)?),
+            PKCS_12_PKCS8_KEY_BAG_OID => {
+                let info = value.decode_as::<EncryptedPrivateKeyInfo>()?;
+                let plaintext = Zeroizing::new(decrypt_data(
+                    &info.encryption_algorithm,
+                    password,
+                    info.encrypted_data.as_bytes(),
+                )?);
+                keys.push(Entry::new(PrivateKeyInfo::from_der(&plaintext)?, &bag)?);
+            }
+            PKCS_12_CERT_BAG_OID => {
+                let cert_bag = value.decode_as::<CertBag>()?;
+                if cert_bag.cert_id == PKCS_12_X509_CERT_OID {
+                    let cert = Certificate::from_der(cert_bag.cert_value.as_bytes())?;
+                    certificates.push(Entry::new(cert, &bag)?);
+                }
+            }
+            PKCS_12_SAFE_CONTENTS_BAG_OID => {
+                decrypt_bags(&value.to_der()?, password, keys, certificates)?
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Decrypt `ciphertext` with the password-based encryption scheme `alg`.
+fn decrypt_data(
+    alg: &AlgorithmIdentifierOwned,
+    password: &str,
+    ciphertext: &[u8],
+) -> Result<Vec<u8>> {
+    match alg.oid {
+        #[cfg(feature = "legacy")]
+        oid if LegacyCipher::is_supported(oid) => {
+            let params = alg
+                .parameters
+                .as_ref()
+                .ok_or(Error::UnsupportedAlgorithm(oid))?
+                .decode_as::<Pkcs12PbeParams>()?;
+            LegacyCipher::new(oid, &params, password)?
+                .decrypt(ciphertext)
+                .ok_or(Error::WrongPassword)
+        }
+        PKCS_12_PBE_WITH_SHAAND3_KEY_TRIPLE_DES_CBC
+        | PKCS_12_PBE_WITH_SHAAND128_BIT_RC2_CBC
+        | PKCS_12_PBEWITH_SHAAND40_BIT_RC2_CBC => Err(Error::UnsupportedAlgorithm(alg.oid)),
+        _ => {
+            let scheme = pkcs5::EncryptionScheme::try_from(alg.to_der()?.as_slice())?;
+            Ok(scheme.decrypt(password, ciphertext)?)
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! Legacy PKCS#12 password-based encryption schemes
+//!
+//! `pbeWithSHAAnd3-KeyTripleDES-CBC`, `pbeWithSHAAnd128BitRC2-CBC` and
+//! `pbeWithSHAAnd40BitRC2-CBC` as defined in [RFC 7292 Appendix C], whose key
+//! and IV are derived with the PKCS#12 KDF and SHA-1.
+//!
+//! [RFC 7292 Appendix C]: https://www.rfc-editor.org/rfc/rfc7292#appendix-C
+
+use crate::kdf::{derive_key_utf8, Pkcs12KeyType};
+use crate::pbe_params::Pkcs12PbeParams;
+use crate::{
+    PKCS_12_PBEWITH_SHAAND40_BIT_RC2_CBC, PKCS_12_PBE_WITH_SHAAND128_BIT_RC2_CBC,
+    PKCS_12_PBE_WITH_SHAAND3_KEY_TRIPLE_DES_CBC,
+};
+use alloc::vec::Vec;
+use cbc::cipher::{block_padding::Pkcs7, BlockModeDecrypt, KeyIvInit};
+use const_oid::ObjectIdentifier;
+use rc2::cipher::{generic_array::GenericArray, BlockDecrypt};
+use zeroize::Zeroizing;
+
+#[cfg(feature = "builder")]
+use {cbc::cipher::BlockModeEncrypt, rc2::cipher::BlockEncrypt};
+
+/// RC2 and 3DES block size.
+const BLOCK_SIZE: usize = 8;
+
+/// Cipher with the key and IV derived from a password.
+pub(crate) struct LegacyCipher {
+    oid: ObjectIdentifier,
+    key: Zeroizing<Vec<u8>>,
+    iv: Zeroizing<Vec<u8>>,
+}
+
+impl LegacyCipher {
+    /// Is `oid` one of the supported legacy schemes?
+    pub(crate) fn is_supported(oid: ObjectIdentifier) -> bool {
+        matches!(
+            oid,
+            PKCS_12_PBE_WITH_SHAAND3_KEY_TRIPLE_DES_CBC
+                | PKCS_12_PBE_WITH_SHAAND128_BIT_RC2_CBC
+                | PKCS_12_PBEWITH_SHAAND40_BIT_RC2_CBC
+        )
+    }
+
+    /// Derive the key and IV of the scheme `oid` from `password`.
+    pub(crate) fn new(
+        oid: ObjectIdentifier,
+        params: &Pkcs12PbeParams,
+        password: &str,
+    ) -> der::Result<Self> {
+        let key_len = match oid {
+            PKCS_12_PBE_WITH_SHAAND3_KEY_TRIPLE_DES_CBC => 24,
+            PKCS_12_PBE_WITH_SHAAND128_BIT_RC2_CBC => 16,
+            _ => 5,
+        };
+        let derive = |id, len| {
+            derive_key_utf8::<sha1::Sha1>(
+                password,
+                params.salt.as_bytes(),
+                id,
+                params.iterations,
+                len,
+            )
+            .map(Zeroizing::new)
+        };
+
+        Ok(Self {
+            oid,
+            key: derive(Pkcs12KeyType::EncryptionKey, key_len)?,
+            iv: derive(Pkcs12KeyType::Iv, BLOCK_SIZE)?,
+        })
+    }
+
+    /// Decrypt `ciphertext`, returning `None` if the padding is invalid.
+    pub(crate) fn decrypt(&self, ciphertext: &[u8]) -> Option<Vec<u8>> {
+        if self.oid == PKCS_12_PBE_WITH_SHAAND3_KEY_TRIPLE_DES_CBC {
+            return cbc::Decryptor::<des::TdesEde3>::new_from_slices(&self.key, &self.iv)
+                .ok()?
+                .decrypt_padded_vec::<Pkcs7>(ciphertext)
+                .ok();
+        }
+
+        if ciphertext.is_empty() || ciphertext.len() % BLOCK_SIZE != 0 {
+            return None;
+        }
+
+        // the `rc2` crate only provides the block function, so CBC is done here
+        let cipher = self.rc2();
+        let mut plaintext = ciphertext.to_vec();
+        let mut previous = self.iv.as_slice();
+
+        for (block, ciphertext_block) in plaintext
+            .chunks_exact_mut(BLOCK_SIZE)
+            .zip(ciphertext.chunks_exact(BLOCK_SIZE))
+        {
+            cipher.decrypt_block(GenericArray::from_mut_slice(block));
+            block.iter_mut().zip(previous).for_each(|(b, p)| *b ^= p);
+            previous = ciphertext_block;
+        }
+
+        let pad = usize::from(*plaintext.last()?);
+        if pad == 0
+            || pad > BLOCK_SIZE
+            || !plaintext[plaintext.len() - pad..]
+                .iter()
+                .all(|&b| usize::from(b) == pad)
+        {
+            return None;
+        }
+        plaintext.truncate(plaintext.len() - pad);
+
+        Some(plaintext)
+    }
+
+    /// Encrypt `plaintext` with PKCS#7 padding.
+    #[cfg(feature = "builder")]
+    pub(crate) fn encrypt(&self, plaintext: &[u8]) -> Option<Vec<u8>> {
+        if self.oid == PKCS_12_PBE_WITH_SHAAND3_KEY_TRIPLE_DES_CBC {
+            return cbc::Encryptor::<des::TdesEde3>::new_from_slices(&self.key, &self.iv)
+                .ok()
+                .map(|cipher| cipher.encrypt_padded_vec::<Pkcs7>(plaintext));
+        }
+
+        let pad = BLOCK_SIZE - plaintext.len() % BLOCK_SIZE;
+        let mut ciphertext = plaintext.to_vec();
+        ciphertext.resize(plaintext.len() + pad, pad as u8);
+
+        let cipher = self.rc2();
+        let mut previous = GenericArray::clone_from_slice(&self.iv);
+
+        for block in ciphertext.chunks_exact_mut(BLOCK_SIZE) {
+            let block = GenericArray::from_mut_slice(block);
+            block.iter_mut().zip(&previous).for_each(|(b, p)| *b ^= p);
+            cipher.encrypt_block(block);
+            previous = *block;
+        }
+
+        Some(ciphertext)
+    }
+
+    /// RC2, using the whole key as effective key length.
+    fn rc2(&self) -> rc2::Rc2 {
+        rc2::Rc2::new_with_eff_key_len(&self.key, self.key.len() * 8)
+    }
+}
@@ -14,12 +14,26 @@
     unused_qualifications
 )]
 
+//! ## Decryption
+//! When the `decrypt` feature is enabled, [`Pfx::decrypt`] verifies the MAC
+//! of a password-protected PFX and extracts its private keys and
+//! certificates. The legacy PKCS#12 encryption schemes (3DES and RC2) used by
+//! older exporters additionally require the `legacy` feature.
+//!
+//! ## Building
+//! When the `builder` feature is enabled, [`builder::PfxBuilder`] produces a
+//! password-protected PFX from a private key and its certificate chain.
+
 extern crate alloc;
 
 pub mod pbe_params;
 pub mod pfx;
 pub mod safe_bag;
 
+#[cfg(feature = "builder")]
+pub mod builder;
+#[cfg(feature = "decrypt")]
+pub mod decrypt;
 #[cfg(feature = "kdf")]
 pub mod kdf;
 
@@ -30,6 +44,9 @@ mod crl_type;
 mod digest_info;
 mod mac_data;
 
+#[cfg(feature = "legacy")]
+mod legacy;
+
 pub use crate::{
     authenticated_safe::AuthenticatedSafe,
     bag_type::BagType,
@@ -103,9 +120,5 @@ pub const PKCS_12_X509_CERT_OID: ObjectIdentifier =
 pub const PKCS_12_SDSI_CERT_OID: ObjectIdentifier =
     ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.22.2");
 
-// todo: return the friendly name if present? (minimally, defer until BMPString support is available)
 // todo: support separate mac and encryption passwords?
-// todo: add decryption support
 // todo: add more encryption tests
-// todo: add a builder
-// todo: add RC2 support
@@ -60,16 +60,12 @@ impl<'a> ::der::DecodeValue<'a> for SafeBag {
 }
 impl ::der::EncodeValue for SafeBag {
     fn value_len(&self) -> ::der::Result<::der::Length> {
+        // `bag_value` is the complete `[0] EXPLICIT` TLV, as read by `decode_value`
         let content = AnyRef::from_der(&self.bag_value)?;
         use ::der::Encode as _;
         [
             self.bag_id.encoded_len()?,
-            ::der::asn1::ContextSpecificRef {
-                tag_number: ::der::TagNumber::N0,
-                tag_mode: ::der::TagMode::Explicit,
-                value: &content,
-            }
-            .encoded_len()?,
+            content.encoded_len()?,
             self.bag_attributes.encoded_len()?,
         ]
         .into_iter()
@@ -78,13 +74,7 @@ impl ::der::EncodeValue for SafeBag {
     fn encode_value(&self, writer: &mut impl ::der::Writer) -> ::der::Result<()> {
         use ::der::Encode as _;
         self.bag_id.encode(writer)?;
-        let content = AnyRef::from_der(&self.bag_value)?;
-        ::der::asn1::ContextSpecificRef {
-            tag_number: ::der::TagNumber::N0,
-            tag_mode: ::der::TagMode::Explicit,
-            value: &content,
-        }
-        .encode(writer)?;
+        AnyRef::from_der(&self.bag_value)?.encode(writer)?;
         self.bag_attributes.encode(writer)?;
         Ok(())
     }
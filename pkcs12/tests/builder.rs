@@ -0,0 +1,159 @@
+//! PFX builder tests
+
+#![cfg(feature = "builder")]
+
+use der::{Decode, Encode};
+use pkcs12::{
+    builder::{Encryption, Error, PfxBuilder},
+    pfx::Pfx,
+    safe_bag::PrivateKeyInfo,
+};
+use rand_core::{CryptoRng, OsRng, RngCore};
+use x509_cert::Certificate;
+
+fn builder() -> PfxBuilder {
+    let key = PrivateKeyInfo::from_der(include_bytes!("examples/ValidCertificatePathTest1EE.key"))
+        .unwrap();
+    let cert =
+        Certificate::from_der(include_bytes!("examples/ValidCertificatePathTest1EE.crt")).unwrap();
+    let ca = Certificate::from_der(include_bytes!("examples/GoodCACert.der")).unwrap();
+
+    let mut builder = PfxBuilder::new(key, cert);
+    builder.add_certificate(ca).friendly_name("Test1 EE");
+    builder
+}
+
+/// Deterministic RNG, so that the builder output can be compared with a
+/// fixture.
+struct CountingRng(u8);
+
+impl RngCore for CountingRng {
+    fn next_u32(&mut self) -> u32 {
+        rand_core::impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        rand_core::impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for byte in dest {
+            *byte = self.0;
+            self.0 = self.0.wrapping_add(1);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for CountingRng {}
+
+fn roundtrip(builder: &PfxBuilder) -> Pfx {
+    let pfx = builder.build_with_rng("hunter42", &mut OsRng).unwrap();
+    let der = pfx.to_der().unwrap();
+    let pfx = Pfx::from_der(&der).unwrap();
+    assert_eq!(pfx.to_der().unwrap(), der);
+
+    let (keys, certs) = pfx.decrypt("hunter42").unwrap();
+    assert_eq!(keys.len(), 1);
+    assert_eq!(
+        keys[0].value.to_der().unwrap(),
+        include_bytes!("examples/ValidCertificatePathTest1EE.key")
+    );
+    assert_eq!(keys[0].friendly_name.as_deref(), Some("Test1 EE"));
+
+    assert_eq!(certs.len(), 2);
+    assert_eq!(
+        certs[0].value.to_der().unwrap(),
+        include_bytes!("examples/ValidCertificatePathTest1EE.crt")
+    );
+    assert!(keys[0].pairs_with(&certs[0]));
+    assert_eq!(
+        certs[1].value.to_der().unwrap(),
+        include_bytes!("examples/GoodCACert.der")
+    );
+    assert!(!keys[0].pairs_with(&certs[1]));
+
+    assert_eq!(
+        pfx.decrypt("hunter41"),
+        Err(pkcs12::decrypt::Error::MacVerification)
+    );
+    pfx
+}
+
+#[test]
+fn build_pbes2() {
+    let pfx = roundtrip(&builder());
+    let mac_data = pfx.mac_data.unwrap();
+    assert_eq!(
+        mac_data.mac.algorithm.oid,
+        const_oid::db::rfc5912::ID_SHA_256
+    );
+    assert_eq!(mac_data.iterations, 2048);
+}
+
+#[test]
+fn build_iterations() {
+    let mut builder = builder();
+    let pfx = roundtrip(builder.iterations(1000).mac_iterations(1));
+    assert_eq!(pfx.mac_data.unwrap().iterations, 1);
+
+    assert_eq!(
+        builder
+            .iterations(0)
+            .build_with_rng("hunter42", &mut OsRng)
+            .err(),
+        Some(Error::Iterations)
+    );
+    assert_eq!(
+        builder
+            .iterations(1000)
+            .mac_iterations(u32::MAX)
+            .build_with_rng("hunter42", &mut OsRng)
+            .err(),
+        Some(Error::Iterations)
+    );
+}
+
+#[cfg(feature = "legacy")]
+#[test]
+fn build_legacy() {
+    let pfx = roundtrip(builder().encryption(Encryption::Legacy));
+    assert_eq!(
+        pfx.mac_data.unwrap().mac.algorithm.oid,
+        const_oid::db::rfc5912::ID_SHA_1
+    );
+}
+
+#[test]
+fn build_default_encryption() {
+    assert_eq!(Encryption::default(), Encryption::Aes256Cbc);
+}
+
+/// Builder output checked with `openssl pkcs12 -info`, see `examples/gen.sh`.
+#[test]
+fn build_pbes2_fixture() {
+    let pfx = builder()
+        .build_with_rng("hunter42", &mut CountingRng(0))
+        .unwrap();
+    assert_eq!(
+        pfx.to_der().unwrap(),
+        include_bytes!("examples/builder_pbes2.pfx")
+    );
+}
+
+#[cfg(feature = "legacy")]
+#[test]
+fn build_legacy_fixture() {
+    let pfx = builder()
+        .encryption(Encryption::Legacy)
+        .build_with_rng("hunter42", &mut CountingRng(0))
+        .unwrap();
+    assert_eq!(
+        pfx.to_der().unwrap(),
+        include_bytes!("examples/builder_legacy.pfx")
+    );
+}
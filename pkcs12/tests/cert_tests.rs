@@ -603,6 +603,10 @@ fn decode_sample_pfx2() {
     let auth_safe0_auth_safes_os =
         OctetString::from_der(&auth_safe0.content.to_der().unwrap()).unwrap();
     let safe_bags = SafeContents::from_der(auth_safe0_auth_safes_os.as_bytes()).unwrap();
+    assert_eq!(
+        auth_safe0_auth_safes_os.as_bytes(),
+        safe_bags.to_der().unwrap().as_slice()
+    );
     for safe_bag in safe_bags {
         match safe_bag.bag_id {
             pkcs12::PKCS_12_CERT_BAG_OID => {
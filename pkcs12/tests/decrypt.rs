@@ -0,0 +1,174 @@
+//! PFX decryption tests
+
+#![cfg(feature = "decrypt")]
+
+use der::{Decode, Encode};
+use pkcs12::{decrypt::Error, pfx::Pfx, safe_bag::PrivateKeyInfo};
+use x509_cert::Certificate;
+
+#[cfg(feature = "legacy")]
+use {
+    der::{asn1::OctetString, Document},
+    hex_literal::hex,
+};
+
+fn test1_ee_key() -> PrivateKeyInfo {
+    PrivateKeyInfo::from_der(include_bytes!("examples/ValidCertificatePathTest1EE.key")).unwrap()
+}
+
+#[test]
+fn decrypt_pbes2() {
+    // see gen.sh
+    let pfx = Pfx::from_der(include_bytes!("examples/example.pfx")).unwrap();
+    pfx.verify_mac("").unwrap();
+
+    let (keys, certs) = pfx.decrypt("").unwrap();
+    assert_eq!(keys.len(), 1);
+    assert_eq!(certs.len(), 1);
+    assert_eq!(
+        keys[0].value.to_der().unwrap(),
+        include_bytes!("examples/key.der")
+    );
+    assert_eq!(
+        certs[0].value,
+        Certificate::from_der(include_bytes!("examples/cert.der")).unwrap()
+    );
+    assert!(keys[0].local_key_id.is_some());
+    assert!(keys[0].pairs_with(&certs[0]));
+}
+
+#[test]
+fn decrypt_unencrypted_key_bag() {
+    let pfx = Pfx::from_der(include_bytes!("examples/example7.pfx")).unwrap();
+    let (keys, certs) = pfx.decrypt("1234").unwrap();
+    assert_eq!(keys.len(), 1);
+    assert_eq!(certs.len(), 2);
+    assert_eq!(
+        certs.iter().filter(|cert| keys[0].pairs_with(cert)).count(),
+        1
+    );
+}
+
+#[test]
+fn decrypt_wrong_password() {
+    let pfx = Pfx::from_der(include_bytes!("examples/example4.pfx")).unwrap();
+    assert_eq!(pfx.verify_mac("4321"), Err(Error::MacVerification));
+    assert_eq!(pfx.decrypt("4321"), Err(Error::MacVerification));
+    assert_eq!(pfx.decrypt("1234").unwrap().0.len(), 1);
+
+    // without a MAC, the wrong password is caught by the decryption
+    let pfx = Pfx::from_der(include_bytes!("examples/example5.pfx")).unwrap();
+    assert_eq!(pfx.verify_mac("1234"), Err(Error::MacNotFound));
+    assert_eq!(pfx.decrypt("4321"), Err(Error::WrongPassword));
+    assert_eq!(pfx.decrypt("1234").unwrap().1.len(), 1);
+}
+
+#[test]
+fn decrypt_mac_algorithms() {
+    for der in [
+        &include_bytes!("examples/example8.pfx")[..],  // SHA-384
+        &include_bytes!("examples/example9.pfx")[..],  // SHA-512
+        &include_bytes!("examples/example13.pfx")[..], // single iteration
+    ] {
+        Pfx::from_der(der).unwrap().verify_mac("1234").unwrap();
+    }
+
+    let pfx = Pfx::from_der(include_bytes!("examples/example11.pfx")).unwrap();
+    assert_eq!(
+        pfx.decrypt("1234"),
+        Err(Error::UnsupportedAlgorithm(
+            const_oid::db::rfc7693::ID_BLAKE_2_B_512
+        ))
+    );
+}
+
+#[test]
+fn decrypt_windows_aes() {
+    let pfx = Pfx::from_der(include_bytes!(
+        "examples/ValidCertificatePathTest1EE_windows_aes.p12.pfx"
+    ))
+    .unwrap();
+    let (keys, certs) = pfx.decrypt("password").unwrap();
+
+    // Windows adds a key usage attribute to the key
+    assert_eq!(keys[0].value.private_key, test1_ee_key().private_key);
+    assert!(keys[0].value.attributes.is_some());
+    assert!(keys[0].pairs_with(&certs[0]));
+}
+
+#[cfg(not(feature = "legacy"))]
+#[test]
+fn decrypt_legacy_unsupported() {
+    let pfx = Pfx::from_der(include_bytes!(
+        "examples/ValidCertificatePathTest1EE_windows_tdes.p12.pfx"
+    ))
+    .unwrap();
+    assert_eq!(
+        pfx.decrypt("password"),
+        Err(Error::UnsupportedAlgorithm(
+            pkcs12::PKCS_12_PBE_WITH_SHAAND3_KEY_TRIPLE_DES_CBC
+        ))
+    );
+}
+
+#[cfg(feature = "legacy")]
+#[test]
+fn decrypt_legacy() {
+    let key = test1_ee_key();
+    let cert =
+        Certificate::from_der(include_bytes!("examples/ValidCertificatePathTest1EE.crt")).unwrap();
+
+    for (der, encryption) in [
+        (
+            Document::from_der(include_bytes!("examples/ValidCertificatePathTest1EE.p12")).unwrap(),
+            "3DES",
+        ),
+        (
+            Document::from_der(include_bytes!(
+                "examples/ValidCertificatePathTest1EE_windows_tdes.p12.pfx"
+            ))
+            .unwrap(),
+            "3DES",
+        ),
+        (
+            Document::from_der(include_bytes!(
+                "examples/ValidCertificatePathTest1EE_macos.p12"
+            ))
+            .unwrap(),
+            "3DES and RC2-40",
+        ),
+        (
+            Document::from_ber(include_bytes!(
+                "examples/ValidCertificatePathTest1EE_firefox.p12"
+            ))
+            .unwrap(),
+            "3DES and RC2-40",
+        ),
+    ] {
+        let pfx = Pfx::from_der(der.as_bytes()).unwrap();
+        let (keys, certs) = pfx.decrypt("password").unwrap();
+        assert_eq!(keys.len(), 1, "{encryption}");
+        assert_eq!(keys[0].value.private_key, key.private_key, "{encryption}");
+        assert_eq!(certs.len(), 1, "{encryption}");
+        assert_eq!(certs[0].value, cert, "{encryption}");
+        assert!(keys[0].pairs_with(&certs[0]), "{encryption}");
+        assert_eq!(pfx.decrypt("wrong"), Err(Error::MacVerification));
+    }
+}
+
+#[cfg(feature = "legacy")]
+#[test]
+fn decrypt_legacy_attributes() {
+    let pfx = Pfx::from_der(include_bytes!("examples/ValidCertificatePathTest1EE.p12")).unwrap();
+    let (keys, certs) = pfx.decrypt("password").unwrap();
+    for entry_id in [&keys[0].local_key_id, &certs[0].local_key_id] {
+        assert_eq!(
+            entry_id,
+            &Some(OctetString::new(hex!("E128464BE734D0F84BD928516C50F15A18B52B96")).unwrap())
+        );
+    }
+    assert_eq!(
+        certs[0].friendly_name.as_deref(),
+        Some("Valid Certificate Path Test1 EE")
+    );
+}
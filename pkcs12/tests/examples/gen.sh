@@ -1,3 +1,7 @@
 openssl req -x509 -newkey rsa:1024 -keyout key.pem -out cert.pem -sha256 -days 365 -noenc -subj "/C=DK/ST=Hovedstaden/L=København/O=.../OU=.../CN=.../emailAddress=..."
 openssl pkcs12 -export -out example.pfx -inkey key.pem -in cert.pem -passout pass:
-openssl pkcs12 -export -out example2.pfx -in cert.pem -inkey key.pem -certpbe NONE -passout pass:1234
\ No newline at end of file
+openssl pkcs12 -export -out example2.pfx -in cert.pem -inkey key.pem -certpbe NONE -passout pass:1234
+
+# builder_pbes2.pfx and builder_legacy.pfx are PfxBuilder output (see tests/builder.rs), checked with:
+openssl pkcs12 -info -in builder_pbes2.pfx -passin pass:hunter42 -noenc
+openssl pkcs12 -info -in builder_legacy.pfx -passin pass:hunter42 -noenc -legacy
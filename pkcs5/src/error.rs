@@ -22,7 +22,17 @@ pub enum Error {
     /// Encryption Failed
     EncryptFailed,
 
-    /// Pbes1 support is limited to parsing; encryption/decryption is not supported (won't fix)
+    /// The memory needed by scrypt to derive the key exceeds the limit.
+    ///
+    /// Returned before deriving the key, so that untrusted parameters can't
+    /// trigger arbitrarily large allocations.
+    MemoryLimitExceeded {
+        /// Maximum number of bytes scrypt may use.
+        limit: u64,
+    },
+
+    /// PBES1 encryption is not supported (won't fix), and PBES1 decryption
+    /// requires the `pbes1-insecure` feature.
     #[cfg(feature = "pbes2")]
     NoPbes1CryptSupport,
 
@@ -34,6 +44,13 @@ pub enum Error {
         /// OID of unsupported algorithm
         oid: ObjectIdentifier,
     },
+
+    /// Decryption failed because the password is wrong
+    ///
+    /// Returned when the GCM authentication tag doesn't match or the CBC
+    /// padding is invalid. Corrupted ciphertexts fail the same way, as the
+    /// two cases can't be told apart.
+    WrongPassword,
 }
 
 #[cfg(feature = "std")]
@@ -47,13 +64,19 @@ impl fmt::Display for Error {
             }
             Error::DecryptFailed => f.write_str("PKCS#5 decryption failed"),
             Error::EncryptFailed => f.write_str("PKCS#5 encryption failed"),
+            Error::MemoryLimitExceeded { limit } => {
+                write!(f, "PKCS#5 scrypt parameters need more than {} bytes of memory", limit)
+            }
             #[cfg(feature = "pbes2")]
             Error::NoPbes1CryptSupport => {
-                f.write_str("PKCS#5 encryption/decryption unsupported for PBES1 (won't fix)")
+                f.write_str("PKCS#5 encryption unsupported for PBES1; decryption requires the `pbes1-insecure` feature")
             }
             Error::UnsupportedAlgorithm { oid } => {
                 write!(f, "PKCS#5 algorithm {} is unsupported", oid)
             }
+            Error::WrongPassword => {
+                f.write_str("PKCS#5 decryption failed: wrong password or corrupted data")
+            }
         }
     }
 }
@@ -74,6 +74,9 @@ impl EncryptionScheme {
     pub fn decrypt(&self, password: impl AsRef<[u8]>, ciphertext: &[u8]) -> Result<Vec<u8>> {
         match self {
             Self::Pbes2(params) => params.decrypt(password, ciphertext),
+            #[cfg(feature = "pbes1-insecure")]
+            Self::Pbes1(alg) => alg.decrypt(password, ciphertext),
+            #[cfg(not(feature = "pbes1-insecure"))]
             Self::Pbes1(_) => Err(Error::NoPbes1CryptSupport),
         }
     }
@@ -92,6 +95,9 @@ impl EncryptionScheme {
     ) -> Result<&'a [u8]> {
         match self {
             Self::Pbes2(params) => params.decrypt_in_place(password, buffer),
+            #[cfg(feature = "pbes1-insecure")]
+            Self::Pbes1(alg) => alg.decrypt_in_place(password, buffer),
+            #[cfg(not(feature = "pbes1-insecure"))]
             Self::Pbes1(_) => Err(Error::NoPbes1CryptSupport),
         }
     }
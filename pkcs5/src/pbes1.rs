@@ -2,12 +2,20 @@
 //!
 //! [RFC 8018 Section 6.1]: https://tools.ietf.org/html/rfc8018#section-6.1
 
+#[cfg(feature = "pbes1-insecure")]
+mod decryption;
+#[cfg(feature = "pbes1-insecure")]
+mod rc2_cbc;
+
 use crate::AlgorithmIdentifierRef;
 use der::{
     asn1::{AnyRef, ObjectIdentifier, OctetStringRef},
     Decode, DecodeValue, Encode, EncodeValue, ErrorKind, Length, Reader, Sequence, Tag, Writer,
 };
 
+#[cfg(all(feature = "alloc", feature = "pbes1-insecure"))]
+use alloc::vec::Vec;
+
 /// `pbeWithMD2AndDES-CBC` Object Identifier (OID).
 pub const PBE_WITH_MD2_AND_DES_CBC_OID: ObjectIdentifier =
     ObjectIdentifier::new_unwrap("1.2.840.113549.1.5.1");
@@ -64,6 +72,32 @@ impl Algorithm {
     pub fn oid(&self) -> ObjectIdentifier {
         self.encryption.oid()
     }
+
+    /// Attempt to decrypt the given ciphertext, allocating and returning a
+    /// byte vector containing the plaintext.
+    #[cfg(all(feature = "alloc", feature = "pbes1-insecure"))]
+    pub fn decrypt(&self, password: impl AsRef<[u8]>, ciphertext: &[u8]) -> crate::Result<Vec<u8>> {
+        let mut buffer = ciphertext.to_vec();
+        let pt_len = self.decrypt_in_place(password, &mut buffer)?.len();
+        buffer.truncate(pt_len);
+        Ok(buffer)
+    }
+
+    /// Attempt to decrypt the given ciphertext in-place using a key derived
+    /// from the provided password and these parameters.
+    ///
+    /// Only `pbeWithSHA1AndDES-CBC` and `pbeWithSHA1AndRC2-CBC` are
+    /// supported: the other schemes return
+    /// [`Error::UnsupportedAlgorithm`][`crate::Error::UnsupportedAlgorithm`].
+    /// There is no support for encrypting with PBES1.
+    #[cfg(feature = "pbes1-insecure")]
+    pub fn decrypt_in_place<'a>(
+        &self,
+        password: impl AsRef<[u8]>,
+        buffer: &'a mut [u8],
+    ) -> crate::Result<&'a [u8]> {
+        decryption::decrypt_in_place(self, password, buffer)
+    }
 }
 
 impl<'a> DecodeValue<'a> for Algorithm {
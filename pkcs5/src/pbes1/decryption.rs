@@ -0,0 +1,57 @@
+//! PBES1 decryption.
+
+use super::{rc2_cbc, Algorithm, EncryptionScheme};
+use crate::{Error, Result};
+use cbc::cipher::{block_padding::Pkcs7, BlockModeDecrypt, KeyIvInit};
+use sha1::{Digest, Sha1};
+
+/// Decrypt a message encrypted with `pbeWithSHA1AndDES-CBC` or
+/// `pbeWithSHA1AndRC2-CBC`.
+pub fn decrypt_in_place<'a>(
+    alg: &Algorithm,
+    password: impl AsRef<[u8]>,
+    buf: &'a mut [u8],
+) -> Result<&'a [u8]> {
+    if !matches!(
+        alg.encryption,
+        EncryptionScheme::PbeWithSha1AndDesCbc | EncryptionScheme::PbeWithSha1AndRc2Cbc
+    ) {
+        return Err(Error::UnsupportedAlgorithm { oid: alg.oid() });
+    }
+
+    let dk = pbkdf1_sha1(password.as_ref(), alg)?;
+    let key = &dk[..8];
+    let mut iv = [0u8; 8];
+    iv.copy_from_slice(&dk[8..16]);
+
+    if alg.encryption == EncryptionScheme::PbeWithSha1AndRc2Cbc {
+        return rc2_cbc::decrypt_in_place(key, &iv, buf);
+    }
+
+    cbc::Decryptor::<des::Des>::new_from_slices(key, &iv)
+        .map_err(|_| Error::AlgorithmParametersInvalid { oid: alg.oid() })?
+        .decrypt_padded::<Pkcs7>(buf)
+        .map_err(|_| Error::WrongPassword)
+}
+
+/// Derive the key and IV using PBKDF1 as defined in
+/// [RFC 8018 Section 5.1], with SHA-1 as the underlying hash function.
+///
+/// [RFC 8018 Section 5.1]: https://tools.ietf.org/html/rfc8018#section-5.1
+fn pbkdf1_sha1(password: &[u8], alg: &Algorithm) -> Result<[u8; 20]> {
+    if alg.parameters.iteration_count == 0 {
+        return Err(Error::AlgorithmParametersInvalid { oid: alg.oid() });
+    }
+
+    let mut dk: [u8; 20] = Sha1::new()
+        .chain_update(password)
+        .chain_update(alg.parameters.salt)
+        .finalize()
+        .into();
+
+    for _ in 1..alg.parameters.iteration_count {
+        dk = Sha1::digest(dk).into();
+    }
+
+    Ok(dk)
+}
@@ -0,0 +1,62 @@
+//! RC2-CBC decryption with PKCS#7 padding.
+//!
+//! The `rc2` crate only provides the block function, so the CBC mode is
+//! implemented here.
+
+use crate::{Error, Result};
+use rc2::{
+    cipher::{generic_array::GenericArray, BlockDecrypt},
+    Rc2,
+};
+
+/// RC2 block size.
+const BLOCK_SIZE: usize = 8;
+
+/// Decrypt `buffer` in-place, returning the plaintext with its padding
+/// removed.
+///
+/// The whole key is used as effective key length. Returns
+/// [`Error::WrongPassword`] if the buffer is not a whole number of blocks or
+/// its padding is invalid.
+pub(crate) fn decrypt_in_place<'a>(
+    key: &[u8],
+    iv: &[u8; BLOCK_SIZE],
+    buffer: &'a mut [u8],
+) -> Result<&'a [u8]> {
+    let cipher = rc2(key).ok_or(Error::DecryptFailed)?;
+
+    if buffer.is_empty() || buffer.len() % BLOCK_SIZE != 0 {
+        return Err(Error::WrongPassword);
+    }
+
+    let mut previous = *iv;
+
+    for block in buffer.chunks_exact_mut(BLOCK_SIZE) {
+        let mut ciphertext = [0u8; BLOCK_SIZE];
+        ciphertext.copy_from_slice(block);
+
+        cipher.decrypt_block(GenericArray::from_mut_slice(block));
+        block.iter_mut().zip(&previous).for_each(|(b, p)| *b ^= p);
+        previous = ciphertext;
+    }
+
+    let pad = buffer.last().copied().map(usize::from).unwrap_or_default();
+    let pt_len = buffer
+        .len()
+        .checked_sub(pad)
+        .filter(|_| (1..=BLOCK_SIZE).contains(&pad))
+        .ok_or(Error::WrongPassword)?;
+
+    if !buffer[pt_len..].iter().all(|&b| usize::from(b) == pad) {
+        return Err(Error::WrongPassword);
+    }
+
+    Ok(&buffer[..pt_len])
+}
+
+/// Initialize RC2 with `key`, which must be 1 to 128 bytes long.
+fn rc2(key: &[u8]) -> Option<Rc2> {
+    (1..=128)
+        .contains(&key.len())
+        .then(|| Rc2::new_with_eff_key_len(key, key.len() * 8))
+}
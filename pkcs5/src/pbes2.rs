@@ -8,15 +8,17 @@ mod kdf;
 mod encryption;
 
 pub use self::kdf::{
-    Kdf, Pbkdf2Params, Pbkdf2Prf, Salt, ScryptParams, HMAC_WITH_SHA1_OID, HMAC_WITH_SHA256_OID,
-    PBKDF2_OID, SCRYPT_OID,
+    Kdf, Pbkdf2Params, Pbkdf2Prf, Salt, ScryptParams, ScryptParamsBuilder, HMAC_WITH_SHA1_OID,
+    HMAC_WITH_SHA256_OID, PBKDF2_OID, SCRYPT_OID,
 };
 
 use crate::{AlgorithmIdentifierRef, Error, Result};
 use der::{
     asn1::{AnyRef, ObjectIdentifier, OctetStringRef},
-    Decode, DecodeValue, Encode, EncodeValue, ErrorKind, Length, Reader, Sequence, Tag, Writer,
+    Decode, DecodeValue, Encode, EncodeValue, ErrorKind, Length, Reader, Sequence, Tag, Tagged,
+    Writer,
 };
+use spki::AlgorithmIdentifier;
 
 #[cfg(feature = "rand_core")]
 use rand_core::CryptoRngCore;
@@ -43,6 +45,10 @@ pub const AES_256_CBC_OID: ObjectIdentifier =
 pub const AES_128_GCM_OID: ObjectIdentifier =
     ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.1.6");
 
+/// 192-bit Advanced Encryption Standard (AES) algorithm with Galois Counter Mode
+pub const AES_192_GCM_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.1.26");
+
 /// 256-bit Advanced Encryption Standard (AES) algorithm with Galois Counter Mode
 pub const AES_256_GCM_OID: ObjectIdentifier =
     ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.1.46");
@@ -69,6 +75,14 @@ const AES_BLOCK_SIZE: usize = 16;
 /// with 96 bit nonces
 const GCM_NONCE_SIZE: usize = 12;
 
+/// Default GCM authentication tag (ICV) length, as defined in [RFC 5084 Section 3.2].
+///
+/// [RFC 5084 Section 3.2]: https://datatracker.ietf.org/doc/html/rfc5084#section-3.2
+const GCM_DEFAULT_ICV_LEN: u8 = 12;
+
+/// GCM authentication tag length when the parameters only consist of the nonce.
+const GCM_NONCE_ONLY_ICV_LEN: u8 = 16;
+
 /// DES / Triple DES block size
 #[cfg(any(feature = "3des", feature = "des-insecure"))]
 const DES_BLOCK_SIZE: usize = 8;
@@ -182,6 +196,36 @@ impl Parameters {
             .expect("invalid scrypt parameters")
     }
 
+    /// Generate PBES2 parameters using scrypt as the password hashing
+    /// algorithm and AES-256-GCM as the authenticated encryption algorithm,
+    /// along with a randomly generated salt and 96-bit nonce.
+    ///
+    /// scrypt parameters are the same as [`Parameters::scrypt`]. The GCM
+    /// parameters are encoded as a [RFC 5084] `GCMParameters` structure with
+    /// a 128-bit authentication tag.
+    ///
+    /// [RFC 5084]: https://datatracker.ietf.org/doc/html/rfc5084#section-3.2
+    #[cfg(all(feature = "pbes2", feature = "rand_core"))]
+    pub fn scrypt_gcm(rng: &mut impl CryptoRngCore) -> Self {
+        let mut nonce = [0u8; GCM_NONCE_SIZE];
+        rng.fill_bytes(&mut nonce);
+
+        let mut salt = [0u8; Self::DEFAULT_SALT_LEN];
+        rng.fill_bytes(&mut salt);
+
+        let kdf = scrypt::Params::new(14, 8, 1, 32)
+            .ok()
+            .and_then(|params| ScryptParams::from_params_and_salt(params, &salt).ok())
+            .expect("invalid scrypt parameters")
+            .into();
+
+        let encryption = EncryptionScheme::Aes256GcmParams {
+            params: GcmParameters { nonce, icv_len: 16 },
+        };
+
+        Self { kdf, encryption }
+    }
+
     /// Initialize PBES2 parameters using scrypt as the password-based
     /// key derivation function and AES-128-CBC as the symmetric cipher.
     ///
@@ -222,6 +266,9 @@ impl Parameters {
     /// Initialize PBES2 parameters using scrypt as the password-based
     /// key derivation function and AES-128-GCM as the symmetric cipher.
     ///
+    /// The GCM parameters only consist of the nonce, as emitted by e.g. Botan,
+    /// and a 128-bit authentication tag is used.
+    ///
     /// For more information on scrypt parameters, see documentation for the
     /// [`scrypt::Params`] struct.
     // TODO(tarcieri): encapsulate `scrypt::Params`?
@@ -239,6 +286,9 @@ impl Parameters {
     /// Initialize PBES2 parameters using scrypt as the password-based
     /// key derivation function and AES-256-GCM as the symmetric cipher.
     ///
+    /// The GCM parameters only consist of the nonce, as emitted by e.g. Botan,
+    /// and a 128-bit authentication tag is used.
+    ///
     /// For more information on scrypt parameters, see documentation for the
     /// [`scrypt::Params`] struct.
     // TODO(tarcieri): encapsulate `scrypt::Params`?
@@ -369,18 +419,51 @@ pub enum EncryptionScheme {
         iv: [u8; AES_BLOCK_SIZE],
     },
 
-    /// AES-128 in CBC mode
+    /// AES-128 in GCM mode, with parameters consisting only of the nonce
+    /// and a 16-byte authentication tag
     Aes128Gcm {
         /// GCM nonce
         nonce: [u8; GCM_NONCE_SIZE],
     },
 
-    /// AES-256 in GCM mode
+    /// AES-128 in GCM mode, with [RFC 5084] `GCMParameters`
+    ///
+    /// [RFC 5084]: https://datatracker.ietf.org/doc/html/rfc5084#section-3.2
+    Aes128GcmParams {
+        /// GCM nonce and authentication tag length
+        params: GcmParameters,
+    },
+
+    /// AES-192 in GCM mode, with parameters consisting only of the nonce
+    /// and a 16-byte authentication tag
+    Aes192Gcm {
+        /// GCM nonce
+        nonce: [u8; GCM_NONCE_SIZE],
+    },
+
+    /// AES-192 in GCM mode, with [RFC 5084] `GCMParameters`
+    ///
+    /// [RFC 5084]: https://datatracker.ietf.org/doc/html/rfc5084#section-3.2
+    Aes192GcmParams {
+        /// GCM nonce and authentication tag length
+        params: GcmParameters,
+    },
+
+    /// AES-256 in GCM mode, with parameters consisting only of the nonce
+    /// and a 16-byte authentication tag
     Aes256Gcm {
         /// GCM nonce
         nonce: [u8; GCM_NONCE_SIZE],
     },
 
+    /// AES-256 in GCM mode, with [RFC 5084] `GCMParameters`
+    ///
+    /// [RFC 5084]: https://datatracker.ietf.org/doc/html/rfc5084#section-3.2
+    Aes256GcmParams {
+        /// GCM nonce and authentication tag length
+        params: GcmParameters,
+    },
+
     /// 3-Key Triple DES in CBC mode
     #[cfg(feature = "3des")]
     DesEde3Cbc {
@@ -404,7 +487,11 @@ impl EncryptionScheme {
             Self::Aes192Cbc { .. } => 24,
             Self::Aes256Cbc { .. } => 32,
             Self::Aes128Gcm { .. } => 16,
+            Self::Aes128GcmParams { .. } => 16,
+            Self::Aes192Gcm { .. } => 24,
+            Self::Aes192GcmParams { .. } => 24,
             Self::Aes256Gcm { .. } => 32,
+            Self::Aes256GcmParams { .. } => 32,
             #[cfg(feature = "des-insecure")]
             Self::DesCbc { .. } => 8,
             #[cfg(feature = "3des")]
@@ -418,8 +505,9 @@ impl EncryptionScheme {
             Self::Aes128Cbc { .. } => AES_128_CBC_OID,
             Self::Aes192Cbc { .. } => AES_192_CBC_OID,
             Self::Aes256Cbc { .. } => AES_256_CBC_OID,
-            Self::Aes128Gcm { .. } => AES_128_GCM_OID,
-            Self::Aes256Gcm { .. } => AES_256_GCM_OID,
+            Self::Aes128Gcm { .. } | Self::Aes128GcmParams { .. } => AES_128_GCM_OID,
+            Self::Aes192Gcm { .. } | Self::Aes192GcmParams { .. } => AES_192_GCM_OID,
+            Self::Aes256Gcm { .. } | Self::Aes256GcmParams { .. } => AES_256_GCM_OID,
             #[cfg(feature = "des-insecure")]
             Self::DesCbc { .. } => DES_CBC_OID,
             #[cfg(feature = "3des")]
@@ -427,6 +515,29 @@ impl EncryptionScheme {
         }
     }
 
+    /// Get the length of the authentication tag appended to the ciphertext in
+    /// bytes, for authenticated encryption schemes.
+    pub fn tag_len(&self) -> Option<usize> {
+        match self {
+            Self::Aes128Gcm { .. } | Self::Aes192Gcm { .. } | Self::Aes256Gcm { .. } => {
+                Some(GCM_NONCE_ONLY_ICV_LEN.into())
+            }
+            _ => self.gcm_parameters().map(|params| params.icv_len.into()),
+        }
+    }
+
+    /// Get the [RFC 5084] `GCMParameters`, if this scheme uses them.
+    ///
+    /// [RFC 5084]: https://datatracker.ietf.org/doc/html/rfc5084#section-3.2
+    pub fn gcm_parameters(&self) -> Option<GcmParameters> {
+        match *self {
+            Self::Aes128GcmParams { params }
+            | Self::Aes192GcmParams { params }
+            | Self::Aes256GcmParams { params } => Some(params),
+            _ => None,
+        }
+    }
+
     /// Convenience function to turn the OID (see [`oid`](Self::oid))
     /// of this [`EncryptionScheme`] into error case
     /// [`Error::AlgorithmParametersInvalid`]
@@ -447,6 +558,20 @@ impl TryFrom<AlgorithmIdentifierRef<'_>> for EncryptionScheme {
     type Error = der::Error;
 
     fn try_from(alg: AlgorithmIdentifierRef<'_>) -> der::Result<Self> {
+        if let (AES_128_GCM_OID | AES_192_GCM_OID | AES_256_GCM_OID, Some(params)) =
+            (alg.oid, alg.parameters)
+        {
+            if params.tag() == Tag::Sequence {
+                let params = params.decode_as()?;
+
+                return Ok(match alg.oid {
+                    AES_128_GCM_OID => Self::Aes128GcmParams { params },
+                    AES_192_GCM_OID => Self::Aes192GcmParams { params },
+                    _ => Self::Aes256GcmParams { params },
+                });
+            }
+        }
+
         // TODO(tarcieri): support for non-AES algorithms?
         let iv = match alg.parameters {
             Some(params) => params.decode_as::<OctetStringRef<'_>>()?.as_bytes(),
@@ -466,6 +591,9 @@ impl TryFrom<AlgorithmIdentifierRef<'_>> for EncryptionScheme {
             AES_128_GCM_OID => Ok(Self::Aes128Gcm {
                 nonce: iv.try_into().map_err(|_| Tag::OctetString.value_error())?,
             }),
+            AES_192_GCM_OID => Ok(Self::Aes192Gcm {
+                nonce: iv.try_into().map_err(|_| Tag::OctetString.value_error())?,
+            }),
             AES_256_GCM_OID => Ok(Self::Aes256Gcm {
                 nonce: iv.try_into().map_err(|_| Tag::OctetString.value_error())?,
             }),
@@ -486,6 +614,10 @@ impl TryFrom<AlgorithmIdentifierRef<'_>> for EncryptionScheme {
     }
 }
 
+/// Returns an error for GCM schemes with [RFC 5084] `GCMParameters`, which can't be
+/// borrowed from the [`EncryptionScheme`]: use its [`Encode`] implementation instead.
+///
+/// [RFC 5084]: https://datatracker.ietf.org/doc/html/rfc5084#section-3.2
 impl<'a> TryFrom<&'a EncryptionScheme> for AlgorithmIdentifierRef<'a> {
     type Error = der::Error;
 
@@ -495,7 +627,13 @@ impl<'a> TryFrom<&'a EncryptionScheme> for AlgorithmIdentifierRef<'a> {
             EncryptionScheme::Aes192Cbc { iv } => iv.as_slice(),
             EncryptionScheme::Aes256Cbc { iv } => iv.as_slice(),
             EncryptionScheme::Aes128Gcm { nonce } => nonce.as_slice(),
+            EncryptionScheme::Aes192Gcm { nonce } => nonce.as_slice(),
             EncryptionScheme::Aes256Gcm { nonce } => nonce.as_slice(),
+            EncryptionScheme::Aes128GcmParams { .. }
+            | EncryptionScheme::Aes192GcmParams { .. }
+            | EncryptionScheme::Aes256GcmParams { .. } => {
+                return Err(Tag::Sequence.value_error());
+            }
             #[cfg(feature = "des-insecure")]
             EncryptionScheme::DesCbc { iv } => iv.as_slice(),
             #[cfg(feature = "3des")]
@@ -511,10 +649,88 @@ impl<'a> TryFrom<&'a EncryptionScheme> for AlgorithmIdentifierRef<'a> {
 
 impl Encode for EncryptionScheme {
     fn encoded_len(&self) -> der::Result<Length> {
-        AlgorithmIdentifierRef::try_from(self)?.encoded_len()
+        match self.gcm_parameters() {
+            Some(params) => AlgorithmIdentifier {
+                oid: self.oid(),
+                parameters: Some(params),
+            }
+            .encoded_len(),
+            None => AlgorithmIdentifierRef::try_from(self)?.encoded_len(),
+        }
     }
 
     fn encode(&self, writer: &mut impl Writer) -> der::Result<()> {
-        AlgorithmIdentifierRef::try_from(self)?.encode(writer)
+        match self.gcm_parameters() {
+            Some(params) => AlgorithmIdentifier {
+                oid: self.oid(),
+                parameters: Some(params),
+            }
+            .encode(writer),
+            None => AlgorithmIdentifierRef::try_from(self)?.encode(writer),
+        }
     }
 }
+
+/// GCM parameters as defined in [RFC 5084 Section 3.2].
+///
+/// ```text
+/// GCMParameters ::= SEQUENCE {
+///     aes-nonce        OCTET STRING, -- recommended size is 12 octets
+///     aes-ICVlen       AES-GCM-ICVlen DEFAULT 12 }
+///
+/// AES-GCM-ICVlen ::= INTEGER (12 | 13 | 14 | 15 | 16)
+/// ```
+///
+/// [RFC 5084 Section 3.2]: https://datatracker.ietf.org/doc/html/rfc5084#section-3.2
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct GcmParameters {
+    /// GCM nonce (`aes-nonce`)
+    pub nonce: [u8; GCM_NONCE_SIZE],
+
+    /// Authentication tag length in bytes (`aes-ICVlen`)
+    pub icv_len: u8,
+}
+
+impl GcmParameters {
+    /// Encoded ICV length, omitted when it is the default.
+    fn encoded_icv_len(&self) -> Option<u8> {
+        Some(self.icv_len).filter(|len| *len != GCM_DEFAULT_ICV_LEN)
+    }
+}
+
+impl<'a> DecodeValue<'a> for GcmParameters {
+    type Error = der::Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: der::Header) -> der::Result<Self> {
+        reader.read_nested(header.length, |reader| {
+            let nonce = OctetStringRef::decode(reader)?
+                .as_bytes()
+                .try_into()
+                .map_err(|_| Tag::OctetString.value_error())?;
+
+            let icv_len = match Option::<u8>::decode(reader)? {
+                // DER requires the default value to be omitted
+                Some(GCM_DEFAULT_ICV_LEN) => return Err(Tag::Integer.non_canonical_error()),
+                Some(len @ 13..=16) => len,
+                Some(_) => return Err(Tag::Integer.value_error()),
+                None => GCM_DEFAULT_ICV_LEN,
+            };
+
+            Ok(Self { nonce, icv_len })
+        })
+    }
+}
+
+impl EncodeValue for GcmParameters {
+    fn value_len(&self) -> der::Result<Length> {
+        OctetStringRef::new(&self.nonce)?.encoded_len()? + self.encoded_icv_len().encoded_len()?
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
+        OctetStringRef::new(&self.nonce)?.encode(writer)?;
+        self.encoded_icv_len().encode(writer)?;
+        Ok(())
+    }
+}
+
+impl Sequence<'_> for GcmParameters {}
@@ -1,6 +1,8 @@
 //! PBES2 encryption.
 
-use super::{EncryptionScheme, Kdf, Parameters, Pbkdf2Params, Pbkdf2Prf, ScryptParams};
+use super::{
+    EncryptionScheme, GcmParameters, Kdf, Parameters, Pbkdf2Params, Pbkdf2Prf, ScryptParams,
+};
 use crate::{Error, Result};
 use aes_gcm::{AeadInPlace, KeyInit as GcmKeyInit, Nonce, Tag};
 use cbc::cipher::{
@@ -12,7 +14,7 @@ use pbkdf2::{
         digest::{
             block_buffer::Eager,
             core_api::{BlockSizeUser, BufferKindUser, FixedOutputCore, UpdateCore},
-            typenum::{IsLess, Le, NonZero, U12, U16, U256},
+            typenum::{IsLess, Le, NonZero, U12, U13, U14, U15, U16, U256},
             HashMarker,
         },
         EagerHash,
@@ -46,7 +48,7 @@ fn cbc_decrypt<'a, C: BlockCipherDecrypt + KeyInit>(
     cbc::Decryptor::<C>::new_from_slices(key.as_slice(), iv)
         .map_err(|_| es.to_alg_params_invalid())?
         .decrypt_padded::<Pkcs7>(buffer)
-        .map_err(|_| Error::EncryptFailed)
+        .map_err(|_| Error::WrongPassword)
 }
 
 fn gcm_encrypt<C, NonceSize, TagSize>(
@@ -71,7 +73,7 @@ where
     let tag = gcm
         .encrypt_in_place_detached(&nonce, &[], &mut buffer[..pos])
         .map_err(|_| Error::EncryptFailed)?;
-    buffer[pos..].copy_from_slice(tag.as_ref());
+    buffer[pos..pos + TagSize::USIZE].copy_from_slice(tag.as_ref());
     Ok(&buffer[0..pos + TagSize::USIZE])
 }
 
@@ -102,12 +104,53 @@ where
         .decrypt_in_place_detached(&nonce, &[], &mut buffer[..msg_len], &tag)
         .is_err()
     {
-        return Err(Error::DecryptFailed);
+        return Err(Error::WrongPassword);
     }
 
     Ok(&buffer[..msg_len])
 }
 
+/// Encrypt with AES-GCM, using the tag length of the given [`EncryptionScheme`].
+fn gcm_encrypt_dyn<C>(
+    es: EncryptionScheme,
+    key: EncryptionKey,
+    nonce: Nonce<U12>,
+    buffer: &mut [u8],
+    pos: usize,
+) -> Result<&[u8]>
+where
+    C: BlockSizeUser<BlockSize = U16> + GcmKeyInit + BlockCipherEncrypt,
+{
+    match es.tag_len() {
+        Some(12) => gcm_encrypt::<C, U12, U12>(es, key, nonce, buffer, pos),
+        Some(13) => gcm_encrypt::<C, U12, U13>(es, key, nonce, buffer, pos),
+        Some(14) => gcm_encrypt::<C, U12, U14>(es, key, nonce, buffer, pos),
+        Some(15) => gcm_encrypt::<C, U12, U15>(es, key, nonce, buffer, pos),
+        Some(16) => gcm_encrypt::<C, U12, U16>(es, key, nonce, buffer, pos),
+        _ => Err(es.to_alg_params_invalid()),
+    }
+}
+
+/// Decrypt with AES-GCM, using the tag length of the given [`EncryptionScheme`].
+fn gcm_decrypt_dyn<C>(
+    es: EncryptionScheme,
+    key: EncryptionKey,
+    nonce: Nonce<U12>,
+    buffer: &mut [u8],
+) -> Result<&[u8]>
+where
+    C: BlockSizeUser<BlockSize = U16> + GcmKeyInit + BlockCipherEncrypt,
+{
+    match es.tag_len() {
+        Some(12) => gcm_decrypt::<C, U12, U12>(es, key, nonce, buffer),
+        Some(13) => gcm_decrypt::<C, U12, U13>(es, key, nonce, buffer),
+        Some(14) => gcm_decrypt::<C, U12, U14>(es, key, nonce, buffer),
+        Some(15) => gcm_decrypt::<C, U12, U15>(es, key, nonce, buffer),
+        Some(16) => gcm_decrypt::<C, U12, U16>(es, key, nonce, buffer),
+        _ => Err(es.to_alg_params_invalid()),
+    }
+}
+
 pub fn encrypt_in_place<'b>(
     params: &Parameters,
     password: impl AsRef<[u8]>,
@@ -125,12 +168,18 @@ pub fn encrypt_in_place<'b>(
         EncryptionScheme::Aes128Cbc { iv } => cbc_encrypt::<aes::Aes128Enc>(es, key, &iv, buf, pos),
         EncryptionScheme::Aes192Cbc { iv } => cbc_encrypt::<aes::Aes192Enc>(es, key, &iv, buf, pos),
         EncryptionScheme::Aes256Cbc { iv } => cbc_encrypt::<aes::Aes256Enc>(es, key, &iv, buf, pos),
-        EncryptionScheme::Aes128Gcm { nonce } => {
-            gcm_encrypt::<aes::Aes128Enc, U12, U16>(es, key, Nonce::from(nonce), buf, pos)
-        }
-        EncryptionScheme::Aes256Gcm { nonce } => {
-            gcm_encrypt::<aes::Aes256Enc, U12, U16>(es, key, Nonce::from(nonce), buf, pos)
-        }
+        EncryptionScheme::Aes128Gcm { nonce }
+        | EncryptionScheme::Aes128GcmParams {
+            params: GcmParameters { nonce, .. },
+        } => gcm_encrypt_dyn::<aes::Aes128Enc>(es, key, Nonce::from(nonce), buf, pos),
+        EncryptionScheme::Aes192Gcm { nonce }
+        | EncryptionScheme::Aes192GcmParams {
+            params: GcmParameters { nonce, .. },
+        } => gcm_encrypt_dyn::<aes::Aes192Enc>(es, key, Nonce::from(nonce), buf, pos),
+        EncryptionScheme::Aes256Gcm { nonce }
+        | EncryptionScheme::Aes256GcmParams {
+            params: GcmParameters { nonce, .. },
+        } => gcm_encrypt_dyn::<aes::Aes256Enc>(es, key, Nonce::from(nonce), buf, pos),
         #[cfg(feature = "3des")]
         EncryptionScheme::DesEde3Cbc { iv } => cbc_encrypt::<des::TdesEde3>(es, key, &iv, buf, pos),
         #[cfg(feature = "des-insecure")]
@@ -153,12 +202,18 @@ pub fn decrypt_in_place<'a>(
         EncryptionScheme::Aes128Cbc { iv } => cbc_decrypt::<aes::Aes128Dec>(es, key, &iv, buf),
         EncryptionScheme::Aes192Cbc { iv } => cbc_decrypt::<aes::Aes192Dec>(es, key, &iv, buf),
         EncryptionScheme::Aes256Cbc { iv } => cbc_decrypt::<aes::Aes256Dec>(es, key, &iv, buf),
-        EncryptionScheme::Aes128Gcm { nonce } => {
-            gcm_decrypt::<aes::Aes128Enc, U12, U16>(es, key, Nonce::from(nonce), buf)
-        }
-        EncryptionScheme::Aes256Gcm { nonce } => {
-            gcm_decrypt::<aes::Aes256Enc, U12, U16>(es, key, Nonce::from(nonce), buf)
-        }
+        EncryptionScheme::Aes128Gcm { nonce }
+        | EncryptionScheme::Aes128GcmParams {
+            params: GcmParameters { nonce, .. },
+        } => gcm_decrypt_dyn::<aes::Aes128Enc>(es, key, Nonce::from(nonce), buf),
+        EncryptionScheme::Aes192Gcm { nonce }
+        | EncryptionScheme::Aes192GcmParams {
+            params: GcmParameters { nonce, .. },
+        } => gcm_decrypt_dyn::<aes::Aes192Enc>(es, key, Nonce::from(nonce), buf),
+        EncryptionScheme::Aes256Gcm { nonce }
+        | EncryptionScheme::Aes256GcmParams {
+            params: GcmParameters { nonce, .. },
+        } => gcm_decrypt_dyn::<aes::Aes256Enc>(es, key, Nonce::from(nonce), buf),
         #[cfg(feature = "3des")]
         EncryptionScheme::DesEde3Cbc { iv } => cbc_decrypt::<des::TdesEde3>(es, key, &iv, buf),
         #[cfg(feature = "des-insecure")]
@@ -11,9 +11,6 @@ use der::{
     Writer,
 };
 
-#[cfg(feature = "pbes2")]
-use core::mem::size_of;
-
 /// Password-Based Key Derivation Function (PBKDF2) OID.
 pub const PBKDF2_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.5.12");
 
@@ -211,8 +208,9 @@ impl Pbkdf2Params {
 
     const INVALID_ERR: Error = Error::AlgorithmParametersInvalid { oid: PBKDF2_OID };
 
-    /// Initialize PBKDF2-SHA256 with the given iteration count and salt
-    pub fn hmac_with_sha256(iteration_count: u32, salt: &[u8]) -> Result<Self> {
+    /// Initialize PBKDF2 with the given pseudo-random function, iteration
+    /// count and salt
+    pub fn new(prf: Pbkdf2Prf, iteration_count: u32, salt: &[u8]) -> Result<Self> {
         if iteration_count > Self::MAX_ITERATION_COUNT {
             return Err(Self::INVALID_ERR);
         }
@@ -221,9 +219,14 @@ impl Pbkdf2Params {
             salt: salt.try_into().map_err(|_| Self::INVALID_ERR)?,
             iteration_count,
             key_length: None,
-            prf: Pbkdf2Prf::HmacWithSha256,
+            prf,
         })
     }
+
+    /// Initialize PBKDF2-SHA256 with the given iteration count and salt
+    pub fn hmac_with_sha256(iteration_count: u32, salt: &[u8]) -> Result<Self> {
+        Self::new(Pbkdf2Prf::HmacWithSha256, iteration_count, salt)
+    }
 }
 
 impl<'a> DecodeValue<'a> for Pbkdf2Params {
@@ -403,7 +406,14 @@ pub struct ScryptParams {
 }
 
 impl ScryptParams {
-    #[cfg(feature = "pbes2")]
+    /// Implementation defined maximum amount of memory used to derive a key, 2 GiB.
+    ///
+    /// scrypt uses `128 * r * (N + p)` bytes of memory, parameters exceeding this bound are
+    /// rejected so that untrusted inputs can't trigger arbitrarily large allocations.
+    ///
+    /// See [RFC 7914, §2](https://datatracker.ietf.org/doc/html/rfc7914#section-2).
+    pub const MAX_MEMORY: u64 = 2 << 30;
+
     const INVALID_ERR: Error = Error::AlgorithmParametersInvalid { oid: SCRYPT_OID };
 
     /// Get the [`ScryptParams`] for the provided upstream [`scrypt::Params`]
@@ -419,6 +429,129 @@ impl ScryptParams {
             key_length: None,
         })
     }
+
+    /// Check the parameters against the constraints of [RFC 7914 Section 2],
+    /// and that scrypt needs at most `max_memory` bytes to derive a key.
+    ///
+    /// - `N` must be a power of 2 larger than 1, and less than `2^(128 * r / 8)`
+    /// - `r` and `p` must not be zero
+    ///
+    /// Returns [`Error::MemoryLimitExceeded`] if the `128 * r * N` bytes of
+    /// the ROMix working memory plus the `128 * r * p` bytes of the
+    /// PBKDF2-derived blocks used by scrypt exceed `max_memory`, and
+    /// [`Error::AlgorithmParametersInvalid`] for the other constraints.
+    ///
+    /// [RFC 7914 Section 2]: https://datatracker.ietf.org/doc/html/rfc7914#section-2
+    pub fn validate(&self, max_memory: u64) -> Result<()> {
+        let n = self.cost_parameter;
+        let r = u64::from(self.block_size);
+        let p = u64::from(self.parallelization);
+
+        if n < 2 || !n.is_power_of_two() || r == 0 || p == 0 {
+            return Err(Self::INVALID_ERR);
+        }
+
+        // `N < 2^(128 * r / 8)`, which always holds for `r >= 4`
+        if r < 4 && n >= 1 << (16 * r) {
+            return Err(Self::INVALID_ERR);
+        }
+
+        let memory = 128u64
+            .checked_mul(r)
+            .and_then(|len| len.checked_mul(n.checked_add(p)?));
+
+        if memory.map_or(true, |len| len > max_memory) {
+            return Err(Error::MemoryLimitExceeded { limit: max_memory });
+        }
+
+        Ok(())
+    }
+}
+
+/// Builder for [`ScryptParams`], checking the parameters with
+/// [`ScryptParams::validate`] against a configurable memory limit.
+///
+/// ```
+/// use pkcs5::pbes2::ScryptParamsBuilder;
+///
+/// let params = ScryptParamsBuilder::openssl()
+///     .build(b"saltsalt")
+///     .unwrap();
+/// assert_eq!(params.cost_parameter, 16384);
+///
+/// // N = 2^20 and r = 8 need over 1 GiB of memory
+/// assert!(ScryptParamsBuilder::high()
+///     .with_max_memory(16 << 20)
+///     .build(b"saltsalt")
+///     .is_err());
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ScryptParamsBuilder {
+    cost_parameter: ScryptCost,
+    block_size: u16,
+    parallelization: u16,
+    key_length: Option<u16>,
+    max_memory: u64,
+}
+
+impl ScryptParamsBuilder {
+    /// Use the CPU/memory cost parameter `N`, block size `r` and
+    /// parallelization parameter `p`.
+    ///
+    /// Defaults to a memory limit of [`ScryptParams::MAX_MEMORY`] and no
+    /// explicit key length.
+    pub const fn new(cost_parameter: ScryptCost, block_size: u16, parallelization: u16) -> Self {
+        Self {
+            cost_parameter,
+            block_size,
+            parallelization,
+            key_length: None,
+            max_memory: ScryptParams::MAX_MEMORY,
+        }
+    }
+
+    /// Parameters used by OpenSSL by default: `N = 16384`, `r = 8` and
+    /// `p = 1`, which need just over 16 MiB of memory.
+    pub const fn openssl() -> Self {
+        Self::new(1 << 14, 8, 1)
+    }
+
+    /// Stronger parameters for keys which are rarely decrypted:
+    /// `N = 2^20`, `r = 8` and `p = 1`, which need just over 1 GiB of memory.
+    pub const fn high() -> Self {
+        Self::new(1 << 20, 8, 1)
+    }
+
+    /// Set the `keyLength` field of the parameters.
+    pub const fn with_key_length(mut self, key_length: u16) -> Self {
+        self.key_length = Some(key_length);
+        self
+    }
+
+    /// Set the maximum number of bytes scrypt may use with these parameters.
+    pub const fn with_max_memory(mut self, max_memory: u64) -> Self {
+        self.max_memory = max_memory;
+        self
+    }
+
+    /// Build the [`ScryptParams`] with the given salt.
+    pub fn build(&self, salt: &[u8]) -> Result<ScryptParams> {
+        let params = ScryptParams {
+            salt: salt.try_into().map_err(|_| ScryptParams::INVALID_ERR)?,
+            cost_parameter: self.cost_parameter,
+            block_size: self.block_size,
+            parallelization: self.parallelization,
+            key_length: self.key_length,
+        };
+        params.validate(self.max_memory)?;
+        Ok(params)
+    }
+}
+
+impl Default for ScryptParamsBuilder {
+    fn default() -> Self {
+        Self::openssl()
+    }
 }
 
 impl<'a> DecodeValue<'a> for ScryptParams {
@@ -480,14 +613,8 @@ impl TryFrom<&ScryptParams> for scrypt::Params {
     type Error = Error;
 
     fn try_from(params: &ScryptParams) -> Result<scrypt::Params> {
-        let n = params.cost_parameter;
-
-        // Compute log2 and verify its correctness
-        let log_n = ((8 * size_of::<ScryptCost>() as u32) - n.leading_zeros() - 1) as u8;
-
-        if 1 << log_n != n {
-            return Err(ScryptParams::INVALID_ERR);
-        }
+        params.validate(ScryptParams::MAX_MEMORY)?;
+        let log_n = params.cost_parameter.trailing_zeros() as u8;
 
         scrypt::Params::new(
             log_n,
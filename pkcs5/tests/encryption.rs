@@ -91,6 +91,41 @@ const ED25519_PKCS8_KEY_CIPHERTEXT_DESCBC: &[u8] = &hex!(
     93E4E3893840181FBC63D75297B416A0B96CB7F9AB45CEABA"
 );
 
+/// PBES1 + `pbeWithSHA1AndDES-CBC` `AlgorithmIdentifier` example.
+///
+/// Generated by OpenSSL and extracted from the `pkcs8` crate's
+/// `ed25519-encpriv-pbes1-sha1-des.der` test vector.
+#[cfg(feature = "pbes1-insecure")]
+const PBES1_SHA1_DESCBC_ALG_ID: &[u8] =
+    &hex!("301b06092a864886f70d01050a300e04088c725113939210de02020800");
+
+/// Ciphertext of Ed25519 PKCS8 private key encrypted with
+/// `pbeWithSHA1AndDES-CBC`.
+#[cfg(feature = "pbes1-insecure")]
+const ED25519_PKCS8_KEY_CIPHERTEXT_PBES1_SHA1_DESCBC: &[u8] = &hex!(
+    "E0500F7E5017346E0DE42334A567E97107DF7B03205EB0DB539CA89320B1468B
+     DA2FF0CAE56B41EC7B8227701DDE30461D3A29D0A2A28D02"
+);
+
+/// PBES1 + `pbeWithSHA1AndRC2-CBC` `AlgorithmIdentifier` example.
+///
+/// Generated by OpenSSL with `-v1 PBE-SHA1-RC2-64`.
+#[cfg(feature = "pbes1-insecure")]
+const PBES1_SHA1_RC2CBC_ALG_ID: &[u8] =
+    &hex!("301b06092a864886f70d01050b300e0408cd143a57eaa96a2702020800");
+
+/// Ciphertext of Ed25519 PKCS8 private key encrypted with
+/// `pbeWithSHA1AndRC2-CBC`.
+#[cfg(feature = "pbes1-insecure")]
+const ED25519_PKCS8_KEY_CIPHERTEXT_PBES1_SHA1_RC2CBC: &[u8] = &hex!(
+    "5FD51D820FCC8B0D3E70B0A25257B1C56BDF1A94AB05D3CE59BB5941131D1C05
+     5D1A599E4A8AC9D86F5FC5B6F330A75B620F1B724BE675E0"
+);
+
+/// PBES1 + `pbeWithMD5AndDES-CBC` `AlgorithmIdentifier` example.
+const PBES1_MD5_DESCBC_ALG_ID: &[u8] =
+    &hex!("301b06092a864886f70d010503300e04082fc42a0d17df34a602020800");
+
 /// Password used to encrypt the keys.
 const PASSWORD: &[u8] = b"hunter42"; // Bad password; don't actually use outside tests!
 
@@ -110,6 +145,16 @@ fn decrypt_pbes2_scrypt_aes256cbc() {
     assert_eq!(plaintext, ED25519_PKCS8_KEY_PLAINTEXT);
 }
 
+#[test]
+fn decrypt_pbes2_pbkdf2_sha256_aes256cbc_wrong_password() {
+    let scheme = pkcs5::EncryptionScheme::try_from(PBES2_PBKDF2_SHA256_AES256CBC_ALG_ID).unwrap();
+    let mut buffer = Vec::from(ED25519_PKCS8_KEY_CIPHERTEXT_PBKDF2_SHA256);
+    assert_eq!(
+        scheme.decrypt_in_place(b"hunter43", &mut buffer),
+        Err(pkcs5::Error::WrongPassword)
+    );
+}
+
 #[test]
 #[cfg(feature = "3des")]
 fn decrypt_pbes2_pbkdf2_sha256_desede3cbc() {
@@ -127,3 +172,202 @@ fn decrypt_pbes2_pbkdf2_sha256_descbc() {
     let plaintext = scheme.decrypt_in_place(PASSWORD, &mut buffer).unwrap();
     assert_eq!(plaintext, ED25519_PKCS8_KEY_PLAINTEXT);
 }
+
+#[test]
+#[cfg(feature = "pbes1-insecure")]
+fn decrypt_pbes1_sha1_descbc() {
+    let scheme = pkcs5::EncryptionScheme::try_from(PBES1_SHA1_DESCBC_ALG_ID).unwrap();
+    let mut buffer = Vec::from(ED25519_PKCS8_KEY_CIPHERTEXT_PBES1_SHA1_DESCBC);
+    let plaintext = scheme.decrypt_in_place(PASSWORD, &mut buffer).unwrap();
+    assert_eq!(plaintext, ED25519_PKCS8_KEY_PLAINTEXT);
+
+    let mut buffer = Vec::from(ED25519_PKCS8_KEY_CIPHERTEXT_PBES1_SHA1_DESCBC);
+    assert_eq!(
+        scheme.decrypt_in_place(b"hunter43", &mut buffer),
+        Err(pkcs5::Error::WrongPassword)
+    );
+}
+
+#[test]
+#[cfg(feature = "pbes1-insecure")]
+fn decrypt_pbes1_sha1_rc2cbc() {
+    let scheme = pkcs5::EncryptionScheme::try_from(PBES1_SHA1_RC2CBC_ALG_ID).unwrap();
+    let mut buffer = Vec::from(ED25519_PKCS8_KEY_CIPHERTEXT_PBES1_SHA1_RC2CBC);
+    let plaintext = scheme.decrypt_in_place(PASSWORD, &mut buffer).unwrap();
+    assert_eq!(plaintext, ED25519_PKCS8_KEY_PLAINTEXT);
+
+    let mut buffer = Vec::from(ED25519_PKCS8_KEY_CIPHERTEXT_PBES1_SHA1_RC2CBC);
+    assert_eq!(
+        scheme.decrypt_in_place(b"hunter43", &mut buffer),
+        Err(pkcs5::Error::WrongPassword)
+    );
+
+    // not a whole number of blocks
+    let mut buffer = Vec::from(&ED25519_PKCS8_KEY_CIPHERTEXT_PBES1_SHA1_RC2CBC[..12]);
+    assert_eq!(
+        scheme.decrypt_in_place(PASSWORD, &mut buffer),
+        Err(pkcs5::Error::WrongPassword)
+    );
+}
+
+#[test]
+fn pbes1_unsupported() {
+    let scheme = pkcs5::EncryptionScheme::try_from(PBES1_MD5_DESCBC_ALG_ID).unwrap();
+    let mut buffer = [0u8; 48];
+
+    #[cfg(feature = "pbes1-insecure")]
+    assert_eq!(
+        scheme.decrypt_in_place(PASSWORD, &mut buffer),
+        Err(pkcs5::Error::UnsupportedAlgorithm {
+            oid: pkcs5::pbes1::PBE_WITH_MD5_AND_DES_CBC_OID
+        })
+    );
+
+    #[cfg(not(feature = "pbes1-insecure"))]
+    assert_eq!(
+        scheme.decrypt_in_place(PASSWORD, &mut buffer),
+        Err(pkcs5::Error::NoPbes1CryptSupport)
+    );
+
+    assert_eq!(
+        scheme.encrypt_in_place(PASSWORD, &mut buffer, 32),
+        Err(pkcs5::Error::NoPbes1CryptSupport)
+    );
+}
+
+#[test]
+fn scrypt_params_validation() {
+    use pkcs5::pbes2::ScryptParams;
+
+    let params = |cost_parameter, block_size| ScryptParams {
+        salt: (&hex!("E6211E2348AD69E0")[..]).try_into().unwrap(),
+        cost_parameter,
+        block_size,
+        parallelization: 1,
+        key_length: None,
+    };
+
+    assert!(pkcs5::scrypt::Params::try_from(&params(16384, 8)).is_ok());
+
+    // `N` must be a power of 2 larger than 1
+    for n in [0, 1, 3, 16383] {
+        assert_eq!(
+            pkcs5::scrypt::Params::try_from(&params(n, 8)).unwrap_err(),
+            pkcs5::Error::AlgorithmParametersInvalid {
+                oid: pkcs5::pbes2::SCRYPT_OID
+            }
+        );
+    }
+
+    // `r` and `p` must not be zero, and `N < 2^(128 * r / 8)`
+    for (n, r) in [(16384, 0), (1 << 16, 1), (1 << 32, 2)] {
+        assert_eq!(
+            params(n, r).validate(ScryptParams::MAX_MEMORY),
+            Err(pkcs5::Error::AlgorithmParametersInvalid {
+                oid: pkcs5::pbes2::SCRYPT_OID
+            })
+        );
+    }
+    assert!(params(1 << 15, 1)
+        .validate(ScryptParams::MAX_MEMORY)
+        .is_ok());
+    assert!(ScryptParams {
+        parallelization: 0,
+        ..params(16384, 8)
+    }
+    .validate(ScryptParams::MAX_MEMORY)
+    .is_err());
+
+    // `128 * r * (N + p)` must not exceed the memory limit
+    let limit = 16 << 20;
+    assert_eq!(
+        params(16384, 8).validate(limit),
+        Err(pkcs5::Error::MemoryLimitExceeded { limit })
+    );
+    assert!(params(16384, 8).validate(limit + 1024).is_ok());
+    assert!(ScryptParams {
+        parallelization: 2,
+        ..params(16384, 8)
+    }
+    .validate(limit + 1024)
+    .is_err());
+
+    let memory_err = pkcs5::Error::MemoryLimitExceeded {
+        limit: ScryptParams::MAX_MEMORY,
+    };
+    assert!(pkcs5::scrypt::Params::try_from(&params(1 << 20, 8)).is_ok());
+    assert_eq!(
+        pkcs5::scrypt::Params::try_from(&params(1 << 21, 8)),
+        Err(memory_err)
+    );
+    assert_eq!(
+        pkcs5::scrypt::Params::try_from(&params(1 << 63, u16::MAX)),
+        Err(memory_err)
+    );
+}
+
+#[test]
+fn scrypt_params_builder() {
+    use pkcs5::pbes2::{ScryptParams, ScryptParamsBuilder};
+
+    let salt = hex!("E6211E2348AD69E0");
+
+    let params = ScryptParamsBuilder::default().build(&salt).unwrap();
+    assert_eq!(params.salt.as_bytes(), salt);
+    assert_eq!(params.cost_parameter, 16384);
+    assert_eq!(params.block_size, 8);
+    assert_eq!(params.parallelization, 1);
+    assert_eq!(params.key_length, None);
+
+    let params = ScryptParamsBuilder::high()
+        .with_key_length(32)
+        .build(&salt)
+        .unwrap();
+    assert_eq!(params.cost_parameter, 1 << 20);
+    assert_eq!(params.key_length, Some(32));
+
+    // 32 MiB are enough for the OpenSSL parameters, but not for the high ones
+    let limit = 32 << 20;
+    assert!(ScryptParamsBuilder::openssl()
+        .with_max_memory(limit)
+        .build(&salt)
+        .is_ok());
+    assert_eq!(
+        ScryptParamsBuilder::high()
+            .with_max_memory(limit)
+            .build(&salt),
+        Err(pkcs5::Error::MemoryLimitExceeded { limit })
+    );
+
+    assert_eq!(
+        ScryptParamsBuilder::new(3, 8, 1).build(&salt),
+        Err(pkcs5::Error::AlgorithmParametersInvalid {
+            oid: pkcs5::pbes2::SCRYPT_OID
+        })
+    );
+    assert!(ScryptParamsBuilder::new(1 << 21, 8, 1)
+        .with_max_memory(4 * ScryptParams::MAX_MEMORY)
+        .build(&salt)
+        .is_ok());
+}
+
+#[test]
+fn decrypt_pbes2_scrypt_memory_limit() {
+    let mut scheme = pkcs5::EncryptionScheme::try_from(PBES2_SCRYPT_AES256CBC_ALG_ID).unwrap();
+    if let pkcs5::EncryptionScheme::Pbes2(pkcs5::pbes2::Parameters {
+        kdf: pkcs5::pbes2::Kdf::Scrypt(params),
+        ..
+    }) = &mut scheme
+    {
+        // 128 GiB
+        params.cost_parameter = 1 << 30;
+    }
+
+    let mut buffer = Vec::from(ED25519_PKCS8_KEY_CIPHERTEXT_SCRYPT);
+    assert_eq!(
+        scheme.decrypt_in_place(PASSWORD, &mut buffer),
+        Err(pkcs5::Error::MemoryLimitExceeded {
+            limit: pkcs5::pbes2::ScryptParams::MAX_MEMORY
+        })
+    );
+}
@@ -232,3 +232,93 @@ fn encode_pbes2_scrypt_aes256cbc() {
     let encoded_der = encoder.finish().unwrap();
     assert_eq!(encoded_der, PBES2_SCRYPT_AES256CBC_ALG_ID);
 }
+
+/// Encoding test for PBES2 + PBKDF2-SHA512 + AES-256-CBC parameters built with
+/// [`pbes2::Pbkdf2Params::new`].
+///
+/// Compared against `tests/examples/pbes2_aes-256-cbc_hmacWithSHA512_algid.der`.
+#[test]
+fn encode_pbes2_pbkdf2_sha512_aes256cbc() {
+    let kdf = pbes2::Pbkdf2Params::new(
+        pbes2::Pbkdf2Prf::HmacWithSha512,
+        10,
+        &hex!("6446871BB0E01F05"),
+    )
+    .unwrap();
+
+    let params = pbes2::Parameters {
+        kdf: kdf.into(),
+        encryption: pbes2::EncryptionScheme::Aes256Cbc {
+            iv: hex!("ED47738BA72FA4F733B77A37AE3E321C"),
+        },
+    };
+
+    let mut buffer = [0u8; 1024];
+
+    let scheme = pkcs5::EncryptionScheme::from(params);
+    let mut encoder = der::SliceWriter::new(&mut buffer);
+    scheme.encode(&mut encoder).unwrap();
+
+    let encoded_der = encoder.finish().unwrap();
+    assert_eq!(
+        encoded_der,
+        include_bytes!("examples/pbes2_aes-256-cbc_hmacWithSHA512_algid.der")
+    );
+}
+
+/// Decoding and encoding test for AES-128-GCM with RFC 5084 `GCMParameters`
+#[test]
+fn decode_aes128gcm_parameters() {
+    use der::Decode;
+
+    // RFC 5084 `GCMParameters` with a 13-byte ICV
+    let alg_id = hex!("301e0609608648016503040106 3011040ca1a2a3a4a5a6a7a8a9aaabac02010d");
+    let scheme = pbes2::EncryptionScheme::from_der(&alg_id).unwrap();
+    assert_eq!(
+        scheme,
+        pbes2::EncryptionScheme::Aes128GcmParams {
+            params: pbes2::GcmParameters {
+                nonce: hex!("a1a2a3a4a5a6a7a8a9aaabac"),
+                icv_len: 13,
+            },
+        }
+    );
+    assert_eq!(scheme.tag_len(), Some(13));
+    assert_eq!(
+        scheme.gcm_parameters().map(|params| params.icv_len),
+        Some(13)
+    );
+
+    let mut buffer = [0u8; 64];
+    assert_eq!(scheme.encode_to_slice(&mut buffer).unwrap(), alg_id);
+
+    // The default ICV length must be omitted
+    let alg_id = hex!("301e0609608648016503040106 3011040ca1a2a3a4a5a6a7a8a9aaabac02010c");
+    assert!(pbes2::EncryptionScheme::from_der(&alg_id).is_err());
+
+    // ICV lengths are restricted to 12..=16 bytes
+    let alg_id = hex!("301e0609608648016503040106 3011040ca1a2a3a4a5a6a7a8a9aaabac020108");
+    assert!(pbes2::EncryptionScheme::from_der(&alg_id).is_err());
+}
+
+/// Decoding and encoding test for AES-192-GCM with only a nonce as parameters
+#[test]
+fn decode_aes192gcm_nonce_only() {
+    use der::Decode;
+
+    let alg_id = hex!("3019060960864801650304011a 040c0c0b0a090807060504030201");
+    let scheme = pbes2::EncryptionScheme::from_der(&alg_id).unwrap();
+    assert_eq!(
+        scheme,
+        pbes2::EncryptionScheme::Aes192Gcm {
+            nonce: hex!("0c0b0a090807060504030201"),
+        }
+    );
+    assert_eq!(scheme.oid(), pbes2::AES_192_GCM_OID);
+    assert_eq!(scheme.key_size(), 24);
+    assert_eq!(scheme.tag_len(), Some(16));
+    assert_eq!(scheme.gcm_parameters(), None);
+
+    let mut buffer = [0u8; 64];
+    assert_eq!(scheme.encode_to_slice(&mut buffer).unwrap(), alg_id);
+}
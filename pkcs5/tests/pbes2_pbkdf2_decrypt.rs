@@ -1,4 +1,4 @@
-//! PBES2 PBKDF2 decryption tests
+//! PBES2 PBKDF2 decryption and encryption tests
 
 #[cfg(feature = "pbes2")]
 use std::fs;
@@ -32,6 +32,15 @@ fn run_combinations(prfs: &[&str]) {
                 .unwrap_or_else(|err| panic!("pbes2 decryption of {aes_mode} {prf}: {err}"));
 
             assert_eq!(sk_bytes, plaintext);
+
+            // Encrypting with the same salt and IV gives back OpenSSL's ciphertext
+            let mut buffer = [0u8; 640];
+            buffer[..sk_bytes.len()].copy_from_slice(&sk_bytes);
+            let ciphertext = scheme
+                .encrypt_in_place(PASSWORD, &mut buffer, sk_bytes.len())
+                .unwrap_or_else(|err| panic!("pbes2 encryption of {aes_mode} {prf}: {err}"));
+
+            assert_eq!(ciphertext, fs::read(&ciphertext_path).unwrap());
         }
     }
 }
@@ -0,0 +1,334 @@
+//! PKCS#8 private key attributes.
+
+use der::{
+    asn1::{AnyRef, ObjectIdentifier, OctetStringRef},
+    Decode, DecodeValue, EncodeValue, ErrorKind, FixedTag, Header, Length, Reader, SliceReader,
+    Tag, Writer,
+};
+
+#[cfg(feature = "alloc")]
+use {
+    alloc::{boxed::Box, string::String, vec::Vec},
+    der::{asn1::BmpString, referenced::*, Encode},
+};
+
+/// `pkcs-9-at-friendlyName` attribute OID, as defined in [RFC 2985 Section 5.5.1].
+///
+/// [RFC 2985 Section 5.5.1]: https://datatracker.ietf.org/doc/html/rfc2985#section-5.5.1
+pub const FRIENDLY_NAME_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.20");
+
+/// `pkcs-9-at-localKeyId` attribute OID, as defined in [RFC 2985 Section 5.5.2].
+///
+/// [RFC 2985 Section 5.5.2]: https://datatracker.ietf.org/doc/html/rfc2985#section-5.5.2
+pub const LOCAL_KEY_ID_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.21");
+
+/// Private key attributes, borrowed from a DER document.
+///
+/// ```text
+/// Attributes ::= SET OF Attribute { { OneAsymmetricKeyAttributes } }
+///
+/// Attribute { ATTRIBUTE:IOSet } ::= SEQUENCE {
+///     type   ATTRIBUTE.&id({IOSet}),
+///     values SET SIZE(1..MAX) OF ATTRIBUTE.&Type({IOSet}{@type})
+/// }
+/// ```
+///
+/// The attributes are kept in their encoded form, so they re-encode to the
+/// exact bytes they were decoded from. Decoding checks that each element is
+/// an `Attribute` with at least one value, and that both the attributes and
+/// their values are sorted as DER requires for `SET OF`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct AttributesRef<'a> {
+    /// DER encoding of the `SET OF` contents.
+    inner: &'a [u8],
+}
+
+impl<'a> AttributesRef<'a> {
+    /// Iterate over the attributes.
+    pub fn iter(&self) -> impl Iterator<Item = AttributeRef<'a>> + 'a {
+        decode_all(self.inner)
+    }
+
+    /// Get the attribute with the given OID, if present.
+    pub fn get(&self, oid: ObjectIdentifier) -> Option<AttributeRef<'a>> {
+        self.iter().find(|attr| attr.oid == oid)
+    }
+
+    /// Get the PKCS#9 `friendlyName` attribute, if present.
+    #[cfg(feature = "alloc")]
+    pub fn friendly_name(&self) -> der::Result<Option<String>> {
+        self.get(FRIENDLY_NAME_OID)
+            .map(|attr| {
+                let name = attr.single_value()?.decode_as::<BmpString>()?;
+                Ok(name.chars().collect())
+            })
+            .transpose()
+    }
+
+    /// Get the PKCS#9 `localKeyId` attribute, if present.
+    pub fn local_key_id(&self) -> der::Result<Option<OctetStringRef<'a>>> {
+        self.get(LOCAL_KEY_ID_OID)
+            .map(|attr| attr.single_value()?.decode_as())
+            .transpose()
+    }
+
+    /// Are there no attributes?
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+}
+
+impl<'a> DecodeValue<'a> for AttributesRef<'a> {
+    type Error = der::Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> der::Result<Self> {
+        let inner = reader.read_slice(header.length)?;
+        validate_set_of::<AttributeRef<'_>>(inner)?;
+        Ok(Self { inner })
+    }
+}
+
+impl EncodeValue for AttributesRef<'_> {
+    fn value_len(&self) -> der::Result<Length> {
+        self.inner.len().try_into()
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
+        writer.write(self.inner)
+    }
+}
+
+impl FixedTag for AttributesRef<'_> {
+    const TAG: Tag = Tag::Set;
+}
+
+/// Private key attribute, borrowed from a DER document.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct AttributeRef<'a> {
+    /// Attribute type.
+    pub oid: ObjectIdentifier,
+
+    /// DER encoding of the `SET OF` values.
+    values: &'a [u8],
+}
+
+impl<'a> AttributeRef<'a> {
+    /// Iterate over the values of this attribute.
+    pub fn values(&self) -> impl Iterator<Item = AnyRef<'a>> + 'a {
+        decode_all(self.values)
+    }
+
+    /// Get the value of a single-valued attribute.
+    fn single_value(&self) -> der::Result<AnyRef<'a>> {
+        let mut values = self.values();
+
+        match (values.next(), values.next()) {
+            (Some(value), None) => Ok(value),
+            _ => Err(Tag::Set.value_error()),
+        }
+    }
+}
+
+impl<'a> DecodeValue<'a> for AttributeRef<'a> {
+    type Error = der::Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> der::Result<Self> {
+        reader.read_nested(header.length, |reader| {
+            let oid = reader.decode()?;
+            let values_header = Header::decode(reader)?;
+            values_header.tag.assert_eq(Tag::Set)?;
+
+            let values = reader.read_slice(values_header.length)?;
+            validate_set_of::<AnyRef<'_>>(values)?;
+
+            if values.is_empty() {
+                return Err(Tag::Set.length_error());
+            }
+
+            Ok(Self { oid, values })
+        })
+    }
+}
+
+impl FixedTag for AttributeRef<'_> {
+    const TAG: Tag = Tag::Sequence;
+}
+
+/// Private key attributes.
+///
+/// Owned equivalent of [`AttributesRef`], which can also be modified. The
+/// attributes are kept in DER order.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Attributes {
+    /// DER encoding of the `SET OF` contents.
+    inner: Box<[u8]>,
+}
+
+#[cfg(feature = "alloc")]
+impl Attributes {
+    /// Create an empty set of attributes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Iterate over the attributes.
+    pub fn iter(&self) -> impl Iterator<Item = AttributeRef<'_>> {
+        self.owned_to_ref().iter()
+    }
+
+    /// Get the attribute with the given OID, if present.
+    pub fn get(&self, oid: ObjectIdentifier) -> Option<AttributeRef<'_>> {
+        self.owned_to_ref().get(oid)
+    }
+
+    /// Get the PKCS#9 `friendlyName` attribute, if present.
+    pub fn friendly_name(&self) -> der::Result<Option<String>> {
+        self.owned_to_ref().friendly_name()
+    }
+
+    /// Get the PKCS#9 `localKeyId` attribute, if present.
+    pub fn local_key_id(&self) -> der::Result<Option<OctetStringRef<'_>>> {
+        self.owned_to_ref().local_key_id()
+    }
+
+    /// Are there no attributes?
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Set the attribute with the given OID to a single value, replacing any
+    /// previous values.
+    pub fn set(&mut self, oid: ObjectIdentifier, value: &impl Encode) -> der::Result<()> {
+        let value = value.to_der()?;
+        let values_len = Length::try_from(value.len())?;
+        let attr_len = (oid.encoded_len()? + values_len.for_tlv()?)?;
+
+        let mut attr = Header::new(Tag::Sequence, attr_len)?.to_der()?;
+        attr.extend(oid.to_der()?);
+        attr.extend(Header::new(Tag::Set, values_len)?.to_der()?);
+        attr.extend(value);
+
+        self.update(oid, Some(&attr))
+    }
+
+    /// Remove the attribute with the given OID, returning whether it was present.
+    pub fn remove(&mut self, oid: ObjectIdentifier) -> der::Result<bool> {
+        let present = self.get(oid).is_some();
+        self.update(oid, None)?;
+        Ok(present)
+    }
+
+    /// Set the PKCS#9 `friendlyName` attribute, encoded as a `BMPString`.
+    pub fn set_friendly_name(&mut self, name: &str) -> der::Result<()> {
+        self.set(FRIENDLY_NAME_OID, &BmpString::from_utf8(name)?)
+    }
+
+    /// Set the PKCS#9 `localKeyId` attribute.
+    pub fn set_local_key_id(&mut self, key_id: &[u8]) -> der::Result<()> {
+        self.set(LOCAL_KEY_ID_OID, &OctetStringRef::new(key_id)?)
+    }
+
+    /// Replace the attribute with the given OID by the provided DER encoding,
+    /// keeping the `SET OF` sorted.
+    fn update(&mut self, oid: ObjectIdentifier, attr: Option<&[u8]>) -> der::Result<()> {
+        let mut reader = SliceReader::new(&self.inner)?;
+        let mut attrs = Vec::new();
+
+        while !reader.is_finished() {
+            let tlv = reader.tlv_bytes()?;
+
+            if AttributeRef::from_der(tlv)?.oid != oid {
+                attrs.push(tlv);
+            }
+        }
+
+        attrs.extend(attr);
+        attrs.sort_unstable();
+        self.inner = attrs.concat().into_boxed_slice();
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> DecodeValue<'a> for Attributes {
+    type Error = der::Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> der::Result<Self> {
+        AttributesRef::decode_value(reader, header).map(|attrs| attrs.ref_to_owned())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl EncodeValue for Attributes {
+    fn value_len(&self) -> der::Result<Length> {
+        self.owned_to_ref().value_len()
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
+        self.owned_to_ref().encode_value(writer)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FixedTag for Attributes {
+    const TAG: Tag = Tag::Set;
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> RefToOwned<'a> for AttributesRef<'a> {
+    type Owned = Attributes;
+    fn ref_to_owned(&self) -> Self::Owned {
+        Attributes {
+            inner: self.inner.into(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl OwnedToRef for Attributes {
+    type Borrowed<'a> = AttributesRef<'a>;
+    fn owned_to_ref(&self) -> Self::Borrowed<'_> {
+        AttributesRef { inner: &self.inner }
+    }
+}
+
+/// Check that `bytes` are the DER-encoded contents of a `SET OF T`, i.e. a
+/// concatenation of valid DER encodings of `T` in ascending order.
+fn validate_set_of<'a, T: Decode<'a, Error = der::Error>>(bytes: &'a [u8]) -> der::Result<()> {
+    let mut reader = SliceReader::new(bytes)?;
+    let mut prev = None;
+
+    while !reader.is_finished() {
+        let tlv = reader.tlv_bytes()?;
+        T::from_der(tlv)?;
+
+        if prev.is_some_and(|prev| prev > tlv) {
+            return Err(ErrorKind::SetOrdering.into());
+        }
+
+        prev = Some(tlv);
+    }
+
+    Ok(())
+}
+
+/// Iterate over a concatenation of DER encodings of `T`, previously checked
+/// with [`validate_set_of`].
+fn decode_all<'a, T: Decode<'a, Error = der::Error>>(
+    bytes: &'a [u8],
+) -> impl Iterator<Item = T> + 'a {
+    let mut reader = SliceReader::new(bytes).ok();
+
+    core::iter::from_fn(move || {
+        let reader = reader.as_mut()?;
+
+        if reader.is_finished() {
+            None
+        } else {
+            T::decode(reader).ok()
+        }
+    })
+}
@@ -12,7 +12,7 @@ use pkcs5::EncryptionScheme;
 use der::{asn1::OctetString, SecretDocument};
 
 #[cfg(feature = "encryption")]
-use {pkcs5::pbes2, rand_core::CryptoRngCore};
+use {crate::EncryptionParameters, pkcs5::pbes2, rand_core::CryptoRngCore};
 
 #[cfg(feature = "pem")]
 use der::pem::PemLabel;
@@ -61,6 +61,21 @@ where
             .try_into()?)
     }
 
+    /// Get the KDF, salt length and cipher this private key was encrypted with.
+    ///
+    /// Returns an error for PBES1 and for algorithms [`EncryptionParameters`]
+    /// can't describe.
+    #[cfg(feature = "encryption")]
+    pub fn encryption_parameters(&self) -> Result<EncryptionParameters> {
+        match self.encryption_algorithm.pbes2() {
+            Some(params) => params.try_into(),
+            None => Err(pkcs5::Error::UnsupportedAlgorithm {
+                oid: self.encryption_algorithm.oid(),
+            }
+            .into()),
+        }
+    }
+
     /// Encrypt the given ASN.1 DER document using a symmetric encryption key
     /// derived from the provided password.
     #[cfg(feature = "encryption")]
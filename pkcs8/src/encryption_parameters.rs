@@ -0,0 +1,328 @@
+//! Builder for PKCS#5 encryption parameters.
+
+use crate::{Error, Result};
+use pkcs5::pbes2::{
+    self, Kdf, Pbkdf2Params, Pbkdf2Prf, Salt, ScryptParams, PBKDF2_OID, SCRYPT_OID,
+};
+use rand_core::CryptoRngCore;
+
+/// Password-based key derivation function and its cost parameters.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum KdfParameters {
+    /// PBKDF2 with the given pseudo-random function.
+    Pbkdf2 {
+        /// Pseudo-random function.
+        prf: Pbkdf2Prf,
+
+        /// Iteration count.
+        iterations: u32,
+    },
+
+    /// scrypt.
+    Scrypt {
+        /// log₂ of the CPU/memory cost parameter `N`.
+        log_n: u8,
+
+        /// Block size parameter `r`.
+        r: u32,
+
+        /// Parallelization parameter `p`.
+        p: u32,
+    },
+}
+
+/// Symmetric cipher used to encrypt the private key.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Cipher {
+    /// AES-128 in CBC mode.
+    Aes128Cbc,
+
+    /// AES-192 in CBC mode.
+    Aes192Cbc,
+
+    /// AES-256 in CBC mode.
+    Aes256Cbc,
+
+    /// AES-128 in GCM mode, with a 128-bit authentication tag.
+    Aes128Gcm,
+
+    /// AES-192 in GCM mode, with a 128-bit authentication tag.
+    Aes192Gcm,
+
+    /// AES-256 in GCM mode, with a 128-bit authentication tag.
+    Aes256Gcm,
+}
+
+/// Builder for the parameters used to encrypt a private key.
+///
+/// Describes the password-based key derivation function, salt length and
+/// cipher, leaving the salt and IV to be randomly generated for each key
+/// with [`EncryptionParameters::generate`].
+///
+/// Obviously insecure choices (PBKDF2 with less than
+/// [`EncryptionParameters::MIN_PBKDF2_ITERATIONS`] iterations, or a salt
+/// shorter than [`EncryptionParameters::MIN_SALT_LEN`] bytes) are rejected
+/// unless [`EncryptionParameters::allow_insecure`] is set.
+///
+/// ```
+/// use pkcs8::{Cipher, EncryptionParameters, pkcs5::pbes2::Pbkdf2Prf};
+///
+/// let params = EncryptionParameters::pbkdf2(Pbkdf2Prf::HmacWithSha256, 600_000)
+///     .with_salt_len(16)
+///     .with_cipher(Cipher::Aes256Cbc);
+///
+/// assert!(params.validate().is_ok());
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct EncryptionParameters {
+    kdf: KdfParameters,
+    salt_len: usize,
+    cipher: Cipher,
+    allow_insecure: bool,
+}
+
+impl EncryptionParameters {
+    /// Minimum PBKDF2 iteration count accepted without
+    /// [`EncryptionParameters::allow_insecure`].
+    pub const MIN_PBKDF2_ITERATIONS: u32 = 1000;
+
+    /// Minimum salt length accepted without
+    /// [`EncryptionParameters::allow_insecure`].
+    pub const MIN_SALT_LEN: usize = 8;
+
+    /// Default salt length.
+    const DEFAULT_SALT_LEN: usize = 16;
+
+    /// Default cipher.
+    const DEFAULT_CIPHER: Cipher = Cipher::Aes256Cbc;
+
+    /// Use PBKDF2 with the given pseudo-random function and iteration count.
+    ///
+    /// Defaults to a 16-byte salt and AES-256-CBC.
+    pub fn pbkdf2(prf: Pbkdf2Prf, iterations: u32) -> Self {
+        Self::new(KdfParameters::Pbkdf2 { prf, iterations })
+    }
+
+    /// Use scrypt with the given `log₂(N)`, `r` and `p` parameters.
+    ///
+    /// Defaults to a 16-byte salt and AES-256-CBC.
+    pub fn scrypt(log_n: u8, r: u32, p: u32) -> Self {
+        Self::new(KdfParameters::Scrypt { log_n, r, p })
+    }
+
+    fn new(kdf: KdfParameters) -> Self {
+        Self {
+            kdf,
+            salt_len: Self::DEFAULT_SALT_LEN,
+            cipher: Self::DEFAULT_CIPHER,
+            allow_insecure: false,
+        }
+    }
+
+    /// Set the length of the randomly generated salt, in bytes.
+    pub fn with_salt_len(mut self, salt_len: usize) -> Self {
+        self.salt_len = salt_len;
+        self
+    }
+
+    /// Set the cipher.
+    pub fn with_cipher(mut self, cipher: Cipher) -> Self {
+        self.cipher = cipher;
+        self
+    }
+
+    /// Accept parameters below the minimum iteration count and salt length.
+    ///
+    /// Only intended for interoperability with legacy software.
+    pub fn allow_insecure(mut self, allow_insecure: bool) -> Self {
+        self.allow_insecure = allow_insecure;
+        self
+    }
+
+    /// Get the key derivation function and its cost parameters.
+    pub fn kdf(&self) -> KdfParameters {
+        self.kdf
+    }
+
+    /// Get the salt length, in bytes.
+    pub fn salt_len(&self) -> usize {
+        self.salt_len
+    }
+
+    /// Get the cipher.
+    pub fn cipher(&self) -> Cipher {
+        self.cipher
+    }
+
+    /// Check these parameters.
+    ///
+    /// Returns an error if they can't be encoded, or if they are insecure and
+    /// [`EncryptionParameters::allow_insecure`] isn't set. scrypt parameters
+    /// are checked with [`ScryptParams::validate`] against
+    /// [`ScryptParams::MAX_MEMORY`].
+    pub fn validate(&self) -> Result<()> {
+        let oid = match self.kdf {
+            KdfParameters::Pbkdf2 { .. } => PBKDF2_OID,
+            KdfParameters::Scrypt { .. } => SCRYPT_OID,
+        };
+        let err = Error::EncryptedPrivateKey(pkcs5::Error::AlgorithmParametersInvalid { oid });
+
+        if self.salt_len > Salt::MAX_LEN {
+            return Err(err);
+        }
+
+        if let KdfParameters::Pbkdf2 { iterations, .. } = self.kdf {
+            if iterations == 0 || iterations > Pbkdf2Params::MAX_ITERATION_COUNT {
+                return Err(err);
+            }
+
+            if iterations < Self::MIN_PBKDF2_ITERATIONS && !self.allow_insecure {
+                return Err(err);
+            }
+        }
+
+        self.build_kdf(Salt::new(&[0u8; Salt::MAX_LEN][..self.salt_len])?)?;
+
+        if self.salt_len < Self::MIN_SALT_LEN && !self.allow_insecure {
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    /// Generate [`pbes2::Parameters`] with a random salt and IV.
+    pub fn generate(&self, rng: &mut impl CryptoRngCore) -> Result<pbes2::Parameters> {
+        self.validate()?;
+
+        let mut salt = [0u8; Salt::MAX_LEN];
+        let salt = &mut salt[..self.salt_len];
+        rng.fill_bytes(salt);
+        let kdf = self.build_kdf(Salt::new(salt)?)?;
+
+        let encryption = match self.cipher {
+            Cipher::Aes128Cbc => pbes2::EncryptionScheme::Aes128Cbc { iv: random(rng) },
+            Cipher::Aes192Cbc => pbes2::EncryptionScheme::Aes192Cbc { iv: random(rng) },
+            Cipher::Aes256Cbc => pbes2::EncryptionScheme::Aes256Cbc { iv: random(rng) },
+            Cipher::Aes128Gcm => pbes2::EncryptionScheme::Aes128GcmParams {
+                params: pbes2::GcmParameters {
+                    nonce: random(rng),
+                    icv_len: 16,
+                },
+            },
+            Cipher::Aes192Gcm => pbes2::EncryptionScheme::Aes192GcmParams {
+                params: pbes2::GcmParameters {
+                    nonce: random(rng),
+                    icv_len: 16,
+                },
+            },
+            Cipher::Aes256Gcm => pbes2::EncryptionScheme::Aes256GcmParams {
+                params: pbes2::GcmParameters {
+                    nonce: random(rng),
+                    icv_len: 16,
+                },
+            },
+        };
+
+        Ok(pbes2::Parameters { kdf, encryption })
+    }
+
+    /// Build the key derivation function parameters with the given salt.
+    fn build_kdf(&self, salt: Salt) -> Result<Kdf> {
+        match self.kdf {
+            KdfParameters::Pbkdf2 { prf, iterations } => Ok(Kdf::Pbkdf2(Pbkdf2Params {
+                salt,
+                iteration_count: iterations,
+                key_length: None,
+                prf,
+            })),
+            KdfParameters::Scrypt { log_n, r, p } => {
+                let err = pkcs5::Error::AlgorithmParametersInvalid { oid: SCRYPT_OID };
+
+                let params = ScryptParams {
+                    salt,
+                    cost_parameter: 1u64.checked_shl(log_n.into()).ok_or(err)?,
+                    block_size: r.try_into().map_err(|_| err)?,
+                    parallelization: p.try_into().map_err(|_| err)?,
+                    key_length: None,
+                };
+                params.validate(ScryptParams::MAX_MEMORY)?;
+
+                Ok(Kdf::Scrypt(params))
+            }
+        }
+    }
+}
+
+impl Default for EncryptionParameters {
+    /// scrypt with `log₂(N)` = 14, `r` = 8 and `p` = 1, a 16-byte salt and
+    /// AES-256-CBC, as used by [`PrivateKeyInfo::encrypt`][`crate::PrivateKeyInfo::encrypt`].
+    fn default() -> Self {
+        Self::scrypt(14, 8, 1)
+    }
+}
+
+impl TryFrom<&pbes2::Parameters> for EncryptionParameters {
+    type Error = Error;
+
+    /// Read the parameters back from existing [`pbes2::Parameters`].
+    ///
+    /// The parameters are not validated, use [`EncryptionParameters::validate`]
+    /// to check them against the minimums.
+    fn try_from(params: &pbes2::Parameters) -> Result<Self> {
+        let (kdf, salt) = match &params.kdf {
+            Kdf::Pbkdf2(pbkdf2) => (
+                KdfParameters::Pbkdf2 {
+                    prf: pbkdf2.prf,
+                    iterations: pbkdf2.iteration_count,
+                },
+                &pbkdf2.salt,
+            ),
+            Kdf::Scrypt(scrypt) => {
+                let n = scrypt.cost_parameter;
+
+                if !n.is_power_of_two() {
+                    return Err(pkcs5::Error::AlgorithmParametersInvalid { oid: SCRYPT_OID }.into());
+                }
+
+                (
+                    KdfParameters::Scrypt {
+                        log_n: n.trailing_zeros() as u8,
+                        r: scrypt.block_size.into(),
+                        p: scrypt.parallelization.into(),
+                    },
+                    &scrypt.salt,
+                )
+            }
+            kdf => return Err(pkcs5::Error::UnsupportedAlgorithm { oid: kdf.oid() }.into()),
+        };
+
+        let cipher = match params.encryption {
+            pbes2::EncryptionScheme::Aes128Cbc { .. } => Cipher::Aes128Cbc,
+            pbes2::EncryptionScheme::Aes192Cbc { .. } => Cipher::Aes192Cbc,
+            pbes2::EncryptionScheme::Aes256Cbc { .. } => Cipher::Aes256Cbc,
+            pbes2::EncryptionScheme::Aes128Gcm { .. }
+            | pbes2::EncryptionScheme::Aes128GcmParams { .. } => Cipher::Aes128Gcm,
+            pbes2::EncryptionScheme::Aes192Gcm { .. }
+            | pbes2::EncryptionScheme::Aes192GcmParams { .. } => Cipher::Aes192Gcm,
+            pbes2::EncryptionScheme::Aes256Gcm { .. }
+            | pbes2::EncryptionScheme::Aes256GcmParams { .. } => Cipher::Aes256Gcm,
+            scheme => return Err(pkcs5::Error::UnsupportedAlgorithm { oid: scheme.oid() }.into()),
+        };
+
+        Ok(Self {
+            kdf,
+            salt_len: salt.as_ref().len(),
+            cipher,
+            allow_insecure: false,
+        })
+    }
+}
+
+/// Generate a random IV or nonce.
+fn random<const N: usize>(rng: &mut impl CryptoRngCore) -> [u8; N] {
+    let mut bytes = [0u8; N];
+    rng.fill_bytes(&mut bytes);
+    bytes
+}
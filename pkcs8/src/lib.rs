@@ -64,17 +64,25 @@
 //!
 //! New keys should use AES instead.
 //!
+//! ## Legacy PBES1 support (optional)
+//! When the `pbes1-insecure` feature is enabled this crate can also decrypt
+//! private keys encrypted with PKCS#5v1.5 `pbeWithSHA1AndDES-CBC` or
+//! `pbeWithSHA1AndRC2-CBC`, so they can be re-encrypted with PBES2.
+//! Encrypting with PBES1 is not supported.
+//!
 //! [RFC 5208]: https://tools.ietf.org/html/rfc5208
 //! [RFC 5958]: https://tools.ietf.org/html/rfc5958
 //! [RFC 7914]: https://datatracker.ietf.org/doc/html/rfc7914
 //! [PKCS#5v2 Password Based Encryption Scheme 2 (RFC 8018)]: https://tools.ietf.org/html/rfc8018#section-6.2
 //! [scrypt]: https://en.wikipedia.org/wiki/Scrypt
 
-#[cfg(feature = "pem")]
+#[cfg(feature = "alloc")]
 extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+pub mod attributes;
+
 mod error;
 mod private_key_info;
 mod traits;
@@ -82,8 +90,11 @@ mod version;
 
 #[cfg(feature = "pkcs5")]
 pub(crate) mod encrypted_private_key_info;
+#[cfg(feature = "encryption")]
+mod encryption_parameters;
 
 pub use crate::{
+    attributes::{AttributeRef, AttributesRef},
     error::{Error, Result},
     private_key_info::{PrivateKeyInfo, PrivateKeyInfoRef},
     traits::DecodePrivateKey,
@@ -96,7 +107,9 @@ pub use spki::{
 
 #[cfg(feature = "alloc")]
 pub use {
-    crate::{private_key_info::PrivateKeyInfoOwned, traits::EncodePrivateKey},
+    crate::{
+        attributes::Attributes, private_key_info::PrivateKeyInfoOwned, traits::EncodePrivateKey,
+    },
     der::{Document, SecretDocument},
     spki::EncodePublicKey,
 };
@@ -112,5 +125,8 @@ pub use {
     pkcs5,
 };
 
+#[cfg(feature = "encryption")]
+pub use encryption_parameters::{Cipher, EncryptionParameters, KdfParameters};
+
 #[cfg(feature = "rand_core")]
 pub use rand_core;
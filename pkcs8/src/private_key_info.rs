@@ -1,23 +1,28 @@
 //! PKCS#8 `PrivateKeyInfo`.
 
-use crate::{Error, Result, Version};
+use crate::{AttributesRef, Error, Result, Version};
 use core::fmt;
 use der::{
-    asn1::{AnyRef, BitStringRef, ContextSpecific, OctetStringRef},
+    asn1::{AnyRef, BitStringRef, ContextSpecific, ContextSpecificRef, OctetStringRef},
     Decode, DecodeValue, Encode, EncodeValue, FixedTag, Header, Length, Reader, Sequence, TagMode,
     TagNumber, Writer,
 };
 use spki::AlgorithmIdentifier;
 
 #[cfg(feature = "alloc")]
-use der::{
-    asn1::{Any, BitString, OctetString},
-    SecretDocument,
+use {
+    crate::Attributes,
+    der::{
+        asn1::{Any, BitString, ObjectIdentifier, OctetString},
+        SecretDocument,
+    },
 };
 
 #[cfg(feature = "encryption")]
 use {
-    crate::EncryptedPrivateKeyInfoRef, der::zeroize::Zeroizing, pkcs5::pbes2,
+    crate::{EncryptedPrivateKeyInfoRef, EncryptionParameters},
+    der::zeroize::Zeroizing,
+    pkcs5::pbes2,
     rand_core::CryptoRngCore,
 };
 
@@ -27,14 +32,32 @@ use der::pem::PemLabel;
 #[cfg(feature = "subtle")]
 use subtle::{Choice, ConstantTimeEq};
 
+/// Context-specific tag number for the attributes.
+const ATTRIBUTES_TAG: TagNumber = TagNumber::N0;
+
 /// Context-specific tag number for the public key.
 const PUBLIC_KEY_TAG: TagNumber = TagNumber::N1;
 
+/// [RFC 8410] algorithm OIDs, with the length of their private and public
+/// keys, which are the same.
+///
+/// [RFC 8410]: https://datatracker.ietf.org/doc/html/rfc8410
+#[cfg(feature = "alloc")]
+const RFC8410_ALGORITHMS: [(ObjectIdentifier, usize); 4] = [
+    // X25519
+    (ObjectIdentifier::new_unwrap("1.3.101.110"), 32),
+    // X448
+    (ObjectIdentifier::new_unwrap("1.3.101.111"), 56),
+    // Ed25519
+    (ObjectIdentifier::new_unwrap("1.3.101.112"), 32),
+    // Ed448
+    (ObjectIdentifier::new_unwrap("1.3.101.113"), 57),
+];
+
 /// PKCS#8 `PrivateKeyInfo`.
 ///
 /// ASN.1 structure containing an `AlgorithmIdentifier`, private key
-/// data in an algorithm specific format, and optional attributes
-/// (ignored by this implementation).
+/// data in an algorithm specific format, and optional attributes.
 ///
 /// Supports PKCS#8 v1 as described in [RFC 5208] and PKCS#8 v2 as described
 /// in [RFC 5958]. PKCS#8 v2 keys include an additional public key field.
@@ -92,18 +115,21 @@ const PUBLIC_KEY_TAG: TagNumber = TagNumber::N1;
 /// [RFC 5208 Section 5]: https://tools.ietf.org/html/rfc5208#section-5
 /// [RFC 5958 Section 2]: https://datatracker.ietf.org/doc/html/rfc5958#section-2
 #[derive(Clone)]
-pub struct PrivateKeyInfo<Params, Key, PubKey> {
+pub struct PrivateKeyInfo<Params, Key, PubKey, Attrs> {
     /// X.509 `AlgorithmIdentifier` for the private key type.
     pub algorithm: AlgorithmIdentifier<Params>,
 
     /// Private key data.
     pub private_key: Key,
 
+    /// Attributes, such as the PKCS#9 `friendlyName` and `localKeyId`.
+    pub attributes: Option<Attrs>,
+
     /// Public key data, optionally available if version is V2.
     pub public_key: Option<PubKey>,
 }
 
-impl<Params, Key, PubKey> PrivateKeyInfo<Params, Key, PubKey> {
+impl<Params, Key, PubKey, Attrs> PrivateKeyInfo<Params, Key, PubKey, Attrs> {
     /// Create a new PKCS#8 [`PrivateKeyInfo`] message.
     ///
     /// This is a helper method which initializes `attributes` and `public_key`
@@ -112,6 +138,7 @@ impl<Params, Key, PubKey> PrivateKeyInfo<Params, Key, PubKey> {
         Self {
             algorithm,
             private_key,
+            attributes: None,
             public_key: None,
         }
     }
@@ -128,13 +155,15 @@ impl<Params, Key, PubKey> PrivateKeyInfo<Params, Key, PubKey> {
     }
 }
 
-impl<'a, Params, Key, PubKey> PrivateKeyInfo<Params, Key, PubKey>
+impl<'a, Params, Key, PubKey, Attrs> PrivateKeyInfo<Params, Key, PubKey, Attrs>
 where
     Params: der::Choice<'a, Error = der::Error> + Encode,
     Key: DecodeValue<'a, Error = der::Error> + FixedTag + 'a,
     Key: EncodeValue,
     PubKey: DecodeValue<'a, Error = der::Error> + FixedTag + 'a,
     PubKey: BitStringLike,
+    Attrs: DecodeValue<'a, Error = der::Error> + FixedTag + 'a,
+    Attrs: EncodeValue,
 {
     /// Encrypt this private key using a symmetric encryption key derived
     /// from the provided password.
@@ -166,13 +195,37 @@ where
         let der = Zeroizing::new(self.to_der()?);
         EncryptedPrivateKeyInfoRef::encrypt_with(pbes2_params, password, der.as_ref())
     }
+
+    /// Encrypt this private key using a symmetric encryption key derived
+    /// from the provided password, with the KDF, salt length and cipher
+    /// described by [`EncryptionParameters`] and a random salt and IV.
+    ///
+    /// Returns an error if the parameters fail [`EncryptionParameters::validate`].
+    #[cfg(feature = "encryption")]
+    pub fn encrypt_with(
+        &self,
+        rng: &mut impl CryptoRngCore,
+        password: impl AsRef<[u8]>,
+        params: &EncryptionParameters,
+    ) -> Result<SecretDocument> {
+        self.encrypt_with_params(params.generate(rng)?, password)
+    }
 }
 
-impl<'a, Params, Key, PubKey> PrivateKeyInfo<Params, Key, PubKey>
+impl<'a, Params, Key, PubKey, Attrs> PrivateKeyInfo<Params, Key, PubKey, Attrs>
 where
     Params: der::Choice<'a> + Encode,
     PubKey: BitStringLike,
 {
+    /// Get a context-specific representation of the attributes, if present.
+    fn attributes_context_specific(&self) -> Option<ContextSpecificRef<'_, Attrs>> {
+        self.attributes.as_ref().map(|value| ContextSpecificRef {
+            tag_number: ATTRIBUTES_TAG,
+            tag_mode: TagMode::Implicit,
+            value,
+        })
+    }
+
     /// Get a `BIT STRING` representation of the public key, if present.
     fn public_key_bit_string(&self) -> Option<ContextSpecific<BitStringRef<'_>>> {
         self.public_key.as_ref().map(|pk| {
@@ -186,11 +239,12 @@ where
     }
 }
 
-impl<'a, Params, Key, PubKey> DecodeValue<'a> for PrivateKeyInfo<Params, Key, PubKey>
+impl<'a, Params, Key, PubKey, Attrs> DecodeValue<'a> for PrivateKeyInfo<Params, Key, PubKey, Attrs>
 where
     Params: der::Choice<'a, Error = der::Error> + Encode,
     Key: DecodeValue<'a, Error = der::Error> + FixedTag + 'a,
     PubKey: DecodeValue<'a, Error = der::Error> + FixedTag + 'a,
+    Attrs: DecodeValue<'a, Error = der::Error> + FixedTag + 'a,
 {
     type Error = der::Error;
 
@@ -200,6 +254,7 @@ where
             let version = Version::decode(reader)?;
             let algorithm = reader.decode()?;
             let private_key = Key::decode(reader)?;
+            let attributes = reader.context_specific::<Attrs>(ATTRIBUTES_TAG, TagMode::Implicit)?;
             let public_key =
                 reader.context_specific::<PubKey>(PUBLIC_KEY_TAG, TagMode::Implicit)?;
 
@@ -222,22 +277,25 @@ where
             Ok(Self {
                 algorithm,
                 private_key,
+                attributes,
                 public_key,
             })
         })
     }
 }
 
-impl<'a, Params, Key, PubKey> EncodeValue for PrivateKeyInfo<Params, Key, PubKey>
+impl<'a, Params, Key, PubKey, Attrs> EncodeValue for PrivateKeyInfo<Params, Key, PubKey, Attrs>
 where
     Params: der::Choice<'a, Error = der::Error> + Encode,
     Key: EncodeValue + FixedTag,
     PubKey: BitStringLike,
+    Attrs: EncodeValue + FixedTag,
 {
     fn value_len(&self) -> der::Result<Length> {
         self.version().encoded_len()?
             + self.algorithm.encoded_len()?
             + self.private_key.encoded_len()?
+            + self.attributes_context_specific().encoded_len()?
             + self.public_key_bit_string().encoded_len()?
     }
 
@@ -245,28 +303,34 @@ where
         self.version().encode(writer)?;
         self.algorithm.encode(writer)?;
         self.private_key.encode(writer)?;
+        self.attributes_context_specific().encode(writer)?;
         self.public_key_bit_string().encode(writer)?;
         Ok(())
     }
 }
 
-impl<'a, Params, Key, PubKey> Sequence<'a> for PrivateKeyInfo<Params, Key, PubKey>
+impl<'a, Params, Key, PubKey, Attrs> Sequence<'a> for PrivateKeyInfo<Params, Key, PubKey, Attrs>
 where
     Params: der::Choice<'a, Error = der::Error> + Encode,
     Key: DecodeValue<'a, Error = der::Error> + FixedTag + 'a,
     Key: EncodeValue,
     PubKey: DecodeValue<'a, Error = der::Error> + FixedTag + 'a,
     PubKey: BitStringLike,
+    Attrs: DecodeValue<'a, Error = der::Error> + FixedTag + 'a,
+    Attrs: EncodeValue,
 {
 }
 
-impl<'a, Params, Key, PubKey> TryFrom<&'a [u8]> for PrivateKeyInfo<Params, Key, PubKey>
+impl<'a, Params, Key, PubKey, Attrs> TryFrom<&'a [u8]>
+    for PrivateKeyInfo<Params, Key, PubKey, Attrs>
 where
     Params: der::Choice<'a, Error = der::Error> + Encode,
     Key: DecodeValue<'a, Error = der::Error> + FixedTag + 'a,
     Key: EncodeValue,
     PubKey: DecodeValue<'a, Error = der::Error> + FixedTag + 'a,
     PubKey: BitStringLike,
+    Attrs: DecodeValue<'a, Error = der::Error> + FixedTag + 'a,
+    Attrs: EncodeValue,
 {
     type Error = Error;
 
@@ -275,68 +339,80 @@ where
     }
 }
 
-impl<Params, Key, PubKey> fmt::Debug for PrivateKeyInfo<Params, Key, PubKey>
+impl<Params, Key, PubKey, Attrs> fmt::Debug for PrivateKeyInfo<Params, Key, PubKey, Attrs>
 where
     Params: fmt::Debug,
     PubKey: fmt::Debug,
+    Attrs: fmt::Debug,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("PrivateKeyInfo")
             .field("version", &self.version())
             .field("algorithm", &self.algorithm)
+            .field("attributes", &self.attributes)
             .field("public_key", &self.public_key)
             .finish_non_exhaustive()
     }
 }
 
 #[cfg(feature = "alloc")]
-impl<'a, Params, Key, PubKey> TryFrom<PrivateKeyInfo<Params, Key, PubKey>> for SecretDocument
+impl<'a, Params, Key, PubKey, Attrs> TryFrom<PrivateKeyInfo<Params, Key, PubKey, Attrs>>
+    for SecretDocument
 where
     Params: der::Choice<'a, Error = der::Error> + Encode,
     Key: DecodeValue<'a, Error = der::Error> + FixedTag + 'a,
     Key: EncodeValue,
     PubKey: DecodeValue<'a, Error = der::Error> + FixedTag + 'a,
     PubKey: BitStringLike,
+    Attrs: DecodeValue<'a, Error = der::Error> + FixedTag + 'a,
+    Attrs: EncodeValue,
 {
     type Error = Error;
 
-    fn try_from(private_key: PrivateKeyInfo<Params, Key, PubKey>) -> Result<SecretDocument> {
+    fn try_from(private_key: PrivateKeyInfo<Params, Key, PubKey, Attrs>) -> Result<SecretDocument> {
         SecretDocument::try_from(&private_key)
     }
 }
 
 #[cfg(feature = "alloc")]
-impl<'a, Params, Key, PubKey> TryFrom<&PrivateKeyInfo<Params, Key, PubKey>> for SecretDocument
+impl<'a, Params, Key, PubKey, Attrs> TryFrom<&PrivateKeyInfo<Params, Key, PubKey, Attrs>>
+    for SecretDocument
 where
     Params: der::Choice<'a, Error = der::Error> + Encode,
     Key: DecodeValue<'a, Error = der::Error> + FixedTag + 'a,
     Key: EncodeValue,
     PubKey: DecodeValue<'a, Error = der::Error> + FixedTag + 'a,
     PubKey: BitStringLike,
+    Attrs: DecodeValue<'a, Error = der::Error> + FixedTag + 'a,
+    Attrs: EncodeValue,
 {
     type Error = Error;
 
-    fn try_from(private_key: &PrivateKeyInfo<Params, Key, PubKey>) -> Result<SecretDocument> {
+    fn try_from(
+        private_key: &PrivateKeyInfo<Params, Key, PubKey, Attrs>,
+    ) -> Result<SecretDocument> {
         Ok(Self::encode_msg(private_key)?)
     }
 }
 
 #[cfg(feature = "pem")]
-impl<Params, Key, PubKey> PemLabel for PrivateKeyInfo<Params, Key, PubKey> {
+impl<Params, Key, PubKey, Attrs> PemLabel for PrivateKeyInfo<Params, Key, PubKey, Attrs> {
     const PEM_LABEL: &'static str = "PRIVATE KEY";
 }
 
 #[cfg(feature = "subtle")]
-impl<Params, Key, PubKey> ConstantTimeEq for PrivateKeyInfo<Params, Key, PubKey>
+impl<Params, Key, PubKey, Attrs> ConstantTimeEq for PrivateKeyInfo<Params, Key, PubKey, Attrs>
 where
     Params: Eq,
     Key: PartialEq + AsRef<[u8]>,
     PubKey: PartialEq,
+    Attrs: PartialEq,
 {
     fn ct_eq(&self, other: &Self) -> Choice {
         // NOTE: public fields are not compared in constant time
-        let public_fields_eq =
-            self.algorithm == other.algorithm && self.public_key == other.public_key;
+        let public_fields_eq = self.algorithm == other.algorithm
+            && self.attributes == other.attributes
+            && self.public_key == other.public_key;
 
         self.private_key.as_ref().ct_eq(other.private_key.as_ref())
             & Choice::from(public_fields_eq as u8)
@@ -344,20 +420,22 @@ where
 }
 
 #[cfg(feature = "subtle")]
-impl<Params, Key, PubKey> Eq for PrivateKeyInfo<Params, Key, PubKey>
+impl<Params, Key, PubKey, Attrs> Eq for PrivateKeyInfo<Params, Key, PubKey, Attrs>
 where
     Params: Eq,
     Key: AsRef<[u8]> + Eq,
     PubKey: Eq,
+    Attrs: Eq,
 {
 }
 
 #[cfg(feature = "subtle")]
-impl<Params, Key, PubKey> PartialEq for PrivateKeyInfo<Params, Key, PubKey>
+impl<Params, Key, PubKey, Attrs> PartialEq for PrivateKeyInfo<Params, Key, PubKey, Attrs>
 where
     Params: Eq,
     Key: PartialEq + AsRef<[u8]>,
     PubKey: PartialEq,
+    Attrs: PartialEq,
 {
     fn eq(&self, other: &Self) -> bool {
         self.ct_eq(other).into()
@@ -365,11 +443,12 @@ where
 }
 
 /// [`PrivateKeyInfo`] with [`AnyRef`] algorithm parameters, and `&[u8]` key.
-pub type PrivateKeyInfoRef<'a> = PrivateKeyInfo<AnyRef<'a>, OctetStringRef<'a>, BitStringRef<'a>>;
+pub type PrivateKeyInfoRef<'a> =
+    PrivateKeyInfo<AnyRef<'a>, OctetStringRef<'a>, BitStringRef<'a>, AttributesRef<'a>>;
 
 /// [`PrivateKeyInfo`] with [`Any`] algorithm parameters, and `Box<[u8]>` key.
 #[cfg(feature = "alloc")]
-pub type PrivateKeyInfoOwned = PrivateKeyInfo<Any, OctetString, BitString>;
+pub type PrivateKeyInfoOwned = PrivateKeyInfo<Any, OctetString, BitString, Attributes>;
 
 /// [`BitStringLike`] marks object that will act like a BitString.
 ///
@@ -401,6 +480,7 @@ mod allocating {
             PrivateKeyInfoOwned {
                 algorithm: self.algorithm.ref_to_owned(),
                 private_key: self.private_key.ref_to_owned(),
+                attributes: self.attributes.ref_to_owned(),
                 public_key: self.public_key.ref_to_owned(),
             }
         }
@@ -412,8 +492,84 @@ mod allocating {
             PrivateKeyInfoRef {
                 algorithm: self.algorithm.owned_to_ref(),
                 private_key: self.private_key.owned_to_ref(),
+                attributes: self.attributes.owned_to_ref(),
                 public_key: self.public_key.owned_to_ref(),
             }
         }
     }
+
+    impl PrivateKeyInfoOwned {
+        /// Convert an [RFC 8410] key (X25519, X448, Ed25519 or Ed448) into a
+        /// PKCS#8 v1 key, by removing its public key.
+        ///
+        /// Attributes are kept, as they are allowed in both versions.
+        ///
+        /// [RFC 8410]: https://datatracker.ietf.org/doc/html/rfc8410
+        pub fn to_v1(&self) -> Result<Self> {
+            self.rfc8410_key_len()?;
+
+            Ok(Self {
+                public_key: None,
+                ..self.clone()
+            })
+        }
+
+        /// Convert an [RFC 8410] key (X25519, X448, Ed25519 or Ed448) into a
+        /// PKCS#8 v2 key, by adding its `public_key`.
+        ///
+        /// Returns [`Error::KeyMalformed`] if `public_key` doesn't have the
+        /// length of the algorithm, or if the key already has a different
+        /// public key.
+        ///
+        /// `public_key` is not checked against the private key, as that
+        /// requires the curve arithmetic: callers must derive it, or check
+        /// that it matches, with the crate implementing the curve.
+        ///
+        /// [RFC 8410]: https://datatracker.ietf.org/doc/html/rfc8410
+        pub fn to_v2(&self, public_key: &[u8]) -> Result<Self> {
+            if public_key.len() != self.rfc8410_key_len()? {
+                return Err(Error::KeyMalformed);
+            }
+
+            let public_key = BitString::from_bytes(public_key)?;
+            if self
+                .public_key
+                .as_ref()
+                .is_some_and(|existing| existing != &public_key)
+            {
+                return Err(Error::KeyMalformed);
+            }
+
+            Ok(Self {
+                public_key: Some(public_key),
+                ..self.clone()
+            })
+        }
+
+        /// Check that this is a well-formed [RFC 8410] key, and get the length
+        /// of its private and public keys.
+        ///
+        /// [RFC 8410]: https://datatracker.ietf.org/doc/html/rfc8410
+        fn rfc8410_key_len(&self) -> Result<usize> {
+            let oid = self.algorithm.oid;
+            let (_, key_len) = RFC8410_ALGORITHMS
+                .into_iter()
+                .find(|(algorithm, _)| *algorithm == oid)
+                .ok_or(spki::Error::OidUnknown { oid })?;
+
+            // the parameters MUST be absent
+            if self.algorithm.parameters.is_some() {
+                return Err(Error::ParametersMalformed);
+            }
+
+            // `CurvePrivateKey ::= OCTET STRING`
+            let private_key = OctetStringRef::from_der(self.private_key.as_bytes())
+                .map_err(|_| Error::KeyMalformed)?;
+            if private_key.as_bytes().len() != key_len {
+                return Err(Error::KeyMalformed);
+            }
+
+            Ok(key_len)
+        }
+    }
 }
@@ -13,7 +13,10 @@ use der::Encode;
 use der::EncodePem;
 
 #[cfg(feature = "encryption")]
-use pkcs8::EncryptedPrivateKeyInfoOwned;
+use pkcs8::{
+    pkcs5::pbes2::Pbkdf2Prf, Cipher, EncryptedPrivateKeyInfoOwned, EncryptionParameters,
+    KdfParameters,
+};
 
 /// Ed25519 PKCS#8 private key plaintext encoded as ASN.1 DER
 #[cfg(feature = "encryption")]
@@ -72,6 +75,44 @@ const ED25519_DER_AES128_GCM_SCRYPT_EXAMPLE: &[u8] =
 const ED25519_DER_AES256_GCM_SCRYPT_EXAMPLE: &[u8] =
     include_bytes!("examples/ed25519-encpriv-aes256-gcm-scrypt.der");
 
+/// Ed25519 PKCS#8 encrypted private key (PBES2 + AES-256-GCM + PBKDF2-SHA256) encoded as ASN.1 DER,
+/// with RFC 5084 `GCMParameters` using the default 96-bit authentication tag.
+///
+/// Generated with the Python `cryptography` package, as OpenSSL doesn't support AEAD ciphers
+/// in PKCS#8.
+#[cfg(feature = "encryption")]
+const ED25519_DER_AES256_GCM12_PBKDF2_SHA256_EXAMPLE: &[u8] =
+    include_bytes!("examples/ed25519-encpriv-aes256-gcm12-pbkdf2-sha256.der");
+
+/// Ed25519 PKCS#8 encrypted private key (PBES2 + AES-128-GCM + PBKDF2-SHA256) encoded as ASN.1 DER,
+/// with RFC 5084 `GCMParameters` using a 128-bit authentication tag.
+///
+/// Generated with the Python `cryptography` package, as OpenSSL doesn't support AEAD ciphers
+/// in PKCS#8.
+#[cfg(feature = "encryption")]
+const ED25519_DER_AES128_GCM16_PBKDF2_SHA256_EXAMPLE: &[u8] =
+    include_bytes!("examples/ed25519-encpriv-aes128-gcm16-pbkdf2-sha256.der");
+
+/// Ed25519 PKCS#8 encrypted private key (PBES2 + AES-192-CBC + PBKDF2-SHA256) encoded as ASN.1 DER.
+///
+/// Generated using:
+///
+/// ```
+/// $ openssl pkcs8 -v2 aes-192-cbc -v2prf hmacWithSHA256 -topk8 -inform der -in ed25519-priv-pkcs8v1.der -outform der -out ed25519-encpriv-aes192-pbkdf2-sha256.der
+/// ```
+#[cfg(feature = "encryption")]
+const ED25519_DER_AES192_PBKDF2_SHA256_EXAMPLE: &[u8] =
+    include_bytes!("examples/ed25519-encpriv-aes192-pbkdf2-sha256.der");
+
+/// Ed25519 PKCS#8 encrypted private key (PBES2 + AES-192-GCM + PBKDF2-SHA256) encoded as ASN.1 DER,
+/// with RFC 5084 `GCMParameters` using a 128-bit authentication tag.
+///
+/// Generated with the Python `cryptography` package, as OpenSSL doesn't support AEAD ciphers
+/// in PKCS#8.
+#[cfg(feature = "encryption")]
+const ED25519_DER_AES192_GCM16_PBKDF2_SHA256_EXAMPLE: &[u8] =
+    include_bytes!("examples/ed25519-encpriv-aes192-gcm16-pbkdf2-sha256.der");
+
 /// Ed25519 PKCS#8 encrypted private key encoded as PEM
 #[cfg(feature = "pem")]
 const ED25519_PEM_AES256_PBKDF2_SHA256_EXAMPLE: &str =
@@ -99,6 +140,28 @@ const ED25519_DER_DES3_PBKDF2_SHA256_EXAMPLE: &[u8] =
 const ED25519_DER_DES_PBKDF2_SHA256_EXAMPLE: &[u8] =
     include_bytes!("examples/ed25519-encpriv-des-pbkdf2-sha256.der");
 
+/// Ed25519 PKCS#8 encrypted private key (PBES1 + SHA-1 + DES) encoded as ASN.1 DER
+///
+/// Generated using:
+///
+/// ```
+/// $ openssl pkcs8 -v1 PBE-SHA1-DES -topk8 -inform der -in ed25519-priv-pkcs8v1.der -outform der -out ed25519-encpriv-pbes1-sha1-des.der -provider legacy -provider default
+/// ```
+#[cfg(feature = "pbes1-insecure")]
+const ED25519_DER_PBES1_SHA1_DES_EXAMPLE: &[u8] =
+    include_bytes!("examples/ed25519-encpriv-pbes1-sha1-des.der");
+
+/// Ed25519 PKCS#8 encrypted private key (PBES1 + SHA1 + RC2-CBC) encoded as ASN.1 DER.
+///
+/// Generated using:
+///
+/// ```
+/// $ openssl pkcs8 -v1 PBE-SHA1-RC2-64 -topk8 -inform der -in ed25519-priv-pkcs8v1.der -outform der -out ed25519-encpriv-pbes1-sha1-rc2.der -provider legacy -provider default
+/// ```
+#[cfg(feature = "pbes1-insecure")]
+const ED25519_DER_PBES1_SHA1_RC2_EXAMPLE: &[u8] =
+    include_bytes!("examples/ed25519-encpriv-pbes1-sha1-rc2.der");
+
 /// Password used to encrypt the keys.
 #[cfg(feature = "encryption")]
 const PASSWORD: &[u8] = b"hunter42"; // Bad password; don't actually use outside tests!
@@ -249,6 +312,150 @@ fn encrypt_ed25519_der_encpriv_aes256_gcm_scrypt() {
     );
 }
 
+#[cfg(feature = "encryption")]
+#[test]
+fn decrypt_ed25519_der_encpriv_aes256_gcm12_pbkdf2_sha256() {
+    let enc_pk =
+        EncryptedPrivateKeyInfoOwned::try_from(ED25519_DER_AES256_GCM12_PBKDF2_SHA256_EXAMPLE)
+            .unwrap();
+    assert_eq!(
+        enc_pk.encryption_algorithm.pbes2().unwrap().encryption,
+        pbes2::EncryptionScheme::Aes256GcmParams {
+            params: pbes2::GcmParameters {
+                nonce: hex!("0102030405060708090A0B0C"),
+                icv_len: 12,
+            },
+        }
+    );
+
+    let pk = enc_pk.decrypt(PASSWORD).unwrap();
+    assert_eq!(pk.as_bytes(), ED25519_DER_PLAINTEXT_EXAMPLE);
+}
+
+#[cfg(feature = "encryption")]
+#[test]
+fn encrypt_ed25519_der_encpriv_aes256_gcm12_pbkdf2_sha256() {
+    let pbes2_params = pbes2::Parameters {
+        kdf: pbes2::Pbkdf2Params::hmac_with_sha256(2048, &hex!("7A1B2C3D4E5F60718293A4B5C6D7E8F9"))
+            .unwrap()
+            .into(),
+        encryption: pbes2::EncryptionScheme::Aes256GcmParams {
+            params: pbes2::GcmParameters {
+                nonce: hex!("0102030405060708090A0B0C"),
+                icv_len: 12,
+            },
+        },
+    };
+
+    let pk_plaintext = PrivateKeyInfoRef::try_from(ED25519_DER_PLAINTEXT_EXAMPLE).unwrap();
+    let pk_encrypted = pk_plaintext
+        .encrypt_with_params(pbes2_params, PASSWORD)
+        .unwrap();
+
+    assert_eq!(
+        pk_encrypted.as_bytes(),
+        ED25519_DER_AES256_GCM12_PBKDF2_SHA256_EXAMPLE
+    );
+}
+
+#[cfg(feature = "encryption")]
+#[test]
+fn decrypt_ed25519_der_encpriv_aes128_gcm16_pbkdf2_sha256() {
+    let enc_pk =
+        EncryptedPrivateKeyInfoOwned::try_from(ED25519_DER_AES128_GCM16_PBKDF2_SHA256_EXAMPLE)
+            .unwrap();
+    assert_eq!(
+        enc_pk.encryption_algorithm.pbes2().unwrap().encryption,
+        pbes2::EncryptionScheme::Aes128GcmParams {
+            params: pbes2::GcmParameters {
+                nonce: hex!("A1A2A3A4A5A6A7A8A9AAABAC"),
+                icv_len: 16,
+            },
+        }
+    );
+
+    let pk = enc_pk.decrypt(PASSWORD).unwrap();
+    assert_eq!(pk.as_bytes(), ED25519_DER_PLAINTEXT_EXAMPLE);
+
+    let enc_der = enc_pk.to_der().unwrap();
+    assert_eq!(enc_der, ED25519_DER_AES128_GCM16_PBKDF2_SHA256_EXAMPLE);
+}
+
+#[cfg(feature = "encryption")]
+#[test]
+fn decrypt_ed25519_der_encpriv_aes192_gcm16_pbkdf2_sha256() {
+    let enc_pk =
+        EncryptedPrivateKeyInfoOwned::try_from(ED25519_DER_AES192_GCM16_PBKDF2_SHA256_EXAMPLE)
+            .unwrap();
+    assert_eq!(
+        enc_pk.encryption_algorithm.pbes2().unwrap().encryption,
+        pbes2::EncryptionScheme::Aes192GcmParams {
+            params: pbes2::GcmParameters {
+                nonce: hex!("0C0B0A090807060504030201"),
+                icv_len: 16,
+            },
+        }
+    );
+
+    let params = EncryptionParameters::try_from(enc_pk.encryption_algorithm.pbes2().unwrap());
+    assert_eq!(params.unwrap().cipher(), Cipher::Aes192Gcm);
+
+    let pk = enc_pk.decrypt(PASSWORD).unwrap();
+    assert_eq!(pk.as_bytes(), ED25519_DER_PLAINTEXT_EXAMPLE);
+}
+
+#[cfg(feature = "encryption")]
+#[test]
+fn encrypt_ed25519_der_encpriv_aes192_gcm16_pbkdf2_sha256() {
+    let pbes2_params = pbes2::Parameters {
+        kdf: pbes2::Pbkdf2Params::hmac_with_sha256(2048, &hex!("A1B2C3D4E5F60718293A4B5C6D7E8F90"))
+            .unwrap()
+            .into(),
+        encryption: pbes2::EncryptionScheme::Aes192GcmParams {
+            params: pbes2::GcmParameters {
+                nonce: hex!("0C0B0A090807060504030201"),
+                icv_len: 16,
+            },
+        },
+    };
+
+    let pk_plaintext = PrivateKeyInfoRef::try_from(ED25519_DER_PLAINTEXT_EXAMPLE).unwrap();
+    let pk_encrypted = pk_plaintext
+        .encrypt_with_params(pbes2_params, PASSWORD)
+        .unwrap();
+
+    assert_eq!(
+        pk_encrypted.as_bytes(),
+        ED25519_DER_AES192_GCM16_PBKDF2_SHA256_EXAMPLE
+    );
+}
+
+#[cfg(feature = "encryption")]
+#[test]
+fn decrypt_ed25519_der_encpriv_aes128_gcm16_pbkdf2_sha256_wrong_password() {
+    let enc_pk =
+        EncryptedPrivateKeyInfoOwned::try_from(ED25519_DER_AES128_GCM16_PBKDF2_SHA256_EXAMPLE)
+            .unwrap();
+    assert_eq!(
+        enc_pk.decrypt(b"hunter43").unwrap_err(),
+        pkcs8::Error::EncryptedPrivateKey(pkcs5::Error::WrongPassword)
+    );
+}
+
+#[cfg(all(feature = "encryption", feature = "getrandom"))]
+#[test]
+fn encrypt_ed25519_der_encpriv_scrypt_gcm() {
+    let pbes2_params = pbes2::Parameters::scrypt_gcm(&mut rand_core::OsRng);
+    let pk_plaintext = PrivateKeyInfoRef::try_from(ED25519_DER_PLAINTEXT_EXAMPLE).unwrap();
+    let pk_encrypted = pk_plaintext
+        .encrypt_with_params(pbes2_params, PASSWORD)
+        .unwrap();
+
+    let enc_pk = EncryptedPrivateKeyInfoOwned::try_from(pk_encrypted.as_bytes()).unwrap();
+    let pk = enc_pk.decrypt(PASSWORD).unwrap();
+    assert_eq!(pk.as_bytes(), ED25519_DER_PLAINTEXT_EXAMPLE);
+}
+
 #[cfg(feature = "encryption")]
 #[test]
 fn encrypt_ed25519_der_encpriv_aes256_pbkdf2_sha256() {
@@ -270,6 +477,45 @@ fn encrypt_ed25519_der_encpriv_aes256_pbkdf2_sha256() {
     );
 }
 
+#[cfg(feature = "encryption")]
+#[test]
+fn decrypt_ed25519_der_encpriv_aes192_pbkdf2_sha256() {
+    let enc_pk =
+        EncryptedPrivateKeyInfoRef::try_from(ED25519_DER_AES192_PBKDF2_SHA256_EXAMPLE).unwrap();
+    assert_eq!(
+        enc_pk.encryption_algorithm.pbes2().unwrap().encryption,
+        pbes2::EncryptionScheme::Aes192Cbc {
+            iv: hex!("26D93568C2928BB93754F98997F6297D"),
+        }
+    );
+
+    let pk = enc_pk.decrypt(PASSWORD).unwrap();
+    assert_eq!(pk.as_bytes(), ED25519_DER_PLAINTEXT_EXAMPLE);
+}
+
+#[cfg(feature = "encryption")]
+#[test]
+fn encrypt_ed25519_der_encpriv_aes192_pbkdf2_sha256() {
+    let pbes2_params = pbes2::Parameters {
+        kdf: pbes2::Pbkdf2Params::hmac_with_sha256(2048, &hex!("249BAF23A8F296FDE4A785CA0D806A6D"))
+            .unwrap()
+            .into(),
+        encryption: pbes2::EncryptionScheme::Aes192Cbc {
+            iv: hex!("26D93568C2928BB93754F98997F6297D"),
+        },
+    };
+
+    let pk_plaintext = PrivateKeyInfoRef::try_from(ED25519_DER_PLAINTEXT_EXAMPLE).unwrap();
+    let pk_encrypted = pk_plaintext
+        .encrypt_with_params(pbes2_params, PASSWORD)
+        .unwrap();
+
+    assert_eq!(
+        pk_encrypted.as_bytes(),
+        ED25519_DER_AES192_PBKDF2_SHA256_EXAMPLE
+    );
+}
+
 #[cfg(feature = "encryption")]
 #[test]
 fn encrypt_ed25519_der_encpriv_aes256_scrypt() {
@@ -327,3 +573,132 @@ fn decrypt_ed25519_der_encpriv_des_pbkdf2_sha256() {
     let pk = enc_pk.decrypt(PASSWORD).unwrap();
     assert_eq!(pk.as_bytes(), ED25519_DER_PLAINTEXT_EXAMPLE);
 }
+
+#[test]
+#[cfg(feature = "pbes1-insecure")]
+fn decrypt_ed25519_der_encpriv_pbes1_sha1_des() {
+    let enc_pk = EncryptedPrivateKeyInfoRef::try_from(ED25519_DER_PBES1_SHA1_DES_EXAMPLE).unwrap();
+    assert!(enc_pk.encryption_algorithm.pbes1().is_some());
+    let pk = enc_pk.decrypt(PASSWORD).unwrap();
+    assert_eq!(pk.as_bytes(), ED25519_DER_PLAINTEXT_EXAMPLE);
+}
+
+#[test]
+#[cfg(feature = "pbes1-insecure")]
+fn decrypt_ed25519_der_encpriv_pbes1_sha1_rc2() {
+    let enc_pk = EncryptedPrivateKeyInfoRef::try_from(ED25519_DER_PBES1_SHA1_RC2_EXAMPLE).unwrap();
+    assert!(enc_pk.encryption_algorithm.pbes1().is_some());
+    let pk = enc_pk.decrypt(PASSWORD).unwrap();
+    assert_eq!(pk.as_bytes(), ED25519_DER_PLAINTEXT_EXAMPLE);
+}
+
+#[cfg(feature = "encryption")]
+#[test]
+fn read_encryption_parameters_pbkdf2() {
+    let enc_pk =
+        EncryptedPrivateKeyInfoRef::try_from(ED25519_DER_AES256_PBKDF2_SHA256_EXAMPLE).unwrap();
+    let params = enc_pk.encryption_parameters().unwrap();
+
+    assert_eq!(
+        params.kdf(),
+        KdfParameters::Pbkdf2 {
+            prf: Pbkdf2Prf::HmacWithSha256,
+            iterations: 2048
+        }
+    );
+    assert_eq!(params.salt_len(), 8);
+    assert_eq!(params.cipher(), Cipher::Aes256Cbc);
+    assert!(params.validate().is_ok());
+}
+
+#[cfg(feature = "encryption")]
+#[test]
+fn read_encryption_parameters_scrypt() {
+    let enc_pk = EncryptedPrivateKeyInfoRef::try_from(ED25519_DER_AES256_SCRYPT_EXAMPLE).unwrap();
+    let params = enc_pk.encryption_parameters().unwrap();
+
+    assert_eq!(
+        params.kdf(),
+        KdfParameters::Scrypt {
+            log_n: 15,
+            r: 8,
+            p: 1
+        }
+    );
+    assert_eq!(params.salt_len(), 8);
+    assert_eq!(params.cipher(), Cipher::Aes256Cbc);
+}
+
+#[cfg(feature = "encryption")]
+#[test]
+fn validate_encryption_parameters() {
+    let params = EncryptionParameters::pbkdf2(Pbkdf2Prf::HmacWithSha256, 999);
+    assert!(params.validate().is_err());
+    assert!(params.allow_insecure(true).validate().is_ok());
+
+    let params = EncryptionParameters::default().with_salt_len(7);
+    assert!(params.validate().is_err());
+    assert!(params.allow_insecure(true).validate().is_ok());
+
+    let params = EncryptionParameters::pbkdf2(Pbkdf2Prf::HmacWithSha256, 0).allow_insecure(true);
+    assert!(params.validate().is_err());
+
+    let params = EncryptionParameters::default()
+        .with_salt_len(33)
+        .allow_insecure(true);
+    assert!(params.validate().is_err());
+
+    // scrypt parameters are checked by `ScryptParams::validate`
+    let invalid = Err(pkcs8::Error::EncryptedPrivateKey(
+        pkcs5::Error::AlgorithmParametersInvalid {
+            oid: pkcs5::pbes2::SCRYPT_OID,
+        },
+    ));
+    assert!(EncryptionParameters::scrypt(15, 1, 1).validate().is_ok());
+    assert_eq!(EncryptionParameters::scrypt(16, 1, 1).validate(), invalid);
+    assert_eq!(EncryptionParameters::scrypt(0, 8, 1).validate(), invalid);
+    assert_eq!(EncryptionParameters::scrypt(15, 8, 0).validate(), invalid);
+    assert_eq!(
+        EncryptionParameters::scrypt(15, 8, 1 << 16).validate(),
+        invalid
+    );
+
+    assert_eq!(
+        EncryptionParameters::scrypt(30, 8, 1).validate(),
+        Err(pkcs8::Error::EncryptedPrivateKey(
+            pkcs5::Error::MemoryLimitExceeded {
+                limit: pkcs5::pbes2::ScryptParams::MAX_MEMORY
+            }
+        ))
+    );
+}
+
+#[cfg(all(feature = "encryption", feature = "getrandom"))]
+#[test]
+fn encrypt_ed25519_der_with_encryption_parameters() {
+    let params = EncryptionParameters::pbkdf2(Pbkdf2Prf::HmacWithSha256, 600_000)
+        .with_salt_len(16)
+        .with_cipher(Cipher::Aes256Cbc);
+
+    let pk_plaintext = PrivateKeyInfoRef::try_from(ED25519_DER_PLAINTEXT_EXAMPLE).unwrap();
+    let pk_encrypted = pk_plaintext
+        .encrypt_with(&mut rand_core::OsRng, PASSWORD, &params)
+        .unwrap();
+
+    let enc_pk = EncryptedPrivateKeyInfoOwned::try_from(pk_encrypted.as_bytes()).unwrap();
+    assert_eq!(enc_pk.encryption_parameters().unwrap(), params);
+
+    let pk = enc_pk.decrypt(PASSWORD).unwrap();
+    assert_eq!(pk.as_bytes(), ED25519_DER_PLAINTEXT_EXAMPLE);
+}
+
+#[cfg(all(feature = "encryption", feature = "getrandom"))]
+#[test]
+fn encrypt_ed25519_der_with_insecure_encryption_parameters() {
+    let params = EncryptionParameters::pbkdf2(Pbkdf2Prf::HmacWithSha256, 100);
+    let pk_plaintext = PrivateKeyInfoRef::try_from(ED25519_DER_PLAINTEXT_EXAMPLE).unwrap();
+
+    assert!(pk_plaintext
+        .encrypt_with(&mut rand_core::OsRng, PASSWORD, &params)
+        .is_err());
+}
@@ -1,11 +1,14 @@
 //! PKCS#8 private key tests
 
-use der::asn1::{ObjectIdentifier, OctetStringRef};
+use der::asn1::{ObjectIdentifier, OctetStringRef, Utf8StringRef};
 use hex_literal::hex;
 use pkcs8::{PrivateKeyInfoRef, Version};
 
 #[cfg(feature = "alloc")]
-use der::Encode;
+use {
+    der::{asn1::BitStringRef, Encode},
+    pkcs8::{Attributes, PrivateKeyInfoOwned},
+};
 
 #[cfg(feature = "pem")]
 use der::{pem::LineEnding, EncodePem};
@@ -22,12 +25,32 @@ const ED25519_DER_V1_EXAMPLE: &[u8] = include_bytes!("examples/ed25519-priv-pkcs
 /// Ed25519 PKCS#8 v2 private key + public key encoded as ASN.1 DER
 const ED25519_DER_V2_EXAMPLE: &[u8] = include_bytes!("examples/ed25519-priv-pkcs8v2.der");
 
+/// Ed25519 PKCS#8 v2 private key + public key encoded as ASN.1 DER, without attributes
+///
+/// Same key as `ED25519_DER_V2_EXAMPLE`, with the `attributes` field removed.
+const ED25519_DER_V2_NO_ATTRS_EXAMPLE: &[u8] =
+    include_bytes!("examples/ed25519-priv-pkcs8v2-no-attrs.der");
+
+/// Ed25519 PKCS#8 v1 private key encoded as ASN.1 DER
+///
+/// Same key as `ED25519_DER_V2_EXAMPLE`, as written by `openssl pkey`.
+#[cfg(feature = "alloc")]
+const ED25519_DER_V2_AS_V1_EXAMPLE: &[u8] =
+    include_bytes!("examples/ed25519-priv-pkcs8v2-as-v1.der");
+
 /// RSA-2048 PKCS#8 private key encoded as ASN.1 DER
 const RSA_2048_DER_EXAMPLE: &[u8] = include_bytes!("examples/rsa2048-priv.der");
 
 /// X25519 PKCS#8 private key encoded as ASN.1 DER
 const X25519_DER_EXAMPLE: &[u8] = include_bytes!("examples/x25519-priv.der");
 
+/// X25519 PKCS#8 v2 private key + public key encoded as ASN.1 DER
+///
+/// Same key as `X25519_DER_EXAMPLE`, with the public key extracted with:
+/// $ openssl pkey -inform der -in tests/examples/x25519-priv.der -pubout
+#[cfg(feature = "alloc")]
+const X25519_DER_V2_EXAMPLE: &[u8] = include_bytes!("examples/x25519-priv-pkcs8v2.der");
+
 /// Elliptic Curve (P-256) PKCS#8 private key encoded as PEM
 #[cfg(feature = "pem")]
 const EC_P256_PEM_EXAMPLE: &str = include_str!("examples/p256-priv.pem");
@@ -124,6 +147,7 @@ fn decode_ed25519_der_v2() {
         hex!("0420D4EE72DBF913584AD5B6D8F1F769F8AD3AFE7C28CBF1D4FBE097A88F44755842");
     const PUB_KEY: [u8; 32] =
         hex!("19BF44096984CDFE8541BAC167DC3B96C85086AA30B6B6CB0C5C38AD703166E1");
+    const ATTR_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.9.20");
 
     let pk = PrivateKeyInfoRef::try_from(ED25519_DER_V2_EXAMPLE).unwrap();
     assert_eq!(pk.version(), Version::V2);
@@ -131,6 +155,15 @@ fn decode_ed25519_der_v2() {
     assert_eq!(pk.algorithm.parameters, None);
     assert_eq!(pk.private_key.as_ref(), PRIV_KEY);
     assert_eq!(pk.public_key.and_then(|p| p.as_bytes()), Some(&PUB_KEY[..]));
+
+    let attributes = pk.attributes.unwrap();
+    let attribute = attributes.get(ATTR_OID).unwrap();
+    assert_eq!(attributes.iter().count(), 1);
+    assert_eq!(
+        attribute.values().collect::<Vec<_>>(),
+        [Utf8StringRef::new("Curdle Chairs").unwrap().into()]
+    );
+    assert_eq!(attributes.local_key_id(), Ok(None));
 }
 
 #[test]
@@ -194,6 +227,106 @@ fn encode_ed25519_der_v2() {
     );
 }
 
+#[test]
+#[cfg(feature = "alloc")]
+fn encode_ed25519_der_v2_attributes() {
+    let pk = PrivateKeyInfoRef::try_from(ED25519_DER_V2_EXAMPLE).unwrap();
+    assert_eq!(ED25519_DER_V2_EXAMPLE, pk.to_der().unwrap());
+
+    let pk_owned = PrivateKeyInfoOwned::try_from(ED25519_DER_V2_EXAMPLE).unwrap();
+    assert_eq!(ED25519_DER_V2_EXAMPLE, pk_owned.to_der().unwrap());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn encode_ed25519_der_v1_attributes() {
+    let mut attributes = Attributes::new();
+    attributes.set_local_key_id(&hex!("01020304")).unwrap();
+    attributes.set_friendly_name("key").unwrap();
+    attributes.set_friendly_name("my key").unwrap();
+    assert_eq!(attributes.iter().count(), 2);
+
+    let mut pk = PrivateKeyInfoOwned::try_from(ED25519_DER_V1_EXAMPLE).unwrap();
+    pk.attributes = Some(attributes);
+    assert_eq!(pk.version(), Version::V1);
+
+    let der = pk.to_der().unwrap();
+    assert_eq!(
+        &der[48..],
+        &hex!(
+            "A032"
+            "301306092A864886F70D0109153106040401020304"
+            "301B06092A864886F70D010914310E1E0C006D00790020006B00650079"
+        )[..]
+    );
+
+    let pk = PrivateKeyInfoRef::try_from(der.as_slice()).unwrap();
+    let attributes = pk.attributes.unwrap();
+    assert_eq!(attributes.friendly_name(), Ok(Some("my key".to_string())));
+    assert_eq!(
+        attributes.local_key_id(),
+        Ok(Some(OctetStringRef::new(&hex!("01020304")).unwrap()))
+    );
+}
+
+#[test]
+fn decode_attributes() {
+    use der::{Decode, ErrorKind};
+    use pkcs8::AttributesRef;
+
+    let attributes = AttributesRef::from_der(&hex!(
+        "3132"
+        "301306092A864886F70D0109153106040401020304"
+        "301B06092A864886F70D010914310E1E0C006D00790020006B00650079"
+    ))
+    .unwrap();
+    assert_eq!(attributes.iter().count(), 2);
+
+    // DER requires the `SET OF` elements to be sorted
+    let err = AttributesRef::from_der(&hex!(
+        "3132"
+        "301B06092A864886F70D010914310E1E0C006D00790020006B00650079"
+        "301306092A864886F70D0109153106040401020304"
+    ))
+    .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::SetOrdering);
+
+    // Attributes must have at least one value
+    assert!(AttributesRef::from_der(&hex!("310F 300D06092A864886F70D0109153100")).is_err());
+
+    // Elements must be `Attribute`s
+    assert!(AttributesRef::from_der(&hex!("3103 020101")).is_err());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn encode_ed25519_der_v2_no_attrs() {
+    let pk = PrivateKeyInfoRef::try_from(ED25519_DER_V2_NO_ATTRS_EXAMPLE).unwrap();
+    assert_eq!(pk.version(), Version::V2);
+    assert_eq!(ED25519_DER_V2_NO_ATTRS_EXAMPLE, pk.to_der().unwrap());
+
+    let pk_owned = PrivateKeyInfoOwned::try_from(ED25519_DER_V2_NO_ATTRS_EXAMPLE).unwrap();
+    assert_eq!(pk_owned.version(), Version::V2);
+    assert_eq!(ED25519_DER_V2_NO_ATTRS_EXAMPLE, pk_owned.to_der().unwrap());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn encode_ed25519_der_v2_from_parts() {
+    let parsed = PrivateKeyInfoRef::try_from(ED25519_DER_V2_NO_ATTRS_EXAMPLE).unwrap();
+    let mut pk = PrivateKeyInfoRef::new(parsed.algorithm, parsed.private_key);
+    assert_eq!(pk.version(), Version::V1);
+
+    pk.public_key = Some(
+        BitStringRef::from_bytes(&hex!(
+            "19BF44096984CDFE8541BAC167DC3B96C85086AA30B6B6CB0C5C38AD703166E1"
+        ))
+        .unwrap(),
+    );
+    assert_eq!(pk.version(), Version::V2);
+    assert_eq!(ED25519_DER_V2_NO_ATTRS_EXAMPLE, pk.to_der().unwrap());
+}
+
 #[test]
 #[cfg(feature = "alloc")]
 fn encode_rsa_2048_der() {
@@ -235,3 +368,72 @@ fn encode_x25519_pem() {
     let pk = PrivateKeyInfoRef::try_from(X25519_DER_EXAMPLE).unwrap();
     assert_eq!(X25519_PEM_EXAMPLE, pk.to_pem(LineEnding::LF).unwrap());
 }
+
+#[test]
+#[cfg(feature = "alloc")]
+fn convert_ed25519_v1_v2() {
+    let v2 = PrivateKeyInfoOwned::try_from(ED25519_DER_V2_NO_ATTRS_EXAMPLE).unwrap();
+    let v1 = v2.to_v1().unwrap();
+    assert_eq!(v1.version(), Version::V1);
+    assert_eq!(v1.to_der().unwrap(), ED25519_DER_V2_AS_V1_EXAMPLE);
+
+    let public_key = v2.public_key.as_ref().unwrap().raw_bytes();
+    assert_eq!(
+        v1.to_v2(public_key).unwrap().to_der().unwrap(),
+        ED25519_DER_V2_NO_ATTRS_EXAMPLE
+    );
+    assert_eq!(
+        v2.to_v2(public_key).unwrap().to_der().unwrap(),
+        ED25519_DER_V2_NO_ATTRS_EXAMPLE
+    );
+
+    // attributes are kept
+    let v2 = PrivateKeyInfoOwned::try_from(ED25519_DER_V2_EXAMPLE).unwrap();
+    let v1 = v2.to_v1().unwrap();
+    assert_eq!(v1.attributes, v2.attributes);
+    assert_eq!(
+        v1.to_v2(public_key).unwrap().to_der().unwrap(),
+        ED25519_DER_V2_EXAMPLE
+    );
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn convert_x25519_v1_v2() {
+    let v1 = PrivateKeyInfoOwned::try_from(X25519_DER_EXAMPLE).unwrap();
+    let v2 = v1
+        .to_v2(&hex!(
+            "6ADFBAB7BC9CB7DAB98C5B050DEBC64899BDF37408B120FC709A7306ADAC8F1C"
+        ))
+        .unwrap();
+    assert_eq!(v2.version(), Version::V2);
+    assert_eq!(v2.to_der().unwrap(), X25519_DER_V2_EXAMPLE);
+    assert_eq!(v2.to_v1().unwrap().to_der().unwrap(), X25519_DER_EXAMPLE);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn convert_v1_v2_errors() {
+    let v2 = PrivateKeyInfoOwned::try_from(ED25519_DER_V2_EXAMPLE).unwrap();
+    let public_key = v2.public_key.as_ref().unwrap().raw_bytes();
+
+    // wrong length
+    assert_eq!(
+        v2.to_v1().unwrap().to_v2(&public_key[1..]).err(),
+        Some(pkcs8::Error::KeyMalformed)
+    );
+
+    // different public key than the one of the key
+    let mut other = public_key.to_vec();
+    other[0] ^= 1;
+    assert_eq!(v2.to_v2(&other).err(), Some(pkcs8::Error::KeyMalformed));
+
+    // not an RFC 8410 key
+    let pk = PrivateKeyInfoOwned::try_from(EC_P256_DER_EXAMPLE).unwrap();
+    assert_eq!(
+        pk.to_v1().err(),
+        Some(pkcs8::Error::PublicKey(spki::Error::OidUnknown {
+            oid: pk.algorithm.oid
+        }))
+    );
+}
@@ -11,7 +11,7 @@ use pkcs8::der::pem::LineEnding;
 #[cfg(feature = "std")]
 use tempfile::tempdir;
 
-#[cfg(all(feature = "pem", feature = "std"))]
+#[cfg(feature = "std")]
 use std::fs;
 
 /// Ed25519 `PrivateKeyInfoRef` encoded as ASN.1 DER
@@ -88,6 +88,72 @@ fn write_pkcs8_der_file() {
     assert_eq!(key.as_ref(), ED25519_DER_EXAMPLE);
 }
 
+#[cfg(all(unix, feature = "std"))]
+#[test]
+fn write_pkcs8_der_file_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("example.der");
+    fs::write(&path, b"stale").unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+    MockKey(ED25519_DER_EXAMPLE.to_vec())
+        .write_pkcs8_der_file(&path)
+        .unwrap();
+
+    let mode = fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+    assert_eq!(fs::read(&path).unwrap(), ED25519_DER_EXAMPLE);
+
+    // The temporary file has been renamed over the destination
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
+#[cfg(all(unix, feature = "std"))]
+#[test]
+fn read_der_file_owner_only() {
+    use pkcs8::der::{ErrorKind, SecretFileOptions};
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("example.der");
+    let options = SecretFileOptions::new().sync(true).owner_only(true);
+
+    MockKey(ED25519_DER_EXAMPLE.to_vec())
+        .write_pkcs8_der_file_with_options(&path, options)
+        .unwrap();
+
+    let key = MockKey::read_pkcs8_der_file_with_options(&path, options).unwrap();
+    assert_eq!(key.as_ref(), ED25519_DER_EXAMPLE);
+
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+    let err = MockKey::read_pkcs8_der_file_with_options(&path, options).err();
+    assert!(matches!(err, Some(Error::Asn1(err)) if err.kind() == ErrorKind::PermissionDenied));
+
+    // Permissions are only checked when requested
+    assert!(MockKey::read_pkcs8_der_file(&path).is_ok());
+    assert!(SecretDocument::read_der_file(&path).is_ok());
+}
+
+#[cfg(all(unix, feature = "std"))]
+#[test]
+fn write_der_file_replaces_symlink() {
+    let dir = tempdir().unwrap();
+    let target = dir.path().join("target.der");
+    let path = dir.path().join("example.der");
+    fs::write(&target, b"unchanged").unwrap();
+    std::os::unix::fs::symlink(&target, &path).unwrap();
+
+    MockKey(ED25519_DER_EXAMPLE.to_vec())
+        .write_pkcs8_der_file(&path)
+        .unwrap();
+
+    assert!(!fs::symlink_metadata(&path).unwrap().is_symlink());
+    assert_eq!(fs::read(&path).unwrap(), ED25519_DER_EXAMPLE);
+    assert_eq!(fs::read(&target).unwrap(), b"unchanged");
+}
+
 #[cfg(all(feature = "pem", feature = "std"))]
 #[test]
 fn write_pkcs8_pem_file() {
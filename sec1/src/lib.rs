@@ -52,7 +52,14 @@ pub use crate::point::EncodedPoint;
 pub use hybrid_array::typenum::consts;
 
 #[cfg(feature = "der")]
-pub use crate::{parameters::EcParameters, private_key::EcPrivateKey, traits::DecodeEcPrivateKey};
+pub use crate::{
+    parameters::{
+        Curve, EcParameters, FieldId, SpecifiedEcDomain, CHARACTERISTIC_TWO_FIELD_OID,
+        PRIME_FIELD_OID,
+    },
+    private_key::EcPrivateKey,
+    traits::DecodeEcPrivateKey,
+};
 
 #[cfg(all(feature = "alloc", feature = "der"))]
 pub use crate::traits::EncodeEcPrivateKey;
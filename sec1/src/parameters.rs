@@ -1,8 +1,22 @@
+mod curves;
+
 use der::{
-    asn1::{AnyRef, ObjectIdentifier},
-    DecodeValue, EncodeValue, FixedTag, Header, Length, Reader, Tag, Writer,
+    asn1::{AnyRef, BitStringRef, ObjectIdentifier, OctetStringRef, UintRef},
+    Choice, Decode, DecodeValue, Encode, EncodeValue, Header, Length, Reader, Sequence, Tag,
+    Tagged, Writer,
 };
 
+/// `prime-field` [`FieldId`] type OID.
+///
+/// <http://oid-info.com/get/1.2.840.10045.1.1>
+pub const PRIME_FIELD_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.1.1");
+
+/// `characteristic-two-field` [`FieldId`] type OID.
+///
+/// <http://oid-info.com/get/1.2.840.10045.1.2>
+pub const CHARACTERISTIC_TWO_FIELD_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.10045.1.2");
+
 /// Elliptic curve parameters as described in
 /// [RFC5480 Section 2.1.1](https://datatracker.ietf.org/doc/html/rfc5480#section-2.1.1):
 ///
@@ -10,68 +24,360 @@ use der::{
 /// ECParameters ::= CHOICE {
 ///   namedCurve         OBJECT IDENTIFIER
 ///   -- implicitCurve   NULL
-///   -- specifiedCurve  SpecifiedECDomain
+///   specifiedCurve     SpecifiedECDomain
 /// }
 ///   -- implicitCurve and specifiedCurve MUST NOT be used in PKIX.
 ///   -- Details for SpecifiedECDomain can be found in [X9.62].
 ///   -- Any future additions to this CHOICE should be coordinated
 ///   -- with ANSI X9.
 /// ```
+///
+/// Explicit parameters are kept as they were encoded: use
+/// [`EcParameters::recognize_named_curve`] to convert them to the equivalent
+/// `namedCurve`.
+#[allow(clippy::large_enum_variant)]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
-pub enum EcParameters {
+pub enum EcParameters<'a> {
     /// Elliptic curve named by a particular OID.
     ///
     /// > namedCurve identifies all the required values for a particular
     /// > set of elliptic curve domain parameters to be represented by an
     /// > object identifier.
     NamedCurve(ObjectIdentifier),
+
+    /// Elliptic curve domain parameters specified explicitly.
+    SpecifiedCurve(SpecifiedEcDomain<'a>),
+}
+
+impl<'a> EcParameters<'a> {
+    /// Obtain the `namedCurve` OID.
+    ///
+    /// Returns `None` for explicit parameters, even if they describe a known
+    /// curve: see [`EcParameters::recognize_named_curve`].
+    pub fn named_curve(self) -> Option<ObjectIdentifier> {
+        match self {
+            Self::NamedCurve(oid) => Some(oid),
+            Self::SpecifiedCurve(_) => None,
+        }
+    }
+
+    /// Obtain the `specifiedCurve` domain parameters.
+    pub fn specified_curve(self) -> Option<SpecifiedEcDomain<'a>> {
+        match self {
+            Self::NamedCurve(_) => None,
+            Self::SpecifiedCurve(domain) => Some(domain),
+        }
+    }
+
+    /// Obtain the `namedCurve` OID, or the OID of the known curve matching
+    /// explicit parameters.
+    ///
+    /// See [`SpecifiedEcDomain::recognize_named_curve`].
+    pub fn recognize_named_curve(&self) -> Option<ObjectIdentifier> {
+        match self {
+            Self::NamedCurve(oid) => Some(*oid),
+            Self::SpecifiedCurve(domain) => domain.recognize_named_curve(),
+        }
+    }
+}
+
+impl<'a> Choice<'a> for EcParameters<'a> {
+    fn can_decode(tag: Tag) -> bool {
+        matches!(tag, Tag::ObjectIdentifier | Tag::Sequence)
+    }
+}
+
+impl<'a> Decode<'a> for EcParameters<'a> {
+    type Error = der::Error;
+
+    fn decode<R: Reader<'a>>(reader: &mut R) -> der::Result<Self> {
+        let header = Header::decode(reader)?;
+        Self::decode_value(reader, header)
+    }
 }
 
-impl<'a> DecodeValue<'a> for EcParameters {
+impl<'a> DecodeValue<'a> for EcParameters<'a> {
     type Error = der::Error;
 
     fn decode_value<R: Reader<'a>>(decoder: &mut R, header: Header) -> der::Result<Self> {
-        ObjectIdentifier::decode_value(decoder, header).map(Self::NamedCurve)
+        match header.tag {
+            Tag::ObjectIdentifier => {
+                ObjectIdentifier::decode_value(decoder, header).map(Self::NamedCurve)
+            }
+            Tag::Sequence => {
+                SpecifiedEcDomain::decode_value(decoder, header).map(Self::SpecifiedCurve)
+            }
+            tag => Err(tag.unexpected_error(None)),
+        }
     }
 }
 
-impl EncodeValue for EcParameters {
+impl EncodeValue for EcParameters<'_> {
     fn value_len(&self) -> der::Result<Length> {
         match self {
             Self::NamedCurve(oid) => oid.value_len(),
+            Self::SpecifiedCurve(domain) => domain.value_len(),
         }
     }
 
     fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
         match self {
             Self::NamedCurve(oid) => oid.encode_value(writer),
+            Self::SpecifiedCurve(domain) => domain.encode_value(writer),
         }
     }
 }
 
-impl EcParameters {
-    /// Obtain the `namedCurve` OID.
-    pub fn named_curve(self) -> Option<ObjectIdentifier> {
+impl Tagged for EcParameters<'_> {
+    fn tag(&self) -> Tag {
         match self {
-            Self::NamedCurve(oid) => Some(oid),
+            Self::NamedCurve(_) => Tag::ObjectIdentifier,
+            Self::SpecifiedCurve(_) => Tag::Sequence,
         }
     }
 }
 
-impl<'a> From<&'a EcParameters> for AnyRef<'a> {
-    fn from(params: &'a EcParameters) -> AnyRef<'a> {
-        match params {
-            EcParameters::NamedCurve(oid) => oid.into(),
+impl From<ObjectIdentifier> for EcParameters<'_> {
+    fn from(oid: ObjectIdentifier) -> Self {
+        EcParameters::NamedCurve(oid)
+    }
+}
+
+impl<'a> From<SpecifiedEcDomain<'a>> for EcParameters<'a> {
+    fn from(domain: SpecifiedEcDomain<'a>) -> Self {
+        EcParameters::SpecifiedCurve(domain)
+    }
+}
+
+/// Explicit elliptic curve domain parameters as described in
+/// [SEC1: Elliptic Curve Cryptography (Version 2.0)] Appendix C.2 (p.104):
+///
+/// ```text
+/// SpecifiedECDomain ::= SEQUENCE {
+///   version   SpecifiedECDomainVersion(ecdpVer1 | ecdpVer2 | ecdpVer3, ...),
+///   fieldID   FieldID {{FieldTypes}},
+///   curve     Curve,
+///   base      ECPoint,
+///   order     INTEGER,
+///   cofactor  INTEGER OPTIONAL,
+///   hash      HashAlgorithm OPTIONAL,
+///   ...
+/// }
+///
+/// SpecifiedECDomainVersion ::= INTEGER {
+///   ecdpVer1(1),
+///   ecdpVer2(2),
+///   ecdpVer3(3)
+/// }
+/// ```
+///
+/// [SEC1: Elliptic Curve Cryptography (Version 2.0)]: https://www.secg.org/sec1-v2.pdf
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct SpecifiedEcDomain<'a> {
+    /// Syntax version: 1, 2 or 3.
+    pub version: u8,
+
+    /// Underlying finite field.
+    pub field_id: FieldId<'a>,
+
+    /// Curve coefficients.
+    pub curve: Curve<'a>,
+
+    /// Base point, encoded as an `Elliptic-Curve-Point-to-Octet-String`.
+    pub base: &'a [u8],
+
+    /// Order of the base point.
+    pub order: UintRef<'a>,
+
+    /// Cofactor of the curve.
+    pub cofactor: Option<UintRef<'a>>,
+
+    /// Hash function used to generate the curve, as an `AlgorithmIdentifier`.
+    pub hash: Option<AnyRef<'a>>,
+}
+
+impl SpecifiedEcDomain<'_> {
+    /// Compare these parameters against the known curves, and return the
+    /// OID of the matching one.
+    ///
+    /// The prime, coefficients, base point, order and cofactor are compared,
+    /// the seed and hash used to generate the curve are ignored. Known
+    /// curves are NIST P-192, P-224, P-256, P-384 and P-521, and secp256k1.
+    pub fn recognize_named_curve(&self) -> Option<ObjectIdentifier> {
+        let prime = self.field_id.prime()?;
+
+        curves::KNOWN_CURVES
+            .iter()
+            .find(|curve| curve.matches(self, prime))
+            .map(|curve| curve.oid)
+    }
+}
+
+impl<'a> DecodeValue<'a> for SpecifiedEcDomain<'a> {
+    type Error = der::Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> der::Result<Self> {
+        reader.read_nested(header.length, |reader| {
+            let version = u8::decode(reader)?;
+
+            if !(1..=3).contains(&version) {
+                return Err(Tag::Integer.value_error());
+            }
+
+            Ok(Self {
+                version,
+                field_id: reader.decode()?,
+                curve: reader.decode()?,
+                base: OctetStringRef::decode(reader)?.as_bytes(),
+                order: reader.decode()?,
+                cofactor: reader.decode()?,
+                hash: reader.decode()?,
+            })
+        })
+    }
+}
+
+impl EncodeValue for SpecifiedEcDomain<'_> {
+    fn value_len(&self) -> der::Result<Length> {
+        self.version.encoded_len()?
+            + self.field_id.encoded_len()?
+            + self.curve.encoded_len()?
+            + OctetStringRef::new(self.base)?.encoded_len()?
+            + self.order.encoded_len()?
+            + self.cofactor.encoded_len()?
+            + self.hash.encoded_len()?
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
+        self.version.encode(writer)?;
+        self.field_id.encode(writer)?;
+        self.curve.encode(writer)?;
+        OctetStringRef::new(self.base)?.encode(writer)?;
+        self.order.encode(writer)?;
+        self.cofactor.encode(writer)?;
+        self.hash.encode(writer)?;
+        Ok(())
+    }
+}
+
+impl<'a> Sequence<'a> for SpecifiedEcDomain<'a> {}
+
+/// Finite field underlying an elliptic curve:
+///
+/// ```text
+/// FieldID { FIELD-ID:IOSet } ::= SEQUENCE {
+///   fieldType  FIELD-ID.&id({IOSet}),
+///   parameters FIELD-ID.&Type({IOSet}{@fieldType})
+/// }
+/// ```
+///
+/// The parameters of a [`PRIME_FIELD_OID`] field are the prime `p`, encoded
+/// as an `INTEGER`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct FieldId<'a> {
+    /// Field type, e.g. [`PRIME_FIELD_OID`] or [`CHARACTERISTIC_TWO_FIELD_OID`].
+    pub field_type: ObjectIdentifier,
+
+    /// Field parameters.
+    pub parameters: AnyRef<'a>,
+}
+
+impl<'a> FieldId<'a> {
+    /// Get the prime `p` of a prime field.
+    ///
+    /// Returns `None` for other field types.
+    pub fn prime(&self) -> Option<UintRef<'a>> {
+        if self.field_type == PRIME_FIELD_OID {
+            self.parameters.decode_as().ok()
+        } else {
+            None
         }
     }
 }
 
-impl From<ObjectIdentifier> for EcParameters {
-    fn from(oid: ObjectIdentifier) -> EcParameters {
-        EcParameters::NamedCurve(oid)
+impl<'a> DecodeValue<'a> for FieldId<'a> {
+    type Error = der::Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> der::Result<Self> {
+        reader.read_nested(header.length, |reader| {
+            let field_id = Self {
+                field_type: reader.decode()?,
+                parameters: reader.decode()?,
+            };
+
+            if field_id.field_type == PRIME_FIELD_OID {
+                field_id.parameters.decode_as::<UintRef<'_>>()?;
+            }
+
+            Ok(field_id)
+        })
     }
 }
 
-impl FixedTag for EcParameters {
-    const TAG: Tag = Tag::ObjectIdentifier;
+impl EncodeValue for FieldId<'_> {
+    fn value_len(&self) -> der::Result<Length> {
+        self.field_type.encoded_len()? + self.parameters.encoded_len()?
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
+        self.field_type.encode(writer)?;
+        self.parameters.encode(writer)?;
+        Ok(())
+    }
 }
+
+impl<'a> Sequence<'a> for FieldId<'a> {}
+
+/// Elliptic curve coefficients:
+///
+/// ```text
+/// Curve ::= SEQUENCE {
+///   a         FieldElement,
+///   b         FieldElement,
+///   seed      BIT STRING OPTIONAL
+/// }
+///
+/// FieldElement ::= OCTET STRING
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Curve<'a> {
+    /// Coefficient `a`, encoded as a `Field-Element-to-Octet-String`.
+    pub a: &'a [u8],
+
+    /// Coefficient `b`, encoded as a `Field-Element-to-Octet-String`.
+    pub b: &'a [u8],
+
+    /// Seed used to generate the curve.
+    pub seed: Option<BitStringRef<'a>>,
+}
+
+impl<'a> DecodeValue<'a> for Curve<'a> {
+    type Error = der::Error;
+
+    fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> der::Result<Self> {
+        reader.read_nested(header.length, |reader| {
+            Ok(Self {
+                a: OctetStringRef::decode(reader)?.as_bytes(),
+                b: OctetStringRef::decode(reader)?.as_bytes(),
+                seed: reader.decode()?,
+            })
+        })
+    }
+}
+
+impl EncodeValue for Curve<'_> {
+    fn value_len(&self) -> der::Result<Length> {
+        OctetStringRef::new(self.a)?.encoded_len()?
+            + OctetStringRef::new(self.b)?.encoded_len()?
+            + self.seed.encoded_len()?
+    }
+
+    fn encode_value(&self, writer: &mut impl Writer) -> der::Result<()> {
+        OctetStringRef::new(self.a)?.encode(writer)?;
+        OctetStringRef::new(self.b)?.encode(writer)?;
+        self.seed.encode(writer)?;
+        Ok(())
+    }
+}
+
+impl<'a> Sequence<'a> for Curve<'a> {}
@@ -16,9 +16,18 @@ use der::{
 #[cfg(all(feature = "alloc", feature = "zeroize"))]
 use der::SecretDocument;
 
+#[cfg(feature = "point")]
+use crate::{point::ModulusSize, EncodedPoint};
+
 #[cfg(feature = "pem")]
 use der::pem::PemLabel;
 
+#[cfg(feature = "pkcs8")]
+use {crate::ALGORITHM_OID, der::asn1::ObjectIdentifier};
+
+#[cfg(all(feature = "alloc", feature = "pkcs8"))]
+use der::zeroize::Zeroizing;
+
 /// `ECPrivateKey` version.
 ///
 /// From [RFC5913 Section 3]:
@@ -64,14 +73,123 @@ pub struct EcPrivateKey<'a> {
     pub private_key: &'a [u8],
 
     /// Elliptic curve parameters.
-    pub parameters: Option<EcParameters>,
+    pub parameters: Option<EcParameters<'a>>,
 
     /// Public key data, optionally available if version is V2.
     pub public_key: Option<&'a [u8]>,
 }
 
 impl<'a> EcPrivateKey<'a> {
-    fn context_specific_parameters(&self) -> Option<ContextSpecificRef<'_, EcParameters>> {
+    /// Parse the public key, if present, as an [`EncodedPoint`].
+    ///
+    /// Compressed, uncompressed and compact points are all accepted: use
+    /// [`EncodedPoint::coordinates`] to tell them apart. Decompressing a point
+    /// requires the curve arithmetic, which is provided by the curve
+    /// implementation crates (e.g. `p256`) rather than `sec1`.
+    #[cfg(feature = "point")]
+    pub fn public_key_point<Size: ModulusSize>(&self) -> Result<Option<EncodedPoint<Size>>> {
+        self.public_key.map(EncodedPoint::from_bytes).transpose()
+    }
+
+    /// Extract the SEC1 key from a PKCS#8 `id-ecPublicKey` private key.
+    ///
+    /// The curve is the `namedCurve` of the PKCS#8 algorithm identifier. The
+    /// `parameters` of the SEC1 key are usually omitted, and must describe
+    /// the same curve when present; the returned key has the `namedCurve` as
+    /// `parameters`.
+    ///
+    /// The public key is taken from the SEC1 key, or else from the `publicKey`
+    /// of a PKCS#8 v2 key, and must agree when both are present. Its encoding
+    /// is checked against the size of the private key, but checking that it is
+    /// the public key of the private key requires the curve arithmetic of the
+    /// curve implementation crates.
+    #[cfg(feature = "pkcs8")]
+    pub fn from_pkcs8(private_key_info: &pkcs8::PrivateKeyInfoRef<'a>) -> Result<Self> {
+        private_key_info
+            .algorithm
+            .assert_algorithm_oid(ALGORITHM_OID)?;
+        let named_curve = private_key_info.algorithm.parameters_oid()?;
+
+        let mut private_key = Self::from_der(private_key_info.private_key.as_bytes())?;
+        private_key.check_named_curve(named_curve)?;
+        private_key.parameters = Some(named_curve.into());
+
+        let outer_public_key = private_key_info
+            .public_key
+            .map(|public_key| public_key.as_bytes().ok_or(pkcs8::Error::KeyMalformed))
+            .transpose()?;
+
+        match (private_key.public_key, outer_public_key) {
+            (Some(inner), Some(outer)) if inner != outer => {
+                return Err(pkcs8::Error::KeyMalformed.into());
+            }
+            (None, outer) => private_key.public_key = outer,
+            _ => (),
+        }
+        private_key.check_public_key()?;
+
+        Ok(private_key)
+    }
+
+    /// Wrap this key in a PKCS#8 `id-ecPublicKey` private key on the curve
+    /// `named_curve`.
+    ///
+    /// The `parameters` of this key must describe the same curve when
+    /// present, and are omitted from the wrapped SEC1 key, as OpenSSL does.
+    #[cfg(all(feature = "alloc", feature = "pkcs8"))]
+    pub fn to_pkcs8(&self, named_curve: ObjectIdentifier) -> Result<SecretDocument> {
+        self.check_named_curve(named_curve)?;
+        self.check_public_key()?;
+
+        let private_key = Zeroizing::new(
+            Self {
+                parameters: None,
+                ..self.clone()
+            }
+            .to_der()?,
+        );
+
+        let private_key_info = pkcs8::PrivateKeyInfoRef::new(
+            pkcs8::AlgorithmIdentifierRef {
+                oid: ALGORITHM_OID,
+                parameters: Some((&named_curve).into()),
+            },
+            OctetStringRef::new(&private_key)?,
+        );
+
+        Ok(SecretDocument::encode_msg(&private_key_info)?)
+    }
+
+    /// Check that the `parameters`, if present, describe the curve
+    /// `named_curve`.
+    #[cfg(feature = "pkcs8")]
+    fn check_named_curve(&self, named_curve: ObjectIdentifier) -> Result<()> {
+        match &self.parameters {
+            Some(parameters) if parameters.recognize_named_curve() != Some(named_curve) => {
+                Err(pkcs8::Error::ParametersMalformed.into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that the public key, if present, is a compressed or uncompressed
+    /// point on a curve whose field elements have the size of the private key.
+    ///
+    /// Empty public keys are rejected as malformed.
+    #[cfg(feature = "pkcs8")]
+    fn check_public_key(&self) -> Result<()> {
+        let field_len = self.private_key.len();
+
+        match self.public_key.map(<[u8]>::split_first) {
+            None => Ok(()),
+            Some(None) => Err(pkcs8::Error::KeyMalformed.into()),
+            Some(Some((0x02 | 0x03, x))) if x.len() == field_len => Ok(()),
+            Some(Some((0x04, xy))) if xy.len() == 2 * field_len => Ok(()),
+            Some(Some(_)) => Err(Error::PointEncoding),
+        }
+    }
+
+    fn context_specific_parameters(&self) -> Option<ContextSpecificRef<'_, EcParameters<'a>>> {
         self.parameters.as_ref().map(|params| ContextSpecificRef {
             tag_number: EC_PARAMETERS_TAG,
             tag_mode: TagMode::Explicit,
@@ -104,7 +222,8 @@ impl<'a> DecodeValue<'a> for EcPrivateKey<'a> {
             }
 
             let private_key = OctetStringRef::decode(reader)?.as_bytes();
-            let parameters = reader.context_specific(EC_PARAMETERS_TAG, TagMode::Explicit)?;
+            let parameters = ContextSpecific::decode_explicit(reader, EC_PARAMETERS_TAG)?
+                .map(|field| field.value);
             let public_key = reader
                 .context_specific::<BitStringRef<'_>>(PUBLIC_KEY_TAG, TagMode::Explicit)?
                 .map(|bs| bs.as_bytes().ok_or_else(|| Tag::BitString.value_error()))
@@ -15,7 +15,7 @@ use {
 };
 
 #[cfg(feature = "std")]
-use std::path::Path;
+use {der::SecretFileOptions, std::path::Path};
 
 #[cfg(feature = "pem")]
 use zeroize::Zeroizing;
@@ -44,13 +44,34 @@ pub trait DecodeEcPrivateKey: Sized {
     /// filesystem (binary format).
     #[cfg(feature = "std")]
     fn read_sec1_der_file(path: impl AsRef<Path>) -> Result<Self> {
-        Self::from_sec1_der(SecretDocument::read_der_file(path)?.as_bytes())
+        Self::read_sec1_der_file_with_options(path, SecretFileOptions::default())
+    }
+
+    /// Load SEC1 private key from an ASN.1 DER-encoded file on the local
+    /// filesystem (binary format), using the given [`SecretFileOptions`].
+    #[cfg(feature = "std")]
+    fn read_sec1_der_file_with_options(
+        path: impl AsRef<Path>,
+        options: SecretFileOptions,
+    ) -> Result<Self> {
+        let doc = SecretDocument::read_der_file_with_options(path, options)?;
+        Self::from_sec1_der(doc.as_bytes())
     }
 
     /// Load SEC1 private key from a PEM-encoded file on the local filesystem.
     #[cfg(all(feature = "pem", feature = "std"))]
     fn read_sec1_pem_file(path: impl AsRef<Path>) -> Result<Self> {
-        let (label, doc) = SecretDocument::read_pem_file(path)?;
+        Self::read_sec1_pem_file_with_options(path, SecretFileOptions::default())
+    }
+
+    /// Load SEC1 private key from a PEM-encoded file on the local filesystem,
+    /// using the given [`SecretFileOptions`].
+    #[cfg(all(feature = "pem", feature = "std"))]
+    fn read_sec1_pem_file_with_options(
+        path: impl AsRef<Path>,
+        options: SecretFileOptions,
+    ) -> Result<Self> {
+        let (label, doc) = SecretDocument::read_pem_file_with_options(path, options)?;
         EcPrivateKey::validate_pem_label(&label)?;
         Self::from_sec1_der(doc.as_bytes())
     }
@@ -74,14 +95,39 @@ pub trait EncodeEcPrivateKey {
     /// Write ASN.1 DER-encoded SEC1 private key to the given path.
     #[cfg(feature = "std")]
     fn write_sec1_der_file(&self, path: impl AsRef<Path>) -> Result<()> {
-        Ok(self.to_sec1_der()?.write_der_file(path)?)
+        self.write_sec1_der_file_with_options(path, SecretFileOptions::default())
+    }
+
+    /// Write ASN.1 DER-encoded SEC1 private key to the given path, using the
+    /// given [`SecretFileOptions`].
+    #[cfg(feature = "std")]
+    fn write_sec1_der_file_with_options(
+        &self,
+        path: impl AsRef<Path>,
+        options: SecretFileOptions,
+    ) -> Result<()> {
+        Ok(self
+            .to_sec1_der()?
+            .write_der_file_with_options(path, options)?)
     }
 
     /// Write ASN.1 PEM-encoded SEC1 private key to the given path.
     #[cfg(all(feature = "pem", feature = "std"))]
     fn write_sec1_pem_file(&self, path: impl AsRef<Path>, line_ending: LineEnding) -> Result<()> {
+        self.write_sec1_pem_file_with_options(path, line_ending, SecretFileOptions::default())
+    }
+
+    /// Write ASN.1 PEM-encoded SEC1 private key to the given path, using the
+    /// given [`SecretFileOptions`].
+    #[cfg(all(feature = "pem", feature = "std"))]
+    fn write_sec1_pem_file_with_options(
+        &self,
+        path: impl AsRef<Path>,
+        line_ending: LineEnding,
+        options: SecretFileOptions,
+    ) -> Result<()> {
         let doc = self.to_sec1_der()?;
-        Ok(doc.write_pem_file(path, EcPrivateKey::PEM_LABEL, line_ending)?)
+        Ok(doc.write_pem_file_with_options(path, EcPrivateKey::PEM_LABEL, line_ending, options)?)
     }
 }
 
@@ -93,7 +139,7 @@ where
     fn from_sec1_der(private_key: &[u8]) -> Result<Self> {
         let params_oid = EcPrivateKey::from_der(private_key)?
             .parameters
-            .and_then(|params| params.named_curve());
+            .and_then(|params| params.recognize_named_curve());
 
         let algorithm = pkcs8::AlgorithmIdentifierRef {
             oid: ALGORITHM_OID,
@@ -105,6 +151,7 @@ where
         Ok(Self::try_from(pkcs8::PrivateKeyInfoRef {
             algorithm,
             private_key,
+            attributes: None,
             public_key: None,
         })?)
     }
@@ -2,19 +2,122 @@
 
 #![cfg(feature = "der")]
 
-use der::asn1::ObjectIdentifier;
+use der::asn1::{ObjectIdentifier, UintRef};
 use hex_literal::hex;
-use sec1::{EcParameters, EcPrivateKey};
+use sec1::{EcParameters, EcPrivateKey, PRIME_FIELD_OID};
 
 #[cfg(feature = "alloc")]
 use der::Encode;
 
+#[cfg(feature = "pkcs8")]
+use {
+    der::{asn1::OctetStringRef, Decode},
+    sec1::{pkcs8, Error},
+};
+
+#[cfg(all(feature = "alloc", feature = "pkcs8"))]
+use der::asn1::BitStringRef;
+
+#[cfg(feature = "point")]
+use sec1::{consts::U32, point::Coordinates};
+
 /// NIST P-256 SEC1 private key encoded as ASN.1 DER.
 ///
 /// Note: this key is extracted from the corresponding `p256-priv.der`
 /// example key in the `pkcs8` crate.
 const P256_DER_EXAMPLE: &[u8] = include_bytes!("examples/p256-priv.der");
 
+/// NIST P-256 SEC1 private key with a compressed public key encoded as ASN.1 DER.
+///
+/// Generated using:
+///
+/// ```
+/// $ openssl ec -inform der -in p256-priv.der -conv_form compressed -outform der -out p256-priv-compressed.der
+/// ```
+const P256_COMPRESSED_DER_EXAMPLE: &[u8] = include_bytes!("examples/p256-priv-compressed.der");
+
+/// NIST P-256 SEC1 private key with explicit curve parameters encoded as ASN.1 DER.
+///
+/// Generated using:
+///
+/// ```
+/// $ openssl ec -inform der -in p256-priv.der -param_enc explicit -outform der -out p256-priv-explicit.der
+/// ```
+const P256_EXPLICIT_DER_EXAMPLE: &[u8] = include_bytes!("examples/p256-priv-explicit.der");
+
+/// NIST P-256 SEC1 private key with explicit curve parameters and compressed
+/// points encoded as ASN.1 DER.
+///
+/// Generated using:
+///
+/// ```
+/// $ openssl ec -inform der -in p256-priv.der -param_enc explicit -conv_form compressed -outform der -out p256-priv-explicit-compressed.der
+/// ```
+const P256_EXPLICIT_COMPRESSED_DER_EXAMPLE: &[u8] =
+    include_bytes!("examples/p256-priv-explicit-compressed.der");
+
+/// NIST P-256 PKCS#8 private key encoded as ASN.1 DER.
+///
+/// Generated using:
+///
+/// ```
+/// $ openssl pkcs8 -topk8 -nocrypt -inform der -in p256-priv.der -outform der -out p256-priv-pkcs8.der
+/// ```
+#[cfg(all(feature = "alloc", feature = "pkcs8"))]
+const P256_PKCS8_DER_EXAMPLE: &[u8] = include_bytes!("examples/p256-priv-pkcs8.der");
+
+/// NIST P-384 SEC1 private key encoded as ASN.1 DER.
+///
+/// Generated using:
+///
+/// ```
+/// $ openssl ecparam -name secp384r1 -genkey -noout -outform der -out p384-priv.der
+/// ```
+#[cfg(all(feature = "alloc", feature = "pkcs8"))]
+const P384_DER_EXAMPLE: &[u8] = include_bytes!("examples/p384-priv.der");
+
+/// NIST P-384 PKCS#8 private key encoded as ASN.1 DER.
+///
+/// Generated using:
+///
+/// ```
+/// $ openssl pkcs8 -topk8 -nocrypt -inform der -in p384-priv.der -outform der -out p384-priv-pkcs8.der
+/// ```
+#[cfg(feature = "pkcs8")]
+const P384_PKCS8_DER_EXAMPLE: &[u8] = include_bytes!("examples/p384-priv-pkcs8.der");
+
+/// NIST P-521 SEC1 private key encoded as ASN.1 DER.
+///
+/// Generated using:
+///
+/// ```
+/// $ openssl ec -in p521-priv-pkcs8.pem -outform der -out p521-priv.der
+/// ```
+#[cfg(all(feature = "alloc", feature = "pkcs8"))]
+const P521_DER_EXAMPLE: &[u8] = include_bytes!("examples/p521-priv.der");
+
+/// NIST P-521 PKCS#8 private key encoded as ASN.1 DER.
+///
+/// Generated using:
+///
+/// ```
+/// $ openssl genpkey -algorithm EC -pkeyopt ec_paramgen_curve:P-521 -out p521-priv-pkcs8.pem
+/// $ openssl base64 -d -in p521-priv-pkcs8.pem -out p521-priv-pkcs8.der
+/// ```
+#[cfg(all(feature = "alloc", feature = "pkcs8"))]
+const P521_PKCS8_DER_EXAMPLE: &[u8] = include_bytes!("examples/p521-priv-pkcs8.der");
+
+/// NIST P-256 `namedCurve` OID.
+const P256_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.3.1.7");
+
+/// NIST P-384 `namedCurve` OID.
+#[cfg(all(feature = "alloc", feature = "pkcs8"))]
+const P384_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.132.0.34");
+
+/// NIST P-521 `namedCurve` OID.
+#[cfg(all(feature = "alloc", feature = "pkcs8"))]
+const P521_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.132.0.35");
+
 #[test]
 fn decode_p256_der() {
     let key = EcPrivateKey::try_from(P256_DER_EXAMPLE).unwrap();
@@ -41,3 +144,287 @@ fn encode_p256_der() {
     let key_encoded = key.to_der().unwrap();
     assert_eq!(P256_DER_EXAMPLE, key_encoded);
 }
+
+#[test]
+fn decode_p256_compressed_der() {
+    let key = EcPrivateKey::try_from(P256_COMPRESSED_DER_EXAMPLE).unwrap();
+    let uncompressed_key = EcPrivateKey::try_from(P256_DER_EXAMPLE).unwrap();
+
+    assert_eq!(key.private_key, uncompressed_key.private_key);
+    assert_eq!(key.parameters, uncompressed_key.parameters);
+    assert_eq!(
+        key.public_key,
+        Some(hex!("031CACFFB55F2F2CEFD89D89EB374B2681152452802DEEA09916068137D839CF7F").as_ref())
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn encode_p256_compressed_der() {
+    let key = EcPrivateKey::try_from(P256_COMPRESSED_DER_EXAMPLE).unwrap();
+    assert_eq!(key.to_der().unwrap(), P256_COMPRESSED_DER_EXAMPLE);
+}
+
+#[cfg(feature = "point")]
+#[test]
+fn p256_public_key_point() {
+    let key = EcPrivateKey::try_from(P256_COMPRESSED_DER_EXAMPLE).unwrap();
+    let point = key.public_key_point::<U32>().unwrap().unwrap();
+    assert!(matches!(
+        point.coordinates(),
+        Coordinates::Compressed { .. }
+    ));
+
+    let uncompressed_key = EcPrivateKey::try_from(P256_DER_EXAMPLE).unwrap();
+    let uncompressed_point = uncompressed_key.public_key_point::<U32>().unwrap().unwrap();
+    assert!(!uncompressed_point.is_compressed());
+    assert_eq!(uncompressed_point.compress(), point);
+}
+
+#[cfg(all(feature = "alloc", feature = "point"))]
+#[test]
+fn encode_p256_der_with_compressed_point() {
+    let key = EcPrivateKey::try_from(P256_DER_EXAMPLE).unwrap();
+    let point = key.public_key_point::<U32>().unwrap().unwrap().compress();
+
+    let key = EcPrivateKey {
+        public_key: Some(point.as_bytes()),
+        ..key
+    };
+    assert_eq!(key.to_der().unwrap(), P256_COMPRESSED_DER_EXAMPLE);
+}
+
+#[cfg(feature = "point")]
+#[test]
+fn p256_public_key_point_invalid() {
+    let key = EcPrivateKey::try_from(P256_DER_EXAMPLE).unwrap();
+    let key = EcPrivateKey {
+        public_key: Some(&hex!("05")),
+        ..key
+    };
+    assert!(key.public_key_point::<U32>().is_err());
+}
+
+#[test]
+fn decode_p256_explicit_der() {
+    let key = EcPrivateKey::try_from(P256_EXPLICIT_DER_EXAMPLE).unwrap();
+    let named_key = EcPrivateKey::try_from(P256_DER_EXAMPLE).unwrap();
+    assert_eq!(key.private_key, named_key.private_key);
+    assert_eq!(key.public_key, named_key.public_key);
+
+    let params = key.parameters.unwrap();
+    assert_eq!(params.named_curve(), None);
+
+    let domain = params.specified_curve().unwrap();
+    assert_eq!(domain.version, 1);
+    assert_eq!(domain.field_id.field_type, PRIME_FIELD_OID);
+    assert_eq!(
+        domain.field_id.prime().unwrap().as_bytes(),
+        hex!("FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFF")
+    );
+    assert_eq!(
+        domain.curve.a,
+        hex!("FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFC")
+    );
+    assert_eq!(
+        domain.curve.b,
+        hex!("5AC635D8AA3A93E7B3EBBD55769886BC651D06B0CC53B0F63BCE3C3E27D2604B")
+    );
+    assert_eq!(
+        domain.curve.seed.unwrap().raw_bytes(),
+        hex!("C49D360886E704936A6678E1139D26B7819F7E90")
+    );
+    assert_eq!(domain.base, hex!("046B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C2964FE342E2FE1A7F9B8EE7EB4A7C0F9E162BCE33576B315ECECBB6406837BF51F5"));
+    assert_eq!(
+        domain.order.as_bytes(),
+        hex!("FFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551")
+    );
+    assert_eq!(domain.cofactor.unwrap().as_bytes(), [1]);
+    assert_eq!(domain.hash, None);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn encode_p256_explicit_der() {
+    for example in [
+        P256_EXPLICIT_DER_EXAMPLE,
+        P256_EXPLICIT_COMPRESSED_DER_EXAMPLE,
+    ] {
+        let key = EcPrivateKey::try_from(example).unwrap();
+        assert_eq!(key.to_der().unwrap(), example);
+    }
+}
+
+#[test]
+fn recognize_p256_explicit() {
+    for example in [
+        P256_EXPLICIT_DER_EXAMPLE,
+        P256_EXPLICIT_COMPRESSED_DER_EXAMPLE,
+    ] {
+        let params = EcPrivateKey::try_from(example).unwrap().parameters.unwrap();
+        assert_eq!(params.recognize_named_curve(), Some(P256_OID));
+    }
+
+    let params = EcPrivateKey::try_from(P256_DER_EXAMPLE)
+        .unwrap()
+        .parameters
+        .unwrap();
+    assert_eq!(params.recognize_named_curve(), Some(P256_OID));
+}
+
+#[test]
+fn recognize_unknown_explicit() {
+    let key = EcPrivateKey::try_from(P256_EXPLICIT_DER_EXAMPLE).unwrap();
+    let domain = key.parameters.unwrap().specified_curve().unwrap();
+
+    let mut b = [0u8; 32];
+    b.copy_from_slice(domain.curve.b);
+    b[31] ^= 1;
+
+    let mut other = domain;
+    other.curve.b = &b;
+    assert_eq!(other.recognize_named_curve(), None);
+
+    let mut other = domain;
+    other.cofactor = Some(UintRef::new(&[4]).unwrap());
+    assert_eq!(other.recognize_named_curve(), None);
+
+    let mut compressed_base = [0u8; 33];
+    compressed_base.copy_from_slice(&domain.base[..33]);
+    compressed_base[0] = 0x03;
+
+    let mut other = domain;
+    other.base = &compressed_base;
+    assert_eq!(other.recognize_named_curve(), Some(P256_OID));
+
+    let mut wrong_parity_base = compressed_base;
+    wrong_parity_base[0] = 0x02;
+
+    let mut other = domain;
+    other.base = &wrong_parity_base;
+    assert_eq!(other.recognize_named_curve(), None);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn convert_p256_explicit_to_named_curve() {
+    let key = EcPrivateKey::try_from(P256_EXPLICIT_DER_EXAMPLE).unwrap();
+    let named_curve = key.parameters.unwrap().recognize_named_curve().unwrap();
+
+    let key = EcPrivateKey {
+        parameters: Some(EcParameters::NamedCurve(named_curve)),
+        ..key
+    };
+    assert_eq!(key.to_der().unwrap(), P256_DER_EXAMPLE);
+}
+
+#[test]
+fn decode_explicit_der_invalid_version() {
+    let mut der = P256_EXPLICIT_DER_EXAMPLE.to_vec();
+    // `version` of the `SpecifiedECDomain`
+    assert_eq!(der[47..50], [0x02, 0x01, 0x01]);
+    der[49] = 4;
+    assert!(EcPrivateKey::try_from(der.as_slice()).is_err());
+}
+
+#[cfg(all(feature = "alloc", feature = "pkcs8"))]
+#[test]
+fn from_pkcs8() {
+    for (pkcs8_der, sec1_der) in [
+        (P256_PKCS8_DER_EXAMPLE, P256_DER_EXAMPLE),
+        (P384_PKCS8_DER_EXAMPLE, P384_DER_EXAMPLE),
+        (P521_PKCS8_DER_EXAMPLE, P521_DER_EXAMPLE),
+    ] {
+        let private_key_info = pkcs8::PrivateKeyInfoRef::from_der(pkcs8_der).unwrap();
+        let key = EcPrivateKey::from_pkcs8(&private_key_info).unwrap();
+        assert_eq!(key.to_der().unwrap(), sec1_der);
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "pkcs8"))]
+#[test]
+fn to_pkcs8() {
+    for (sec1_der, named_curve, pkcs8_der) in [
+        (P256_DER_EXAMPLE, P256_OID, P256_PKCS8_DER_EXAMPLE),
+        (P256_EXPLICIT_DER_EXAMPLE, P256_OID, P256_PKCS8_DER_EXAMPLE),
+        (P384_DER_EXAMPLE, P384_OID, P384_PKCS8_DER_EXAMPLE),
+        (P521_DER_EXAMPLE, P521_OID, P521_PKCS8_DER_EXAMPLE),
+    ] {
+        let key = EcPrivateKey::try_from(sec1_der).unwrap();
+        assert_eq!(key.to_pkcs8(named_curve).unwrap().as_bytes(), pkcs8_der);
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "pkcs8"))]
+#[test]
+fn from_pkcs8_v2_public_key() {
+    let mut private_key_info = pkcs8::PrivateKeyInfoRef::from_der(P256_PKCS8_DER_EXAMPLE).unwrap();
+    let key = EcPrivateKey::from_der(private_key_info.private_key.as_bytes()).unwrap();
+    let public_key = key.public_key.unwrap();
+    private_key_info.public_key = Some(BitStringRef::from_bytes(public_key).unwrap());
+    assert!(EcPrivateKey::from_pkcs8(&private_key_info).is_ok());
+
+    // without a public key in the SEC1 key, the PKCS#8 one is used
+    let inner = EcPrivateKey {
+        public_key: None,
+        ..key.clone()
+    }
+    .to_der()
+    .unwrap();
+    private_key_info.private_key = OctetStringRef::new(&inner).unwrap();
+    let from_v2 = EcPrivateKey::from_pkcs8(&private_key_info).unwrap();
+    assert_eq!(from_v2.public_key, Some(public_key));
+
+    // empty public keys
+    private_key_info.public_key = Some(BitStringRef::from_bytes(&[]).unwrap());
+    assert_eq!(
+        EcPrivateKey::from_pkcs8(&private_key_info).err(),
+        Some(Error::Pkcs8(pkcs8::Error::KeyMalformed))
+    );
+    private_key_info.public_key = Some(BitStringRef::from_bytes(public_key).unwrap());
+
+    // mismatched public keys
+    let mut other = public_key.to_vec();
+    other[1] ^= 1;
+    let inner = EcPrivateKey {
+        public_key: Some(&other),
+        ..key
+    }
+    .to_der()
+    .unwrap();
+    private_key_info.private_key = OctetStringRef::new(&inner).unwrap();
+    assert_eq!(
+        EcPrivateKey::from_pkcs8(&private_key_info).err(),
+        Some(Error::Pkcs8(pkcs8::Error::KeyMalformed))
+    );
+}
+
+#[cfg(feature = "pkcs8")]
+#[test]
+fn from_pkcs8_curve_mismatch() {
+    // P-256 SEC1 key, with its `parameters`, wrapped as a P-384 key
+    let mut private_key_info = pkcs8::PrivateKeyInfoRef::from_der(P384_PKCS8_DER_EXAMPLE).unwrap();
+    private_key_info.private_key = OctetStringRef::new(P256_DER_EXAMPLE).unwrap();
+    assert_eq!(
+        EcPrivateKey::from_pkcs8(&private_key_info).err(),
+        Some(Error::Pkcs8(pkcs8::Error::ParametersMalformed))
+    );
+}
+
+#[cfg(all(feature = "alloc", feature = "pkcs8"))]
+#[test]
+fn to_pkcs8_errors() {
+    let key = EcPrivateKey::try_from(P256_DER_EXAMPLE).unwrap();
+    assert_eq!(
+        key.to_pkcs8(P384_OID).err(),
+        Some(Error::Pkcs8(pkcs8::Error::ParametersMalformed))
+    );
+
+    // public key of a P-384 key
+    let p384_key = EcPrivateKey::try_from(P384_DER_EXAMPLE).unwrap();
+    let key = EcPrivateKey {
+        public_key: p384_key.public_key,
+        ..key
+    };
+    assert_eq!(key.to_pkcs8(P256_OID).err(), Some(Error::PointEncoding));
+}
@@ -98,3 +98,17 @@ fn write_sec1_pem_file() {
     let pem = fs::read_to_string(path).unwrap();
     assert_eq!(&pem, P256_PEM_EXAMPLE);
 }
+
+#[cfg(all(feature = "pem", feature = "std"))]
+#[test]
+fn write_sec1_pem_file_with_options() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("example.pem");
+    let options = der::SecretFileOptions::new().sync(true).owner_only(true);
+    MockPrivateKey(P256_DER_EXAMPLE.to_vec())
+        .write_sec1_pem_file_with_options(&path, LineEnding::LF, options)
+        .unwrap();
+
+    let key = MockPrivateKey::read_sec1_pem_file_with_options(&path, options).unwrap();
+    assert_eq!(key.as_ref(), P256_DER_EXAMPLE);
+}
@@ -3,7 +3,7 @@
 use crate::{Error, Result};
 use core::cmp::Ordering;
 use der::{
-    asn1::{AnyRef, Choice, ObjectIdentifier},
+    asn1::{AnyRef, Choice, Null, ObjectIdentifier},
     Decode, DecodeValue, DerOrd, Encode, EncodeValue, Header, Length, Reader, Sequence, ValueOrd,
     Writer,
 };
@@ -112,6 +112,24 @@ impl<Params> AlgorithmIdentifier<Params> {
 }
 
 impl<'a> AlgorithmIdentifierRef<'a> {
+    /// Create an [`AlgorithmIdentifierRef`] with `NULL` parameters, as used
+    /// by e.g. `rsaEncryption`.
+    pub const fn new_null(oid: ObjectIdentifier) -> Self {
+        Self {
+            oid,
+            parameters: Some(AnyRef::NULL),
+        }
+    }
+
+    /// Create an [`AlgorithmIdentifierRef`] whose parameters are an OID, as
+    /// used by e.g. `id-ecPublicKey`.
+    pub fn new_with_oid_param(oid: ObjectIdentifier, parameters: &'a ObjectIdentifier) -> Self {
+        Self {
+            oid,
+            parameters: Some(parameters.into()),
+        }
+    }
+
     /// Assert `parameters` is an OID and has the expected value.
     pub fn assert_parameters_oid(
         &self,
@@ -151,6 +169,30 @@ impl<'a> AlgorithmIdentifierRef<'a> {
         Ok(ObjectIdentifier::try_from(self.parameters_any()?)?)
     }
 
+    /// Are the `parameters` either `NULL` or absent?
+    ///
+    /// Some encoders omit the `NULL` parameters which other ones include, e.g.
+    /// for RSA algorithms.
+    pub fn has_null_or_absent_parameters(&self) -> bool {
+        matches!(self.parameters, None | Some(AnyRef::NULL))
+    }
+
+    /// Assert `parameters` are present and `NULL`.
+    pub fn assert_parameters_null(&self) -> Result<()> {
+        self.parameters_any()?.decode_as::<Null>()?;
+        Ok(())
+    }
+
+    /// Compare to another [`AlgorithmIdentifierRef`], treating `NULL` and
+    /// absent `parameters` as equivalent.
+    pub fn eq_treating_null_as_absent(&self, other: &Self) -> bool {
+        self.oid == other.oid
+            && match (self.parameters, other.parameters) {
+                (None | Some(AnyRef::NULL), None | Some(AnyRef::NULL)) => true,
+                (params, other_params) => params == other_params,
+            }
+    }
+
     /// Convert to a pair of [`ObjectIdentifier`]s.
     ///
     /// This method is helpful for decomposing in match statements. Note in
@@ -196,4 +238,55 @@ mod allocating {
             }
         }
     }
+
+    impl AlgorithmIdentifierOwned {
+        /// Create an [`AlgorithmIdentifierOwned`] with `NULL` parameters, as
+        /// used by e.g. `rsaEncryption`.
+        pub fn new_null(oid: ObjectIdentifier) -> Self {
+            Self {
+                oid,
+                parameters: Some(Any::null()),
+            }
+        }
+
+        /// Create an [`AlgorithmIdentifierOwned`] whose parameters are an
+        /// OID, as used by e.g. `id-ecPublicKey`.
+        pub fn new_with_oid_param(oid: ObjectIdentifier, parameters: ObjectIdentifier) -> Self {
+            Self {
+                oid,
+                parameters: Some(parameters.into()),
+            }
+        }
+
+        /// Get the `parameters` field as an [`AnyRef`].
+        ///
+        /// Returns an error if `parameters` are `None`.
+        pub fn parameters_any(&self) -> Result<AnyRef<'_>> {
+            self.owned_to_ref().parameters_any()
+        }
+
+        /// Get the `parameters` field as an [`ObjectIdentifier`].
+        ///
+        /// Returns an error if it is absent or not an OID.
+        pub fn parameters_oid(&self) -> Result<ObjectIdentifier> {
+            self.owned_to_ref().parameters_oid()
+        }
+
+        /// Are the `parameters` either `NULL` or absent?
+        pub fn has_null_or_absent_parameters(&self) -> bool {
+            self.owned_to_ref().has_null_or_absent_parameters()
+        }
+
+        /// Assert `parameters` are present and `NULL`.
+        pub fn assert_parameters_null(&self) -> Result<()> {
+            self.owned_to_ref().assert_parameters_null()
+        }
+
+        /// Compare to another [`AlgorithmIdentifierOwned`], treating `NULL`
+        /// and absent `parameters` as equivalent.
+        pub fn eq_treating_null_as_absent(&self, other: &Self) -> bool {
+            self.owned_to_ref()
+                .eq_treating_null_as_absent(&other.owned_to_ref())
+        }
+    }
 }
@@ -7,7 +7,7 @@ use der::asn1::ObjectIdentifier;
 pub type Result<T> = core::result::Result<T, Error>;
 
 #[cfg(feature = "pem")]
-use der::pem;
+use {alloc::string::String, der::pem};
 
 /// Error type
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -66,3 +66,47 @@ impl From<pem::Error> for Error {
 
 #[cfg(feature = "std")]
 impl std::error::Error for Error {}
+
+/// Error type of [`SubjectPublicKeyInfoOwned::from_pem_any`][`crate::SubjectPublicKeyInfoOwned::from_pem_any`].
+#[cfg(feature = "pem")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum AnyPemError {
+    /// The PEM type label is not one of a public key.
+    LabelUnknown {
+        /// Type label of the PEM document.
+        label: String,
+    },
+
+    /// Malformed PEM document or public key.
+    Spki(Error),
+}
+
+#[cfg(feature = "pem")]
+impl fmt::Display for AnyPemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnyPemError::LabelUnknown { label } => {
+                write!(f, "unknown/unsupported public key PEM label: {}", label)
+            }
+            AnyPemError::Spki(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+#[cfg(feature = "pem")]
+impl From<Error> for AnyPemError {
+    fn from(err: Error) -> AnyPemError {
+        AnyPemError::Spki(err)
+    }
+}
+
+#[cfg(feature = "pem")]
+impl From<der::Error> for AnyPemError {
+    fn from(err: der::Error) -> AnyPemError {
+        Error::from(err).into()
+    }
+}
+
+#[cfg(all(feature = "pem", feature = "std"))]
+impl std::error::Error for AnyPemError {}
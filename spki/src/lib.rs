@@ -69,3 +69,6 @@ pub use {
 
 #[cfg(feature = "fingerprint")]
 pub use crate::fingerprint::FingerprintBytes;
+
+#[cfg(feature = "pem")]
+pub use crate::{error::AnyPemError, spki::PublicKeyPemForm};
@@ -18,7 +18,23 @@ use der::{
 use crate::{fingerprint, FingerprintBytes};
 
 #[cfg(feature = "pem")]
-use der::pem::PemLabel;
+use {
+    crate::AnyPemError,
+    alloc::string::ToString,
+    der::{
+        asn1::{ObjectIdentifier, UintRef},
+        pem::PemLabel,
+        SliceReader,
+    },
+};
+
+/// `rsaEncryption` algorithm OID, of the SPKI of a PKCS#1 RSA public key.
+#[cfg(feature = "pem")]
+const RSA_ENCRYPTION_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.1");
+
+/// Type label of a PEM-encoded PKCS#1 RSA public key.
+#[cfg(feature = "pem")]
+const RSA_PUBLIC_KEY_PEM_LABEL: &str = "RSA PUBLIC KEY";
 
 /// [`SubjectPublicKeyInfo`] with [`AnyRef`] algorithm parameters, and [`BitStringRef`] params.
 ///
@@ -84,6 +100,30 @@ where
         self.encode(&mut builder)?;
         Ok(builder.finish())
     }
+
+    /// Compare the SHA-256 fingerprint of this [`SubjectPublicKeyInfo`] to an
+    /// expected pin, in constant time.
+    #[cfg(feature = "fingerprint")]
+    pub fn matches_fingerprint(&self, expected: &FingerprintBytes) -> Result<bool> {
+        use subtle::ConstantTimeEq;
+        Ok(self.fingerprint_bytes()?.ct_eq(expected).into())
+    }
+
+    /// Compare the SHA-256 fingerprint of this [`SubjectPublicKeyInfo`] to an
+    /// expected pin encoded as a Base64 string, in constant time.
+    ///
+    /// Returns `false` if `pin` is not a Base64-encoded SHA-256 fingerprint.
+    #[cfg(all(feature = "fingerprint", feature = "base64"))]
+    pub fn matches_fingerprint_base64(&self, pin: &str) -> Result<bool> {
+        use base64ct::{Base64, Encoding};
+
+        let mut expected = FingerprintBytes::default();
+
+        match Base64::decode(pin, &mut expected).map(|decoded| decoded.len()) {
+            Ok(len) if len == expected.len() => self.matches_fingerprint(&expected),
+            _ => Ok(false),
+        }
+    }
 }
 
 impl<'a: 'k, 'k, Params, Key: 'k> DecodeValue<'a> for SubjectPublicKeyInfo<Params, Key>
@@ -184,6 +224,19 @@ impl<Params, Key> PemLabel for SubjectPublicKeyInfo<Params, Key> {
     const PEM_LABEL: &'static str = "PUBLIC KEY";
 }
 
+/// Form of a PEM-encoded public key, as detected by
+/// [`SubjectPublicKeyInfoOwned::from_pem_any`].
+#[cfg(feature = "pem")]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum PublicKeyPemForm {
+    /// `PUBLIC KEY`: a `SubjectPublicKeyInfo`.
+    Spki,
+
+    /// `RSA PUBLIC KEY`: a PKCS#1 `RSAPublicKey`.
+    Pkcs1Rsa,
+}
+
 #[cfg(feature = "alloc")]
 mod allocating {
     use super::*;
@@ -219,5 +272,55 @@ mod allocating {
         {
             Ok(source.to_public_key_der()?.decode_msg::<Self>()?)
         }
+
+        /// Decode a PEM-encoded public key, accepting the PKCS#1 `RSA PUBLIC KEY`
+        /// label in addition to the `PUBLIC KEY` label of a SPKI.
+        ///
+        /// A PKCS#1 RSA public key is wrapped in an `rsaEncryption` SPKI, the
+        /// same way as `pkcs1::EncodePublicKey` does, and the detected form is
+        /// returned along with the key.
+        #[cfg(feature = "pem")]
+        pub fn from_pem_any(
+            s: &str,
+        ) -> core::result::Result<(Self, PublicKeyPemForm), AnyPemError> {
+            let (label, doc) = Document::from_pem(s)?;
+
+            match label {
+                Self::PEM_LABEL => Ok((doc.decode_msg::<Self>()?, PublicKeyPemForm::Spki)),
+                RSA_PUBLIC_KEY_PEM_LABEL => {
+                    check_rsa_public_key(doc.as_bytes())?;
+
+                    let spki = SubjectPublicKeyInfo {
+                        algorithm: AlgorithmIdentifier {
+                            oid: RSA_ENCRYPTION_OID,
+                            parameters: Some(AnyRef::NULL.into()),
+                        },
+                        subject_public_key: BitString::from_bytes(doc.as_bytes())?,
+                    };
+                    Ok((spki, PublicKeyPemForm::Pkcs1Rsa))
+                }
+                _ => Err(AnyPemError::LabelUnknown {
+                    label: label.to_string(),
+                }),
+            }
+        }
+    }
+
+    /// Check that `der` is a PKCS#1 `RSAPublicKey`:
+    ///
+    /// ```text
+    /// RSAPublicKey ::= SEQUENCE {
+    ///     modulus           INTEGER,  -- n
+    ///     publicExponent    INTEGER   -- e
+    /// }
+    /// ```
+    #[cfg(feature = "pem")]
+    fn check_rsa_public_key(der: &[u8]) -> Result<()> {
+        let mut reader = SliceReader::new(der)?;
+        reader.sequence(|reader| {
+            UintRef::decode(reader)?;
+            UintRef::decode(reader)
+        })?;
+        Ok(reader.finish(())?)
     }
 }
@@ -0,0 +1,111 @@
+//! `AlgorithmIdentifier` tests.
+
+use der::{
+    asn1::{AnyRef, ObjectIdentifier},
+    Decode,
+};
+use spki::{AlgorithmIdentifierRef, Error, SubjectPublicKeyInfoRef};
+
+#[cfg(feature = "alloc")]
+use {
+    der::{referenced::RefToOwned, Encode},
+    spki::AlgorithmIdentifierOwned,
+};
+
+/// Elliptic Curve (P-256) `SubjectPublicKeyInfo` encoded as ASN.1 DER
+const EC_P256_DER_EXAMPLE: &[u8] = include_bytes!("examples/p256-pub.der");
+
+/// Ed25519 `SubjectPublicKeyInfo` encoded as ASN.1 DER
+const ED25519_DER_EXAMPLE: &[u8] = include_bytes!("examples/ed25519-pub.der");
+
+/// RSA-2048 `SubjectPublicKeyInfo` encoded as ASN.1 DER
+const RSA_2048_DER_EXAMPLE: &[u8] = include_bytes!("examples/rsa2048-pub.der");
+
+const EC_PUBLIC_KEY_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
+const P256_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.3.1.7");
+const RSA_ENCRYPTION_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.1");
+
+fn algorithm(spki_der: &[u8]) -> AlgorithmIdentifierRef<'_> {
+    SubjectPublicKeyInfoRef::from_der(spki_der)
+        .unwrap()
+        .algorithm
+}
+
+#[test]
+fn null_parameters() {
+    let rsa = algorithm(RSA_2048_DER_EXAMPLE);
+    assert_eq!(rsa, AlgorithmIdentifierRef::new_null(RSA_ENCRYPTION_OID));
+    assert!(rsa.has_null_or_absent_parameters());
+    assert!(rsa.assert_parameters_null().is_ok());
+
+    let ed25519 = algorithm(ED25519_DER_EXAMPLE);
+    assert!(ed25519.has_null_or_absent_parameters());
+    assert_eq!(
+        ed25519.assert_parameters_null(),
+        Err(Error::AlgorithmParametersMissing)
+    );
+
+    let ec = algorithm(EC_P256_DER_EXAMPLE);
+    assert!(!ec.has_null_or_absent_parameters());
+    assert!(ec.assert_parameters_null().is_err());
+}
+
+#[test]
+fn oid_parameters() {
+    let ec = algorithm(EC_P256_DER_EXAMPLE);
+    assert_eq!(
+        ec,
+        AlgorithmIdentifierRef::new_with_oid_param(EC_PUBLIC_KEY_OID, &P256_OID)
+    );
+    assert_eq!(ec.parameters_oid(), Ok(P256_OID));
+
+    let rsa = algorithm(RSA_2048_DER_EXAMPLE);
+    assert!(rsa.parameters_oid().is_err());
+    assert_eq!(rsa.parameters_any(), Ok(AnyRef::NULL));
+}
+
+#[test]
+fn eq_treating_null_as_absent() {
+    let null = AlgorithmIdentifierRef::new_null(RSA_ENCRYPTION_OID);
+    let absent = AlgorithmIdentifierRef {
+        oid: RSA_ENCRYPTION_OID,
+        parameters: None,
+    };
+
+    assert_ne!(null, absent);
+    assert!(null.eq_treating_null_as_absent(&absent));
+    assert!(absent.eq_treating_null_as_absent(&null));
+
+    let other_oid = AlgorithmIdentifierRef::new_null(EC_PUBLIC_KEY_OID);
+    assert!(!null.eq_treating_null_as_absent(&other_oid));
+
+    let ec = AlgorithmIdentifierRef::new_with_oid_param(RSA_ENCRYPTION_OID, &P256_OID);
+    assert!(!null.eq_treating_null_as_absent(&ec));
+    assert!(!absent.eq_treating_null_as_absent(&ec));
+    assert!(ec.eq_treating_null_as_absent(&ec));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn owned() {
+    let rsa = AlgorithmIdentifierOwned::new_null(RSA_ENCRYPTION_OID);
+    assert_eq!(rsa, algorithm(RSA_2048_DER_EXAMPLE).ref_to_owned());
+    assert!(rsa.has_null_or_absent_parameters());
+    assert!(rsa.assert_parameters_null().is_ok());
+    assert!(rsa.parameters_oid().is_err());
+
+    let ec = AlgorithmIdentifierOwned::new_with_oid_param(EC_PUBLIC_KEY_OID, P256_OID);
+    assert_eq!(
+        ec.to_der().unwrap(),
+        algorithm(EC_P256_DER_EXAMPLE).to_der().unwrap()
+    );
+    assert_eq!(ec.parameters_oid(), Ok(P256_OID));
+    assert!(!ec.has_null_or_absent_parameters());
+
+    let absent = AlgorithmIdentifierOwned {
+        oid: RSA_ENCRYPTION_OID,
+        parameters: None,
+    };
+    assert!(rsa.eq_treating_null_as_absent(&absent));
+    assert!(!ec.eq_treating_null_as_absent(&absent));
+}
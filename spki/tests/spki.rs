@@ -11,7 +11,10 @@ use {
 };
 
 #[cfg(feature = "pem")]
-use der::{pem::LineEnding, EncodePem};
+use {
+    der::{pem::LineEnding, EncodePem},
+    spki::{AnyPemError, PublicKeyPemForm, SubjectPublicKeyInfoOwned},
+};
 
 /// Elliptic Curve (P-256) `SubjectPublicKeyInfo` encoded as ASN.1 DER
 const EC_P256_DER_EXAMPLE: &[u8] = include_bytes!("examples/p256-pub.der");
@@ -35,10 +38,14 @@ const ED25519_PEM_EXAMPLE: &str = include_str!("examples/ed25519-pub.pem");
 #[cfg(feature = "pem")]
 const RSA_2048_PEM_EXAMPLE: &str = include_str!("examples/rsa2048-pub.pem");
 
+/// RSA-2048 PKCS#1 public key encoded as PEM
+#[cfg(feature = "pem")]
+const RSA_2048_PKCS1_PEM_EXAMPLE: &str = include_str!("examples/rsa2048-pub-pkcs1.pem");
+
 /// The SPKI fingerprint for `ED25519_SPKI_FINGERPRINT` as a Base64 string
 ///
 /// Generated using `cat ed25519-pub.der | openssl dgst -binary -sha256 | base64`
-#[cfg(all(feature = "alloc", feature = "base64", feature = "fingerprint"))]
+#[cfg(all(feature = "base64", feature = "fingerprint"))]
 const ED25519_SPKI_FINGERPRINT_BASE64: &str = "Vd1MdLDkhTTi9OFzzs61DfjyenrCqomRzHrpFOAwvO0=";
 
 /// The SPKI fingerprint for `ED25519_SPKI_FINGERPRINT` as straight hash bytes
@@ -106,6 +113,57 @@ fn decode_ed25519_and_fingerprint_base64() {
     );
 }
 
+#[test]
+#[cfg(all(feature = "base64", feature = "fingerprint"))]
+fn fingerprint_pins() {
+    // Generated using:
+    // `openssl pkey -pubin -inform der -in <file> -pubout -outform der | openssl dgst -sha256 -binary | base64`
+    for (der, pin) in [
+        (
+            EC_P256_DER_EXAMPLE,
+            "uZaNVu2NaqP7Q7FfoB41XXo6AgOxQIs/0nM2N8TRZCw=",
+        ),
+        (ED25519_DER_EXAMPLE, ED25519_SPKI_FINGERPRINT_BASE64),
+        (
+            RSA_2048_DER_EXAMPLE,
+            "7+2pv+rZ/QWU9qXPb99sFjEWo7H61tc86gUpW2j9F5Q=",
+        ),
+    ] {
+        let spki = SubjectPublicKeyInfoRef::try_from(der).unwrap();
+        assert!(spki.matches_fingerprint_base64(pin).unwrap());
+    }
+}
+
+#[test]
+#[cfg(feature = "fingerprint")]
+fn matches_fingerprint() {
+    let spki = SubjectPublicKeyInfoRef::try_from(ED25519_DER_EXAMPLE).unwrap();
+    let mut fingerprint = spki.fingerprint_bytes().unwrap();
+    assert!(spki.matches_fingerprint(&fingerprint).unwrap());
+
+    fingerprint[31] ^= 1;
+    assert!(!spki.matches_fingerprint(&fingerprint).unwrap());
+
+    let other = SubjectPublicKeyInfoRef::try_from(EC_P256_DER_EXAMPLE).unwrap();
+    assert!(!other
+        .matches_fingerprint(&spki.fingerprint_bytes().unwrap())
+        .unwrap());
+}
+
+#[test]
+#[cfg(all(feature = "base64", feature = "fingerprint"))]
+fn matches_fingerprint_base64_invalid_pin() {
+    let spki = SubjectPublicKeyInfoRef::try_from(ED25519_DER_EXAMPLE).unwrap();
+    assert!(!spki.matches_fingerprint_base64("").unwrap());
+    assert!(!spki.matches_fingerprint_base64("not base64!").unwrap());
+    assert!(!spki
+        .matches_fingerprint_base64("Vd1MdLDkhTTi9OFzzs61DQ==")
+        .unwrap());
+    assert!(!spki
+        .matches_fingerprint_base64("uZaNVu2NaqP7Q7FfoB41XXo6AgOxQIs/0nM2N8TRZCw=")
+        .unwrap());
+}
+
 #[test]
 fn decode_rsa_2048_der() {
     let spki = SubjectPublicKeyInfoRef::try_from(RSA_2048_DER_EXAMPLE).unwrap();
@@ -163,6 +221,49 @@ fn encode_rsa_2048_pem() {
     assert_eq!(RSA_2048_PEM_EXAMPLE, pk_encoded);
 }
 
+#[test]
+#[cfg(feature = "pem")]
+fn from_pem_any_spki() {
+    for (pem, der) in [
+        (EC_P256_PEM_EXAMPLE, EC_P256_DER_EXAMPLE),
+        (ED25519_PEM_EXAMPLE, ED25519_DER_EXAMPLE),
+        (RSA_2048_PEM_EXAMPLE, RSA_2048_DER_EXAMPLE),
+    ] {
+        let (pk, form) = SubjectPublicKeyInfoOwned::from_pem_any(pem).unwrap();
+        assert_eq!(form, PublicKeyPemForm::Spki);
+        assert_eq!(pk.to_der().unwrap(), der);
+    }
+}
+
+#[test]
+#[cfg(feature = "pem")]
+fn from_pem_any_pkcs1_rsa() {
+    let (pk, form) = SubjectPublicKeyInfoOwned::from_pem_any(RSA_2048_PKCS1_PEM_EXAMPLE).unwrap();
+    assert_eq!(form, PublicKeyPemForm::Pkcs1Rsa);
+    assert_eq!(pk.to_der().unwrap(), RSA_2048_DER_EXAMPLE);
+}
+
+#[test]
+#[cfg(feature = "pem")]
+fn from_pem_any_errors() {
+    let pem = RSA_2048_PEM_EXAMPLE.replace("PUBLIC KEY", "EC PUBLIC KEY");
+    let err = SubjectPublicKeyInfoOwned::from_pem_any(&pem).unwrap_err();
+    assert_eq!(
+        err,
+        AnyPemError::LabelUnknown {
+            label: "EC PUBLIC KEY".into()
+        }
+    );
+    assert!(err.to_string().contains("EC PUBLIC KEY"));
+
+    // a SPKI with the PKCS#1 label
+    let pem = RSA_2048_PEM_EXAMPLE.replace("PUBLIC KEY", "RSA PUBLIC KEY");
+    assert!(matches!(
+        SubjectPublicKeyInfoOwned::from_pem_any(&pem),
+        Err(AnyPemError::Spki(_))
+    ));
+}
+
 #[test]
 #[cfg(feature = "alloc")]
 fn build_hashset_of_digests() {
@@ -3,7 +3,7 @@
 use alloc::vec::Vec;
 use const_oid::db::{
     rfc3280::EMAIL_ADDRESS,
-    rfc4519::{COUNTRY_NAME, DOMAIN_COMPONENT, SERIAL_NUMBER},
+    rfc4519::{self, COUNTRY_NAME, DOMAIN_COMPONENT, SERIAL_NUMBER},
     Database, DB,
 };
 use core::{
@@ -92,6 +92,114 @@ pub struct AttributeTypeAndValue {
     pub value: AttributeValue,
 }
 
+/// Upper bounds on the length of attribute values, in characters, as defined in
+/// [RFC 5280 Appendix A.1].
+///
+/// [RFC 5280 Appendix A.1]: https://datatracker.ietf.org/doc/html/rfc5280#appendix-A.1
+mod ub {
+    pub const COMMON_NAME: usize = 64;
+    pub const LOCALITY_NAME: usize = 128;
+    pub const STATE_NAME: usize = 128;
+    pub const ORGANIZATION_NAME: usize = 64;
+    pub const ORGANIZATIONAL_UNIT_NAME: usize = 64;
+    pub const SERIAL_NUMBER: usize = 64;
+    pub const EMAIL_ADDRESS: usize = 255;
+}
+
+impl AttributeTypeAndValue {
+    /// Build a `commonName` (CN) attribute, encoded as a `UTF8String` of up to 64 characters.
+    pub fn new_common_name(value: &str) -> Result<Self, Error> {
+        Self::new_utf8(rfc4519::COMMON_NAME, value, ub::COMMON_NAME)
+    }
+
+    /// Build a `countryName` (C) attribute, encoded as a two-letter `PrintableString`.
+    pub fn new_country(value: &str) -> Result<Self, Error> {
+        if value.len() != 2 {
+            return Err(Tag::PrintableString.length_error());
+        }
+
+        Ok(Self {
+            oid: COUNTRY_NAME,
+            value: Any::encode_from(&PrintableStringRef::new(value)?)?,
+        })
+    }
+
+    /// Build a `stateOrProvinceName` (ST) attribute, encoded as a `UTF8String` of up to 128
+    /// characters.
+    pub fn new_state_or_province(value: &str) -> Result<Self, Error> {
+        Self::new_utf8(rfc4519::ST, value, ub::STATE_NAME)
+    }
+
+    /// Build a `localityName` (L) attribute, encoded as a `UTF8String` of up to 128 characters.
+    pub fn new_locality(value: &str) -> Result<Self, Error> {
+        Self::new_utf8(rfc4519::LOCALITY_NAME, value, ub::LOCALITY_NAME)
+    }
+
+    /// Build an `organizationName` (O) attribute, encoded as a `UTF8String` of up to 64
+    /// characters.
+    pub fn new_organization(value: &str) -> Result<Self, Error> {
+        Self::new_utf8(rfc4519::ORGANIZATION_NAME, value, ub::ORGANIZATION_NAME)
+    }
+
+    /// Build an `organizationalUnitName` (OU) attribute, encoded as a `UTF8String` of up to 64
+    /// characters.
+    pub fn new_organizational_unit(value: &str) -> Result<Self, Error> {
+        Self::new_utf8(
+            rfc4519::ORGANIZATIONAL_UNIT_NAME,
+            value,
+            ub::ORGANIZATIONAL_UNIT_NAME,
+        )
+    }
+
+    /// Build a `serialNumber` attribute, encoded as a `PrintableString` of up to 64 characters.
+    pub fn new_serial_number(value: &str) -> Result<Self, Error> {
+        check_len(Tag::PrintableString, value, ub::SERIAL_NUMBER)?;
+
+        Ok(Self {
+            oid: SERIAL_NUMBER,
+            value: Any::encode_from(&PrintableStringRef::new(value)?)?,
+        })
+    }
+
+    /// Build an `emailAddress` attribute, encoded as an `IA5String` of up to 255 characters.
+    pub fn new_email_address(value: &str) -> Result<Self, Error> {
+        check_len(Tag::Ia5String, value, ub::EMAIL_ADDRESS)?;
+
+        Ok(Self {
+            oid: EMAIL_ADDRESS,
+            value: Any::encode_from(&Ia5StringRef::new(value)?)?,
+        })
+    }
+
+    /// Build a `domainComponent` (DC) attribute, encoded as an `IA5String`.
+    pub fn new_domain_component(value: &str) -> Result<Self, Error> {
+        check_len(Tag::Ia5String, value, usize::MAX)?;
+
+        Ok(Self {
+            oid: DOMAIN_COMPONENT,
+            value: Any::encode_from(&Ia5StringRef::new(value)?)?,
+        })
+    }
+
+    fn new_utf8(oid: ObjectIdentifier, value: &str, max_len: usize) -> Result<Self, Error> {
+        check_len(Tag::Utf8String, value, max_len)?;
+
+        Ok(Self {
+            oid,
+            value: Any::encode_from(&Utf8StringRef::new(value)?)?,
+        })
+    }
+}
+
+/// Values are `SIZE (1..ub)`, with the upper bound counted in characters.
+fn check_len(tag: Tag, value: &str, max_len: usize) -> Result<(), Error> {
+    match value.chars().count() {
+        0 => Err(tag.length_error()),
+        len if len > max_len => Err(tag.length_error()),
+        _ => Ok(()),
+    }
+}
+
 #[derive(Copy, Clone)]
 enum Escape {
     None,
@@ -17,8 +17,10 @@ use crate::{
     AlgorithmIdentifier, SubjectPublicKeyInfo,
 };
 
+mod extensions;
 pub mod profile;
 
+pub use self::extensions::ExtensionsBuilder;
 use self::profile::BuilderProfile;
 
 #[deprecated(
@@ -64,6 +66,12 @@ pub enum Error {
 
     /// Not all required elements were specified
     MissingAttributes,
+
+    /// An extension MUST NOT be included more than once
+    DuplicateExtension {
+        /// Offending [`ObjectIdentifier`]
+        oid: ObjectIdentifier,
+    },
 }
 
 #[cfg(feature = "std")]
@@ -82,6 +90,7 @@ impl fmt::Display for Error {
             Error::NonUniqueATV => write!(f, "Each Name MUST NOT contain more than one instance of a given AttributeTypeAndValue"),
             Error::InvalidAttribute{oid} => write!(f, "Non-ordered attribute or invalid attribute found (oid={oid})"),
             Error::MissingAttributes => write!(f, "Not all required elements were specified"),
+            Error::DuplicateExtension{oid} => write!(f, "Extension included more than once (oid={oid})"),
         }
     }
 }
@@ -214,6 +223,30 @@ where
 
         Ok(())
     }
+
+    /// Add the extensions assembled by an [`ExtensionsBuilder`] to this certificate
+    ///
+    /// The criticality chosen in the [`ExtensionsBuilder`] is kept as-is. Returns
+    /// [`Error::DuplicateExtension`] if one of the extensions was already added.
+    pub fn add_extensions(&mut self, extensions: ExtensionsBuilder) -> Result<()> {
+        extend_extensions(&mut self.extensions, extensions)
+    }
+}
+
+/// Append the extensions of `builder` to `extensions`, rejecting duplicate `extnID`s.
+pub(crate) fn extend_extensions(
+    extensions: &mut Extensions,
+    builder: ExtensionsBuilder,
+) -> Result<()> {
+    for ext in builder.build() {
+        if extensions.iter().any(|e| e.extn_id == ext.extn_id) {
+            return Err(Error::DuplicateExtension { oid: ext.extn_id });
+        }
+
+        extensions.push(ext);
+    }
+
+    Ok(())
 }
 
 /// Trait for X509 builders
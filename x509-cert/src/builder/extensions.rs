@@ -0,0 +1,112 @@
+//! Extensions builder
+
+use alloc::vec::Vec;
+use der::asn1::OctetString;
+use spki::ObjectIdentifier;
+
+use crate::{
+    builder::{Error, Result},
+    ext::{AsExtension, Extension, Extensions},
+};
+
+/// Builder for a list of X.509v3 [`Extensions`] with explicit criticality.
+///
+/// Unlike [`AsExtension::to_extension`], which lets the extension decide on its criticality,
+/// every extension added through this builder is given an explicit `critical` flag.
+///
+/// Extensions are emitted in insertion order, and adding two extensions with the same
+/// `extnID` is rejected with [`Error::DuplicateExtension`] as required by
+/// [RFC 5280 Section 4.2].
+///
+/// ```
+/// use x509_cert::{
+///     builder::ExtensionsBuilder,
+///     ext::pkix::{BasicConstraints, KeyUsage, KeyUsages},
+/// };
+///
+/// let mut builder = ExtensionsBuilder::new();
+/// builder
+///     .add(&BasicConstraints { ca: true, path_len_constraint: None }, true)
+///     .unwrap()
+///     .add(&KeyUsage(KeyUsages::KeyCertSign.into()), true)
+///     .unwrap();
+///
+/// let extensions = builder.build();
+/// assert_eq!(extensions.len(), 2);
+/// ```
+///
+/// [RFC 5280 Section 4.2]: https://www.rfc-editor.org/rfc/rfc5280#section-4.2
+#[derive(Clone, Debug, Default)]
+pub struct ExtensionsBuilder {
+    extensions: Extensions,
+}
+
+impl ExtensionsBuilder {
+    /// Creates a new, empty, extensions builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a typed extension with the given criticality.
+    ///
+    /// The extension value is DER-encoded and wrapped in an OCTET STRING.
+    pub fn add<E: AsExtension>(&mut self, extension: &E, critical: bool) -> Result<&mut Self> {
+        let value = extension.to_der()?;
+        self.add_raw(E::OID, critical, &value)
+    }
+
+    /// Add an extension from its raw DER-encoded value with the given criticality.
+    ///
+    /// `value` is the DER encoding of the extension value itself, it will be wrapped in an
+    /// OCTET STRING.
+    pub fn add_raw(
+        &mut self,
+        oid: ObjectIdentifier,
+        critical: bool,
+        value: &[u8],
+    ) -> Result<&mut Self> {
+        self.push(Extension {
+            extn_id: oid,
+            critical,
+            extn_value: OctetString::new(value)?,
+        })
+    }
+
+    /// Add an already-assembled [`Extension`].
+    pub fn push(&mut self, extension: Extension) -> Result<&mut Self> {
+        if self.contains(&extension.extn_id) {
+            return Err(Error::DuplicateExtension {
+                oid: extension.extn_id,
+            });
+        }
+
+        self.extensions.push(extension);
+        Ok(self)
+    }
+
+    /// Is an extension with the given `extnID` already present?
+    pub fn contains(&self, oid: &ObjectIdentifier) -> bool {
+        self.extensions.iter().any(|ext| &ext.extn_id == oid)
+    }
+
+    /// Number of extensions added so far.
+    pub fn len(&self) -> usize {
+        self.extensions.len()
+    }
+
+    /// Returns `true` if no extension was added.
+    pub fn is_empty(&self) -> bool {
+        self.extensions.is_empty()
+    }
+
+    /// Return the assembled list of extensions.
+    pub fn build(self) -> Extensions {
+        self.extensions
+    }
+}
+
+impl From<ExtensionsBuilder> for Vec<Extension> {
+    fn from(builder: ExtensionsBuilder) -> Vec<Extension> {
+        builder.build()
+    }
+}
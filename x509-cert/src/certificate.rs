@@ -4,8 +4,17 @@ use crate::{ext, name::Name, serial_number::SerialNumber, time::Validity};
 use crate::{AlgorithmIdentifier, SubjectPublicKeyInfo};
 use alloc::vec::Vec;
 use const_oid::AssociatedOid;
-use core::{cmp::Ordering, fmt::Debug};
-use der::{asn1::BitString, Decode, Enumerated, ErrorKind, Sequence, Tag, ValueOrd};
+use core::{
+    cmp::Ordering,
+    fmt::Debug,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+use der::{
+    asn1::{BitString, Int},
+    Decode, DecodeValue, Encode, Enumerated, ErrorKind, Header, Length, Reader, Sequence, Tag,
+    ValueOrd, Writer,
+};
 
 #[cfg(feature = "pem")]
 use der::{
@@ -44,6 +53,16 @@ pub trait Profile: PartialEq + Debug + Eq + Clone + Copy + Default + 'static {
         time.rfc5280_adjust_utc_time()?;
         Ok(time)
     }
+
+    /// Decodes the `INTEGER` value of a serial number.
+    ///
+    /// By default, the value is required to be encoded in its minimal, DER-canonical form.
+    fn decode_serial_number_value<'a, R: Reader<'a>>(
+        reader: &mut R,
+        header: Header,
+    ) -> der::Result<Int> {
+        Int::decode_value(reader, header)
+    }
 }
 
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -356,6 +375,129 @@ impl<P: Profile> PemLabel for CertificateInner<P> {
     const PEM_LABEL: &'static str = "CERTIFICATE";
 }
 
+/// Serialized as a PEM string by human-readable formats, and as DER bytes otherwise.
+#[cfg(feature = "serde")]
+impl<P: Profile> serde::Serialize for CertificateInner<P> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::serde_support::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, P: Profile> serde::Deserialize<'de> for CertificateInner<P> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::serde_support::deserialize(deserializer)
+    }
+}
+
+/// [`Certificate`] along with its DER serialization.
+pub type CertificateBytes = CertificateBytesInner<Rfc5280>;
+
+/// [`CertificateInner`] along with its DER serialization.
+///
+/// The serialization is computed once, when the wrapper is built, and is then available as a
+/// byte slice through [`AsRef<[u8]>`][AsRef]. When decoded, the original input bytes are kept
+/// as-is. This makes it usable as a stable, byte-level identifier (e.g. as a key in a hash map),
+/// while still providing structural access to the certificate through [`Deref`].
+///
+/// The serialization is built eagerly rather than on the first call to [`AsRef::as_ref`]:
+/// encoding can fail, and doing it in [`TryFrom`] reports the error to the caller instead of
+/// panicking in `as_ref`. It also keeps the type `Sync` in `no_std` builds, where a lazily
+/// initialized cell would need a synchronization primitive. Wrap only the certificates whose
+/// bytes are needed.
+///
+/// ```
+/// use x509_cert::{certificate::CertificateBytes, der::Decode};
+/// # const CERT_DER: &[u8] = include_bytes!("../tests/examples/amazon.der");
+///
+/// let certificate = CertificateBytes::from_der(CERT_DER).expect("parse certificate");
+/// assert_eq!(certificate.as_ref(), CERT_DER);
+/// let _subject = certificate.tbs_certificate().subject();
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CertificateBytesInner<P: Profile = Rfc5280> {
+    certificate: CertificateInner<P>,
+    der: Vec<u8>,
+}
+
+impl<P: Profile> CertificateBytesInner<P> {
+    /// Get the DER serialization of the certificate.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.der
+    }
+
+    /// Borrow the inner [`CertificateInner`].
+    pub fn certificate(&self) -> &CertificateInner<P> {
+        &self.certificate
+    }
+
+    /// Return the inner [`CertificateInner`], dropping its serialization.
+    pub fn into_certificate(self) -> CertificateInner<P> {
+        self.certificate
+    }
+}
+
+impl<P: Profile> TryFrom<CertificateInner<P>> for CertificateBytesInner<P> {
+    type Error = der::Error;
+
+    fn try_from(certificate: CertificateInner<P>) -> der::Result<Self> {
+        let der = certificate.to_der()?;
+        Ok(Self { certificate, der })
+    }
+}
+
+impl<P: Profile> From<CertificateBytesInner<P>> for CertificateInner<P> {
+    fn from(certificate: CertificateBytesInner<P>) -> CertificateInner<P> {
+        certificate.into_certificate()
+    }
+}
+
+impl<P: Profile> AsRef<[u8]> for CertificateBytesInner<P> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<P: Profile> Deref for CertificateBytesInner<P> {
+    type Target = CertificateInner<P>;
+
+    fn deref(&self) -> &CertificateInner<P> {
+        &self.certificate
+    }
+}
+
+impl<P: Profile> Hash for CertificateBytesInner<P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.der.hash(state)
+    }
+}
+
+impl<'a, P: Profile> Decode<'a> for CertificateBytesInner<P> {
+    type Error = der::Error;
+
+    fn decode<R: Reader<'a>>(reader: &mut R) -> der::Result<Self> {
+        let header = Header::peek(reader)?;
+        let der = reader.read_vec((header.encoded_len()? + header.length)?)?;
+        let certificate = CertificateInner::from_der(&der)?;
+        Ok(Self { certificate, der })
+    }
+}
+
+impl<P: Profile> Encode for CertificateBytesInner<P> {
+    fn encoded_len(&self) -> der::Result<Length> {
+        self.der.len().try_into()
+    }
+
+    fn encode(&self, writer: &mut impl Writer) -> der::Result<()> {
+        writer.write(&self.der)
+    }
+}
+
+#[cfg(feature = "pem")]
+impl<P: Profile> PemLabel for CertificateBytesInner<P> {
+    const PEM_LABEL: &'static str = "CERTIFICATE";
+}
+
 /// `PkiPath` as defined by X.509 and referenced by [RFC 6066].
 ///
 /// This contains a series of certificates in validation order from the
@@ -0,0 +1,150 @@
+//! Certificate expiry reporting.
+//!
+//! Helpers to compute reporting-friendly information (days remaining, categorical status) about
+//! the [`Validity`] period of a certificate, e.g. for monitoring purposes.
+//!
+//! All computations are done on seconds since `UNIX_EPOCH`: X.509 times are expressed in UTC,
+//! and there is no DST or leap second to account for.
+//!
+//! ```
+//! # #[cfg(feature = "std")]
+//! # fn main() {
+//! use std::time::Duration;
+//! use x509_cert::{der::DecodePem, expiry::{ExpiryReport, ExpiryStatus}, Certificate};
+//! # const CERT_PEM: &str = include_str!("../tests/examples/amazon.pem");
+//!
+//! let certificate = Certificate::from_pem(CERT_PEM).expect("parse certificate");
+//! let validity = certificate.tbs_certificate().validity();
+//!
+//! // 2022-09-01T00:00:00Z
+//! let now = Duration::from_secs(1_661_990_400);
+//! let report = ExpiryReport::at(validity, now, Duration::from_secs(30 * 86400));
+//!
+//! assert_eq!(report.days_remaining, Some(18));
+//! assert!(matches!(report.status, ExpiryStatus::ExpiringWithin(_)));
+//! # }
+//! # #[cfg(not(feature = "std"))]
+//! # fn main() {}
+//! ```
+
+use core::time::Duration;
+
+#[cfg(feature = "std")]
+use std::time::SystemTime;
+
+use crate::{
+    certificate::Profile,
+    time::{Time, Validity},
+};
+
+/// Number of seconds in a day.
+const SECONDS_PER_DAY: i64 = 86400;
+
+/// Categorical expiry status of a certificate.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExpiryStatus {
+    /// The current time is before `notBefore`.
+    NotYetValid,
+
+    /// The certificate is valid, and does not expire within the requested threshold.
+    Valid,
+
+    /// The certificate is valid, but expires within the requested threshold.
+    ///
+    /// Contains the time remaining until `notAfter`.
+    ExpiringWithin(Duration),
+
+    /// The current time is after `notAfter`.
+    Expired,
+}
+
+/// Expiry report for a certificate [`Validity`], at a given point in time.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ExpiryReport {
+    /// Expiration date of the certificate (`notAfter`).
+    ///
+    /// `None` if the certificate has no well-defined expiration date (i.e. `notAfter` is
+    /// [`Time::INFINITY`]).
+    pub not_after: Option<Time>,
+
+    /// Number of whole days remaining until `notAfter`, negative once the certificate expired.
+    ///
+    /// The value is rounded towards negative infinity: a certificate expiring in less than a
+    /// day reports `0`, and a certificate which expired less than a day ago reports `-1`.
+    ///
+    /// `None` if the certificate has no well-defined expiration date.
+    pub days_remaining: Option<i64>,
+
+    /// Categorical status.
+    pub status: ExpiryStatus,
+}
+
+impl ExpiryReport {
+    /// Compute the expiry report of `validity` at `now` (expressed as a duration since
+    /// `UNIX_EPOCH`).
+    ///
+    /// Certificates expiring within `threshold` of `now` are reported as
+    /// [`ExpiryStatus::ExpiringWithin`].
+    pub fn at<P: Profile>(validity: &Validity<P>, now: Duration, threshold: Duration) -> Self {
+        let now = now.as_secs();
+        let not_before = validity.not_before.to_unix_duration().as_secs();
+
+        let not_after = if validity.not_after.is_infinity() {
+            None
+        } else {
+            Some(validity.not_after)
+        };
+
+        let not_after_secs = not_after.map(|t| t.to_unix_duration().as_secs());
+
+        let days_remaining = not_after_secs.map(|not_after| {
+            let remaining = i128::from(not_after) - i128::from(now);
+            // `remaining` is bounded by the range of `DateTime`, this can't overflow.
+            remaining.div_euclid(i128::from(SECONDS_PER_DAY)) as i64
+        });
+
+        // RFC 5280 Section 4.1.2.5: the validity period is inclusive of both bounds
+        let status = match not_after_secs {
+            _ if now < not_before => ExpiryStatus::NotYetValid,
+            Some(not_after) if now > not_after => ExpiryStatus::Expired,
+            Some(not_after) if not_after - now <= threshold.as_secs() => {
+                ExpiryStatus::ExpiringWithin(Duration::from_secs(not_after - now))
+            }
+            _ => ExpiryStatus::Valid,
+        };
+
+        Self {
+            not_after,
+            days_remaining,
+            status,
+        }
+    }
+
+    /// Compute the expiry report of `validity` at the current system time.
+    ///
+    /// See [`ExpiryReport::at`].
+    #[cfg(feature = "std")]
+    pub fn now<P: Profile>(validity: &Validity<P>, threshold: Duration) -> Self {
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+
+        Self::at(validity, now, threshold)
+    }
+
+    /// Expiration date as a [`chrono::DateTime`].
+    ///
+    /// `None` if the certificate has no well-defined expiration date.
+    #[cfg(feature = "chrono")]
+    pub fn not_after_chrono(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.not_after.and_then(|t| t.try_into().ok())
+    }
+
+    /// Expiration date as a [`time::OffsetDateTime`][::time::OffsetDateTime].
+    ///
+    /// `None` if the certificate has no well-defined expiration date.
+    #[cfg(feature = "time")]
+    pub fn not_after_time(&self) -> Option<::time::OffsetDateTime> {
+        self.not_after.and_then(|t| t.try_into().ok())
+    }
+}
@@ -0,0 +1,395 @@
+//! Permissive parsing of malformed real-world certificates.
+//!
+//! Certificates found in the wild regularly violate the DER or [RFC 5280] rules, and are
+//! rejected by [`Certificate::from_der`]. Inventory tooling may still need to read their fields,
+//! which is what [`Certificate::from_der_lenient`] is for. Strict parsing remains the default.
+//!
+//! The following relaxations are applied, each of them is reported with a [`ParseWarning`]
+//! when it was needed to parse a given certificate:
+//!
+//! - serial numbers longer than 20 octets are accepted ([`ParseWarning::SerialNumberTooLong`]),
+//! - serial numbers with a non-minimal `INTEGER` encoding are accepted
+//!   ([`ParseWarning::NonMinimalSerialNumber`]),
+//! - negative and zero serial numbers are reported ([`ParseWarning::NonPositiveSerialNumber`]),
+//! - attribute values of a name using a string type whose contents do not match its character
+//!   set are reported ([`ParseWarning::InvalidString`]),
+//! - relative distinguished names whose `SET OF` attributes are not in DER order are reported
+//!   ([`ParseWarning::UnsortedRdn`]),
+//! - names which are not DER-encoded for any other reason, e.g. a length which is not encoded
+//!   in the minimum number of octets, are reported ([`ParseWarning::NonCanonicalName`]).
+//!
+//! The input is decoded using [`EncodingRules::Ber`], so any relaxation supported by the `der`
+//! crate under those rules applies as well.
+//!
+//! When the `TBSCertificate` of the input does not re-encode to the same bytes (reported with
+//! [`ParseWarning::NonCanonicalTbsCertificate`]), the signature must be verified over
+//! [`LenientCertificate::tbs_certificate_der`] rather than over a re-encoding of the parsed
+//! certificate.
+//!
+//! [RFC 5280]: https://www.rfc-editor.org/rfc/rfc5280
+
+use alloc::vec::Vec;
+use core::{cmp::Ordering, fmt};
+use der::{
+    asn1::{Ia5StringRef, Int, PrintableStringRef, Utf8StringRef},
+    Decode, DerOrd, Encode, EncodingRules, Header, Length, Reader, SliceReader, Tag, Tagged,
+};
+use spki::ObjectIdentifier;
+
+use crate::{
+    attr::AttributeTypeAndValue,
+    certificate::{CertificateInner, Profile},
+    name::Name,
+    serial_number::SerialNumber,
+    time::Time,
+    Certificate,
+};
+
+/// Profile applying the relaxations listed in the [module documentation](self).
+///
+/// Prefer [`Certificate::from_der_lenient`], which also reports the relaxations that were
+/// needed to parse a given certificate.
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub struct Lenient;
+
+impl Profile for Lenient {
+    fn check_serial_number(_serial: &SerialNumber<Self>) -> der::Result<()> {
+        Ok(())
+    }
+
+    fn time_encoding(time: Time) -> der::Result<Time> {
+        Ok(time)
+    }
+
+    fn decode_serial_number_value<'a, R: Reader<'a>>(
+        reader: &mut R,
+        header: Header,
+    ) -> der::Result<Int> {
+        let mut bytes = reader.read_slice(header.length)?;
+
+        // Strip redundant zero and sign extension octets.
+        while let [first, second, ..] = bytes {
+            if (*first == 0x00 && *second < 0x80) || (*first == 0xFF && *second >= 0x80) {
+                bytes = &bytes[1..];
+            } else {
+                break;
+            }
+        }
+
+        Int::new(bytes)
+    }
+}
+
+/// Relaxation applied by [`Certificate::from_der_lenient`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ParseWarning {
+    /// The serial number is longer than the 20 octets permitted by RFC 5280.
+    SerialNumberTooLong {
+        /// Length of the serial number, in octets.
+        len: Length,
+    },
+
+    /// The serial number `INTEGER` has redundant leading octets.
+    NonMinimalSerialNumber,
+
+    /// The serial number is negative or zero.
+    NonPositiveSerialNumber,
+
+    /// An attribute value uses a string type whose character set it does not conform to.
+    InvalidString {
+        /// Name in which the attribute was found.
+        field: NameField,
+
+        /// Type of the attribute.
+        oid: ObjectIdentifier,
+
+        /// String type of the attribute value.
+        tag: Tag,
+    },
+
+    /// A relative distinguished name lists its attributes out of DER order.
+    UnsortedRdn {
+        /// Name in which the relative distinguished name was found.
+        field: NameField,
+    },
+
+    /// A name does not re-encode to the bytes it was parsed from, for reasons other than the
+    /// order of its relative distinguished names' attributes.
+    NonCanonicalName {
+        /// Name which is not DER-encoded.
+        field: NameField,
+    },
+
+    /// The `TBSCertificate` does not re-encode to the bytes it was parsed from.
+    NonCanonicalTbsCertificate,
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseWarning::SerialNumberTooLong { len } => {
+                write!(f, "serial number is too long ({len} octets)")
+            }
+            ParseWarning::NonMinimalSerialNumber => {
+                f.write_str("serial number is not minimally encoded")
+            }
+            ParseWarning::NonPositiveSerialNumber => {
+                f.write_str("serial number is negative or zero")
+            }
+            ParseWarning::InvalidString { field, oid, tag } => {
+                write!(f, "{field} attribute {oid} is not a valid {tag}")
+            }
+            ParseWarning::UnsortedRdn { field } => {
+                write!(
+                    f,
+                    "{field} has a relative distinguished name out of DER order"
+                )
+            }
+            ParseWarning::NonCanonicalName { field } => {
+                write!(f, "{field} is not DER-canonical")
+            }
+            ParseWarning::NonCanonicalTbsCertificate => {
+                f.write_str("TBSCertificate is not DER-canonical")
+            }
+        }
+    }
+}
+
+/// Name of a certificate a [`ParseWarning`] relates to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NameField {
+    /// Certificate issuer.
+    Issuer,
+
+    /// Certificate subject.
+    Subject,
+}
+
+impl fmt::Display for NameField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NameField::Issuer => f.write_str("issuer"),
+            NameField::Subject => f.write_str("subject"),
+        }
+    }
+}
+
+/// Result of [`Certificate::from_der_lenient`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LenientCertificate<'a> {
+    certificate: CertificateInner<Lenient>,
+    warnings: Vec<ParseWarning>,
+    tbs_certificate_der: &'a [u8],
+}
+
+impl<'a> LenientCertificate<'a> {
+    /// Parsed certificate.
+    pub fn certificate(&self) -> &CertificateInner<Lenient> {
+        &self.certificate
+    }
+
+    /// Relaxations which were needed to parse the certificate, empty if it is well-formed.
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
+    /// `TBSCertificate` exactly as found in the input, to verify the signature over.
+    pub fn tbs_certificate_der(&self) -> &'a [u8] {
+        self.tbs_certificate_der
+    }
+
+    /// Return the parsed certificate and the triggered relaxations.
+    pub fn into_parts(self) -> (CertificateInner<Lenient>, Vec<ParseWarning>) {
+        (self.certificate, self.warnings)
+    }
+}
+
+impl Certificate {
+    /// Parse a certificate, relaxing the checks listed in the [`lenient`](crate::lenient)
+    /// module documentation.
+    ///
+    /// The relaxations that were needed are reported alongside the certificate.
+    ///
+    /// ```
+    /// use x509_cert::Certificate;
+    /// # const CERT_DER: &[u8] = include_bytes!("../tests/examples/amazon.der");
+    ///
+    /// let parsed = Certificate::from_der_lenient(CERT_DER).expect("parse certificate");
+    /// assert!(parsed.warnings().is_empty());
+    /// ```
+    pub fn from_der_lenient(bytes: &[u8]) -> der::Result<LenientCertificate<'_>> {
+        let certificate = CertificateInner::<Lenient>::from_ber(bytes)?;
+        let raw = RawTbsCertificate::from_certificate_der(bytes)?;
+        let tbs = certificate.tbs_certificate();
+        let mut warnings = Vec::new();
+
+        let serial = tbs.serial_number();
+        let len = Length::try_from(serial.as_bytes().len())?;
+        if len > SerialNumber::<Lenient>::MAX_DECODE_LEN {
+            warnings.push(ParseWarning::SerialNumberTooLong { len });
+        }
+        if serial.to_der()? != raw.serial_number {
+            warnings.push(ParseWarning::NonMinimalSerialNumber);
+        }
+        let non_positive = match serial.as_bytes() {
+            [first, ..] if *first >= 0x80 => true,
+            bytes => bytes.iter().all(|byte| *byte == 0),
+        };
+        if non_positive {
+            warnings.push(ParseWarning::NonPositiveSerialNumber);
+        }
+
+        check_name(NameField::Issuer, tbs.issuer(), raw.issuer, &mut warnings)?;
+        check_name(
+            NameField::Subject,
+            tbs.subject(),
+            raw.subject,
+            &mut warnings,
+        )?;
+
+        if tbs.to_der()? != raw.tbs_certificate {
+            warnings.push(ParseWarning::NonCanonicalTbsCertificate);
+        }
+
+        Ok(LenientCertificate {
+            certificate,
+            warnings,
+            tbs_certificate_der: raw.tbs_certificate,
+        })
+    }
+}
+
+fn check_name(
+    field: NameField,
+    name: &Name,
+    raw: &[u8],
+    warnings: &mut Vec<ParseWarning>,
+) -> der::Result<()> {
+    for atv in name.iter() {
+        let valid = match atv.value.tag() {
+            Tag::PrintableString => PrintableStringRef::try_from(&atv.value).is_ok(),
+            Tag::Ia5String => Ia5StringRef::try_from(&atv.value).is_ok(),
+            Tag::Utf8String => Utf8StringRef::try_from(&atv.value).is_ok(),
+            _ => true,
+        };
+
+        if !valid {
+            warnings.push(ParseWarning::InvalidString {
+                field,
+                oid: atv.oid,
+                tag: atv.value.tag(),
+            });
+        }
+    }
+
+    // Decoding sorts the attributes of each relative distinguished name, so they are read
+    // again in the order in which they were encoded.
+    let rdns = decode_rdns(raw)?;
+
+    let unsorted = rdns.iter().any(|rdn| {
+        rdn.windows(2)
+            .any(|pair| pair[0].der_cmp(&pair[1]) != Ok(Ordering::Less))
+    });
+    if unsorted {
+        warnings.push(ParseWarning::UnsortedRdn { field });
+    }
+
+    if encode_rdns(&rdns)? != raw {
+        warnings.push(ParseWarning::NonCanonicalName { field });
+    }
+
+    Ok(())
+}
+
+/// Decode the attributes of each relative distinguished name of a `Name`, in encoding order.
+fn decode_rdns(raw: &[u8]) -> der::Result<Vec<Vec<AttributeTypeAndValue>>> {
+    let mut reader = SliceReader::new_with_encoding_rules(raw, EncodingRules::Ber)?;
+    let rdns = reader.sequence(|reader| -> der::Result<_> {
+        let mut rdns = Vec::new();
+
+        while !reader.is_finished() {
+            let header = Header::decode(reader)?;
+            header.tag.assert_eq(Tag::Set)?;
+
+            rdns.push(
+                reader.read_nested(header.length, |reader| -> der::Result<_> {
+                    let mut attributes = Vec::new();
+                    while !reader.is_finished() {
+                        attributes.push(AttributeTypeAndValue::decode(reader)?);
+                    }
+                    Ok(attributes)
+                })?,
+            );
+        }
+
+        Ok(rdns)
+    })?;
+
+    reader.finish(rdns)
+}
+
+/// DER-encode a `Name` keeping the attributes of each relative distinguished name in the given
+/// order.
+fn encode_rdns(rdns: &[Vec<AttributeTypeAndValue>]) -> der::Result<Vec<u8>> {
+    let mut body = Vec::new();
+
+    for rdn in rdns {
+        let mut attributes = Vec::new();
+        for attribute in rdn {
+            attributes.extend_from_slice(&attribute.to_der()?);
+        }
+
+        body.extend_from_slice(&Header::new(Tag::Set, attributes.len())?.to_der()?);
+        body.extend_from_slice(&attributes);
+    }
+
+    let mut name = Header::new(Tag::Sequence, body.len())?.to_der()?;
+    name.extend_from_slice(&body);
+    Ok(name)
+}
+
+/// Fields of the `TBSCertificate` as they appear in the input.
+struct RawTbsCertificate<'a> {
+    tbs_certificate: &'a [u8],
+    serial_number: &'a [u8],
+    issuer: &'a [u8],
+    subject: &'a [u8],
+}
+
+impl<'a> RawTbsCertificate<'a> {
+    fn from_certificate_der(bytes: &'a [u8]) -> der::Result<Self> {
+        let mut reader = SliceReader::new_with_encoding_rules(bytes, EncodingRules::Ber)?;
+        let tbs_certificate = reader.sequence(|reader| {
+            let tbs_certificate = reader.tlv_bytes()?;
+            reader.read_slice(reader.remaining_len())?;
+            Ok::<_, der::Error>(tbs_certificate)
+        })?;
+
+        let mut reader = SliceReader::new_with_encoding_rules(tbs_certificate, EncodingRules::Ber)?;
+        let fields = reader.sequence(|reader| {
+            let mut fields = Vec::new();
+            while !reader.is_finished() {
+                fields.push(reader.tlv_bytes()?);
+            }
+            Ok::<_, der::Error>(fields)
+        })?;
+
+        // Skip the optional `[0] EXPLICIT Version`.
+        let offset = match fields.first() {
+            Some([0xA0, ..]) => 1,
+            _ => 0,
+        };
+
+        match fields.get(offset..) {
+            Some([serial_number, _signature, issuer, _validity, subject, ..]) => Ok(Self {
+                tbs_certificate,
+                serial_number,
+                issuer,
+                subject,
+            }),
+            _ => Err(Tag::Sequence.value_error()),
+        }
+    }
+}
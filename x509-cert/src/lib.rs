@@ -27,7 +27,9 @@ pub mod anchor;
 pub mod attr;
 pub mod certificate;
 pub mod crl;
+pub mod expiry;
 pub mod ext;
+pub mod lenient;
 pub mod name;
 pub mod request;
 pub mod serial_number;
@@ -36,6 +38,9 @@ pub mod time;
 #[cfg(feature = "builder")]
 pub mod builder;
 
+#[cfg(feature = "serde")]
+mod serde_support;
+
 pub use certificate::{Certificate, PkiPath, TbsCertificate, Version};
 pub use der;
 pub use spki;
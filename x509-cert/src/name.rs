@@ -1,7 +1,7 @@
 //! Name-related definitions as defined in X.501 (and updated by RFC 5280).
 
 use crate::{attr::AttributeTypeAndValue, ext::pkix::name::DirectoryString};
-use alloc::vec::Vec;
+use alloc::{vec, vec::Vec};
 use const_oid::{
     db::{rfc3280, rfc4519},
     ObjectIdentifier,
@@ -85,6 +85,13 @@ impl Name {
     }
 }
 
+impl Name {
+    /// Start building a [`Name`] from its attributes, see [`NameBuilder`].
+    pub fn builder() -> NameBuilder {
+        NameBuilder::default()
+    }
+}
+
 impl From<Name> for RdnSequence {
     #[inline]
     fn from(value: Name) -> Self {
@@ -299,6 +306,132 @@ impl fmt::Display for Name {
     }
 }
 
+/// Builder for a [`Name`].
+///
+/// Each attribute is validated as it is added: its OID and string type are picked as
+/// recommended by [RFC 5280 Appendix A.1] (`PrintableString` for the country, `IA5String` for
+/// email addresses and domain components, `UTF8String` elsewhere) and the X.520 length limits
+/// are enforced.
+///
+/// Every attribute forms its own [`RelativeDistinguishedName`]. They are ordered
+/// conventionally, from the most general to the most specific: DC, C, ST, L, street, O, OU, CN,
+/// then any other attribute in the order it was added. Multi-valued RDNs and attributes with
+/// custom OIDs can be added with [`NameBuilder::rdn`] and [`NameBuilder::attribute`].
+///
+/// ```
+/// use x509_cert::name::Name;
+///
+/// let name = Name::builder()
+///     .common_name("example.com")?
+///     .organization("Example Corp")?
+///     .country("US")?
+///     .build();
+/// assert_eq!(name.to_string(), "CN=example.com,O=Example Corp,C=US");
+/// # Ok::<(), der::Error>(())
+/// ```
+///
+/// [RFC 5280 Appendix A.1]: https://datatracker.ietf.org/doc/html/rfc5280#appendix-A.1
+#[derive(Clone, Debug, Default)]
+pub struct NameBuilder {
+    rdns: Vec<RelativeDistinguishedName>,
+}
+
+impl NameBuilder {
+    /// Add a `commonName` (CN), see [`AttributeTypeAndValue::new_common_name`].
+    pub fn common_name(self, value: &str) -> der::Result<Self> {
+        self.attribute(AttributeTypeAndValue::new_common_name(value)?)
+    }
+
+    /// Add a `countryName` (C), see [`AttributeTypeAndValue::new_country`].
+    pub fn country(self, value: &str) -> der::Result<Self> {
+        self.attribute(AttributeTypeAndValue::new_country(value)?)
+    }
+
+    /// Add a `stateOrProvinceName` (ST), see [`AttributeTypeAndValue::new_state_or_province`].
+    pub fn state_or_province(self, value: &str) -> der::Result<Self> {
+        self.attribute(AttributeTypeAndValue::new_state_or_province(value)?)
+    }
+
+    /// Add a `localityName` (L), see [`AttributeTypeAndValue::new_locality`].
+    pub fn locality(self, value: &str) -> der::Result<Self> {
+        self.attribute(AttributeTypeAndValue::new_locality(value)?)
+    }
+
+    /// Add an `organizationName` (O), see [`AttributeTypeAndValue::new_organization`].
+    pub fn organization(self, value: &str) -> der::Result<Self> {
+        self.attribute(AttributeTypeAndValue::new_organization(value)?)
+    }
+
+    /// Add an `organizationalUnitName` (OU), see
+    /// [`AttributeTypeAndValue::new_organizational_unit`].
+    pub fn organizational_unit(self, value: &str) -> der::Result<Self> {
+        self.attribute(AttributeTypeAndValue::new_organizational_unit(value)?)
+    }
+
+    /// Add a `serialNumber`, see [`AttributeTypeAndValue::new_serial_number`].
+    pub fn serial_number(self, value: &str) -> der::Result<Self> {
+        self.attribute(AttributeTypeAndValue::new_serial_number(value)?)
+    }
+
+    /// Add an `emailAddress`, see [`AttributeTypeAndValue::new_email_address`].
+    pub fn email_address(self, value: &str) -> der::Result<Self> {
+        self.attribute(AttributeTypeAndValue::new_email_address(value)?)
+    }
+
+    /// Add a `domainComponent` (DC), see [`AttributeTypeAndValue::new_domain_component`].
+    ///
+    /// Domain components are kept in the order they were added, which should be from the
+    /// top-level domain down, e.g. `com` then `example`.
+    pub fn domain_component(self, value: &str) -> der::Result<Self> {
+        self.attribute(AttributeTypeAndValue::new_domain_component(value)?)
+    }
+
+    /// Add an arbitrary attribute, as its own [`RelativeDistinguishedName`].
+    ///
+    /// The value is used as-is, without any validation.
+    pub fn attribute(self, attribute: AttributeTypeAndValue) -> der::Result<Self> {
+        self.rdn(RelativeDistinguishedName::try_from(vec![attribute])?)
+    }
+
+    /// Add an arbitrary, possibly multi-valued, [`RelativeDistinguishedName`].
+    ///
+    /// It is ordered according to the most general of its attributes.
+    pub fn rdn(mut self, rdn: RelativeDistinguishedName) -> der::Result<Self> {
+        if rdn.is_empty() {
+            return Err(Tag::Set.length_error());
+        }
+
+        self.rdns.push(rdn);
+        Ok(self)
+    }
+
+    /// Build the [`Name`].
+    pub fn build(mut self) -> Name {
+        // Stable sort, preserving the order in which attributes of the same rank were added.
+        self.rdns.sort_by_key(|rdn| rdn.iter().map(rank).min());
+        Name(RdnSequence(self.rdns))
+    }
+}
+
+/// Conventional position of an attribute in a [`Name`] built by [`NameBuilder`].
+fn rank(atv: &AttributeTypeAndValue) -> usize {
+    const ORDER: &[ObjectIdentifier] = &[
+        rfc4519::DOMAIN_COMPONENT,
+        rfc4519::COUNTRY_NAME,
+        rfc4519::ST,
+        rfc4519::LOCALITY_NAME,
+        rfc4519::STREET,
+        rfc4519::ORGANIZATION_NAME,
+        rfc4519::ORGANIZATIONAL_UNIT_NAME,
+        rfc4519::COMMON_NAME,
+    ];
+
+    ORDER
+        .iter()
+        .position(|oid| *oid == atv.oid)
+        .unwrap_or(ORDER.len())
+}
+
 /// X.501 RDNSequence as defined in [RFC 5280 Section 4.1.2.4].
 ///
 /// ```text
@@ -93,6 +93,21 @@ impl PemLabel for CertReq {
     const PEM_LABEL: &'static str = "CERTIFICATE REQUEST";
 }
 
+/// Serialized as a PEM string by human-readable formats, and as DER bytes otherwise.
+#[cfg(feature = "serde")]
+impl serde::Serialize for CertReq {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::serde_support::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CertReq {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::serde_support::deserialize(deserializer)
+    }
+}
+
 impl<'a> TryFrom<&'a [u8]> for CertReq {
     type Error = der::Error;
 
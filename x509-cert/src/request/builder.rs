@@ -7,7 +7,7 @@ use spki::{
 };
 
 use crate::{
-    builder::{Builder, Error, Result, NULL_OID},
+    builder::{extend_extensions, Builder, Error, ExtensionsBuilder, Result, NULL_OID},
     ext::AsExtension,
     name::Name,
     request::{attributes::AsAttribute, CertReq, CertReqInfo, ExtensionReq},
@@ -88,6 +88,14 @@ impl RequestBuilder {
         Ok(())
     }
 
+    /// Add the extensions assembled by an [`ExtensionsBuilder`] to this certificate request
+    ///
+    /// The criticality chosen in the [`ExtensionsBuilder`] is kept as-is. Returns
+    /// [`Error::DuplicateExtension`] if one of the extensions was already added.
+    pub fn add_extensions(&mut self, extensions: ExtensionsBuilder) -> Result<()> {
+        extend_extensions(&mut self.extension_req.0, extensions)
+    }
+
     /// Add an attribute to this certificate request
     pub fn add_attribute<A: AsAttribute>(&mut self, attribute: &A) -> Result<()> {
         let attr = attribute.to_attribute()?;
@@ -0,0 +1,85 @@
+//! Support for serializing certificates and requests with `serde`.
+//!
+//! Human-readable formats (e.g. JSON, TOML) get the PEM encoding as a string, while binary
+//! formats (e.g. CBOR, bincode) get the DER encoding as a byte string.
+
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+use der::{pem::LineEnding, Decode, DecodePem, EncodePem};
+use serde::{de, ser, Deserializer, Serializer};
+
+/// Serialize `value` as PEM for human-readable formats, or as DER otherwise.
+pub(crate) fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: EncodePem,
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        let pem = value.to_pem(LineEnding::LF).map_err(ser::Error::custom)?;
+        serializer.serialize_str(&pem)
+    } else {
+        let der = value.to_der().map_err(ser::Error::custom)?;
+        serializer.serialize_bytes(&der)
+    }
+}
+
+/// Deserialize a value serialized with [`serialize`], fully parsing it.
+pub(crate) fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: DecodePem + for<'a> Decode<'a, Error = der::Error>,
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        let pem = deserializer.deserialize_str(PemVisitor)?;
+        T::from_pem(pem.as_bytes()).map_err(de::Error::custom)
+    } else {
+        let der = deserializer.deserialize_bytes(DerVisitor)?;
+        T::from_der(&der).map_err(de::Error::custom)
+    }
+}
+
+struct PemVisitor;
+
+impl de::Visitor<'_> for PemVisitor {
+    type Value = String;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a PEM-encoded string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<String, E> {
+        Ok(v.into())
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<String, E> {
+        Ok(v)
+    }
+}
+
+struct DerVisitor;
+
+impl<'de> de::Visitor<'de> for DerVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DER-encoded bytes")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Vec<u8>, E> {
+        Ok(v.into())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Vec<u8>, E> {
+        Ok(v)
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+
+        Ok(bytes)
+    }
+}
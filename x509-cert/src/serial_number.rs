@@ -133,7 +133,7 @@ impl<'a, P: Profile> DecodeValue<'a> for SerialNumber<P> {
     type Error = der::Error;
 
     fn decode_value<R: Reader<'a>>(reader: &mut R, header: Header) -> Result<Self> {
-        let inner = Int::decode_value(reader, header)?;
+        let inner = P::decode_serial_number_value(reader, header)?;
         let serial = Self {
             inner,
             _profile: PhantomData,
@@ -42,6 +42,16 @@ impl Time {
     pub const INFINITY: Time =
         Time::GeneralTime(GeneralizedTime::from_date_time(DateTime::INFINITY));
 
+    /// Is this the [`Time::INFINITY`] sentinel (`99991231235959Z`) used by certificates which
+    /// have no well-defined expiration date?
+    ///
+    /// See [RFC 5280 Section 4.1.2.5].
+    ///
+    /// [RFC 5280 Section 4.1.2.5]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.1.2.5
+    pub fn is_infinity(&self) -> bool {
+        self.to_date_time() == DateTime::INFINITY
+    }
+
     /// Get duration since `UNIX_EPOCH`.
     pub fn to_unix_duration(self) -> Duration {
         match self {
@@ -122,6 +132,26 @@ impl TryFrom<SystemTime> for Time {
     }
 }
 
+#[cfg(feature = "chrono")]
+impl TryFrom<Time> for chrono::DateTime<chrono::Utc> {
+    type Error = der::Error;
+
+    fn try_from(time: Time) -> der::Result<Self> {
+        let secs = i64::try_from(time.to_unix_duration().as_secs())
+            .map_err(|_| der::ErrorKind::DateTime)?;
+        Self::from_timestamp(secs, 0).ok_or_else(|| der::ErrorKind::DateTime.into())
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<Time> for ::time::OffsetDateTime {
+    type Error = der::Error;
+
+    fn try_from(time: Time) -> der::Result<Self> {
+        Ok(::time::PrimitiveDateTime::try_from(time.to_date_time())?.assume_utc())
+    }
+}
+
 /// X.501 `Validity` as defined in [RFC 5280 Section 4.1.2.5]
 ///
 /// ```text
@@ -13,10 +13,13 @@ use sha2::Sha256;
 use spki::SubjectPublicKeyInfo;
 use std::{str::FromStr, time::Duration};
 use x509_cert::{
-    builder::{profile, AsyncBuilder, Builder, CertificateBuilder, RequestBuilder},
+    builder::{
+        profile, AsyncBuilder, Builder, CertificateBuilder, Error, ExtensionsBuilder,
+        RequestBuilder,
+    },
     ext::pkix::{
         name::{DirectoryString, GeneralName},
-        SubjectAltName,
+        ExtendedKeyUsage, SubjectAltName,
     },
     name::Name,
     request,
@@ -262,6 +265,67 @@ fn ecdsa_signer() -> ecdsa::SigningKey<NistP256> {
     ecdsa::SigningKey::from(secret_key)
 }
 
+#[test]
+fn extensions_builder_criticality() {
+    use der::Decode;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    let san = SubjectAltName(vec![GeneralName::from(IpAddr::V4(Ipv4Addr::new(
+        192, 0, 2, 0,
+    )))]);
+    let eku = ExtendedKeyUsage(vec![const_oid::db::rfc5280::ID_KP_CODE_SIGNING]);
+
+    let mut extensions = ExtensionsBuilder::new();
+    extensions
+        .add(&san, true)
+        .unwrap()
+        .add(&eku, false)
+        .unwrap();
+
+    assert!(matches!(
+        extensions.add(&eku, true),
+        Err(Error::DuplicateExtension { .. })
+    ));
+
+    let serial_number = SerialNumber::from(42u32);
+    let validity = Validity::from_now(Duration::new(5, 0)).unwrap();
+    let subject =
+        Name::from_str("CN=World domination corporation,O=World domination Inc,C=US").unwrap();
+    let profile = profile::cabf::Root::new(false, subject).expect("create root profile");
+    let pub_key = SubjectPublicKeyInfo::try_from(PKCS8_PUBLIC_KEY_DER).expect("get ecdsa pub key");
+
+    let signer = ecdsa_signer();
+    let mut builder = CertificateBuilder::new(profile, serial_number, validity, pub_key)
+        .expect("Create certificate");
+    builder.add_extensions(extensions).unwrap();
+
+    let certificate = builder.build::<_, DerSignature>(&signer).unwrap();
+    let extensions = certificate
+        .tbs_certificate()
+        .extensions()
+        .expect("extensions");
+
+    let ext = &extensions[0];
+    assert!(ext.critical);
+    assert_eq!(
+        SubjectAltName::from_der(ext.extn_value.as_bytes()).unwrap(),
+        san
+    );
+
+    let ext = &extensions[1];
+    assert!(!ext.critical);
+    assert_eq!(
+        ExtendedKeyUsage::from_der(ext.extn_value.as_bytes()).unwrap(),
+        eku
+    );
+
+    let pem = certificate.to_pem(LineEnding::LF).expect("generate pem");
+    let output = openssl::check_certificate(pem.as_bytes());
+    assert!(output.contains("X509v3 Subject Alternative Name: critical"));
+    assert!(output.contains("X509v3 Extended Key Usage"));
+    assert!(!output.contains("X509v3 Extended Key Usage: critical"));
+}
+
 #[test]
 fn certificate_request() {
     use std::net::{IpAddr, Ipv4Addr};
@@ -433,6 +433,225 @@ fn decode_cert_overlength_serial_number() {
     assert_eq!(pem_encoded_cert, reencoded.as_bytes());
 }
 
+#[test]
+fn decode_cert_unique_ids() {
+    // rsa2048-crt.der with issuerUniqueID and subjectUniqueID spliced into the TBSCertificate
+    let der_encoded_cert = include_bytes!("examples/unique-ids.der");
+
+    let cert = Certificate::from_der(der_encoded_cert).unwrap();
+    let tbs = cert.tbs_certificate();
+    assert_eq!(tbs.version(), Version::V3);
+
+    let issuer_unique_id = tbs.issuer_unique_id().as_ref().unwrap();
+    assert_eq!(issuer_unique_id.unused_bits(), 0);
+    assert_eq!(issuer_unique_id.raw_bytes(), &hex!("0102030405060708"));
+
+    let subject_unique_id = tbs.subject_unique_id().as_ref().unwrap();
+    assert_eq!(subject_unique_id.unused_bits(), 4);
+    assert_eq!(subject_unique_id.raw_bytes(), &hex!("A1B2C3D4E0"));
+
+    assert!(tbs.extensions().is_some());
+
+    let reencoded = cert.to_der().unwrap();
+    assert_eq!(der_encoded_cert, reencoded.as_slice());
+}
+
+#[test]
+fn certificate_bytes() {
+    use std::collections::HashSet;
+    use x509_cert::certificate::CertificateBytes;
+
+    let der_encoded_cert = include_bytes!("examples/28903a635b5280fae6774c0b6da7d6baa64af2e8.der");
+
+    let cert_bytes = CertificateBytes::from_der(der_encoded_cert).unwrap();
+    assert_eq!(cert_bytes.as_ref(), der_encoded_cert);
+    assert_eq!(cert_bytes.to_der().unwrap(), der_encoded_cert);
+
+    let cert = Certificate::from_der(der_encoded_cert).unwrap();
+    assert_eq!(cert_bytes.tbs_certificate(), cert.tbs_certificate());
+
+    let from_cert = CertificateBytes::try_from(cert.clone()).unwrap();
+    assert_eq!(from_cert, cert_bytes);
+    assert_eq!(Certificate::from(from_cert), cert);
+
+    let set: HashSet<_> = [cert_bytes.clone(), cert_bytes].into_iter().collect();
+    assert_eq!(set.len(), 1);
+
+    #[cfg(feature = "pem")]
+    {
+        let pem_encoded_cert = include_bytes!("examples/amazon.pem");
+        let der_encoded_cert = include_bytes!("examples/amazon.der");
+        let cert_bytes = CertificateBytes::from_pem(pem_encoded_cert).unwrap();
+        assert_eq!(cert_bytes.as_ref(), der_encoded_cert);
+    }
+}
+
+/// Splice `replacement` in place of `range` of `rsa2048-crt.der`, fixing up the lengths of the
+/// enclosing `Certificate` and `TBSCertificate` SEQUENCEs.
+fn splice_rsa2048_crt(range: core::ops::Range<usize>, replacement: &[u8]) -> Vec<u8> {
+    let mut der = include_bytes!("examples/rsa2048-crt.der").to_vec();
+    let delta = replacement.len() as isize - range.len() as isize;
+    der.splice(range, replacement.iter().copied());
+
+    for offset in [2, 6] {
+        let len = u16::from_be_bytes([der[offset], der[offset + 1]]) as isize + delta;
+        der[offset..offset + 2].copy_from_slice(&(len as u16).to_be_bytes());
+    }
+
+    der
+}
+
+#[test]
+fn lenient_well_formed() {
+    let der_encoded_cert = include_bytes!("examples/amazon.der");
+
+    let cert = Certificate::from_der(der_encoded_cert).unwrap();
+    let lenient = Certificate::from_der_lenient(der_encoded_cert).unwrap();
+    assert!(lenient.warnings().is_empty());
+    assert_eq!(
+        lenient.tbs_certificate_der(),
+        cert.tbs_certificate().to_der().unwrap()
+    );
+    assert_eq!(lenient.certificate().to_der().unwrap(), der_encoded_cert);
+}
+
+#[test]
+fn lenient_negative_serial_number() {
+    use x509_cert::lenient::ParseWarning;
+
+    let der_encoded_cert = include_bytes!("examples/28903a635b5280fae6774c0b6da7d6baa64af2e8.der");
+
+    let lenient = Certificate::from_der_lenient(der_encoded_cert).unwrap();
+    assert_eq!(lenient.warnings(), &[ParseWarning::NonPositiveSerialNumber]);
+}
+
+#[cfg(feature = "pem")]
+#[test]
+fn lenient_overlength_serial_number() {
+    use x509_cert::lenient::ParseWarning;
+
+    let (_, der_encoded_cert) =
+        der::pem::decode_vec(include_bytes!("examples/qualcomm.pem")).unwrap();
+    assert!(Certificate::from_der(&der_encoded_cert).is_err());
+
+    let lenient = Certificate::from_der_lenient(&der_encoded_cert).unwrap();
+    assert_eq!(
+        lenient.warnings(),
+        &[ParseWarning::SerialNumberTooLong {
+            len: der::Length::new(22)
+        }]
+    );
+    assert_eq!(
+        lenient
+            .certificate()
+            .tbs_certificate()
+            .serial_number()
+            .as_bytes(),
+        &hex!("0084CE0BF6A0FE824EE5E506CAA89D78C61501625771")
+    );
+    assert_eq!(lenient.certificate().to_der().unwrap(), der_encoded_cert);
+}
+
+#[test]
+fn lenient_non_minimal_serial_number() {
+    use x509_cert::lenient::ParseWarning;
+
+    // INTEGER 00 A4332E8DE969E3BB, with a redundant leading zero
+    let der_encoded_cert = splice_rsa2048_crt(13..24, &hex!("020A0000A4332E8DE969E3BB"));
+    assert!(Certificate::from_der(&der_encoded_cert).is_err());
+
+    let lenient = Certificate::from_der_lenient(&der_encoded_cert).unwrap();
+    assert_eq!(
+        lenient.warnings(),
+        &[
+            ParseWarning::NonMinimalSerialNumber,
+            ParseWarning::NonCanonicalTbsCertificate
+        ]
+    );
+    assert_eq!(
+        lenient
+            .certificate()
+            .tbs_certificate()
+            .serial_number()
+            .as_bytes(),
+        &hex!("00A4332E8DE969E3BB")
+    );
+    assert_eq!(lenient.tbs_certificate_der(), &der_encoded_cert[4..653]);
+}
+
+#[test]
+fn lenient_invalid_string() {
+    use x509_cert::lenient::{NameField, ParseWarning};
+
+    // Issuer countryName PrintableString "U*"
+    let der_encoded_cert = splice_rsa2048_crt(137..141, &hex!("1302552A"));
+    assert!(Certificate::from_der(&der_encoded_cert).is_ok());
+
+    let lenient = Certificate::from_der_lenient(&der_encoded_cert).unwrap();
+    assert_eq!(
+        lenient.warnings(),
+        &[ParseWarning::InvalidString {
+            field: NameField::Issuer,
+            oid: const_oid::db::rfc4519::COUNTRY_NAME,
+            tag: Tag::PrintableString,
+        }]
+    );
+}
+
+#[test]
+fn lenient_unsorted_rdn() {
+    use x509_cert::lenient::{NameField, ParseWarning};
+
+    // Merge the issuer organizationName and countryName into a single relative distinguished
+    // name, listing them out of DER order.
+    let input = include_bytes!("examples/rsa2048-crt.der");
+    let mut rdn = hex!("311F").to_vec();
+    rdn.extend_from_slice(&input[108..128]);
+    rdn.extend_from_slice(&input[130..141]);
+    let mut der_encoded_cert = splice_rsa2048_crt(106..141, &rdn);
+    der_encoded_cert[40] -= 2;
+
+    let cert = Certificate::from_der(&der_encoded_cert).unwrap();
+    assert_eq!(cert.tbs_certificate().issuer().iter_rdn().count(), 4);
+
+    let lenient = Certificate::from_der_lenient(&der_encoded_cert).unwrap();
+    assert_eq!(
+        lenient.warnings(),
+        &[
+            ParseWarning::UnsortedRdn {
+                field: NameField::Issuer
+            },
+            ParseWarning::NonCanonicalTbsCertificate
+        ]
+    );
+}
+
+#[test]
+fn lenient_non_canonical_name() {
+    use x509_cert::lenient::{NameField, ParseWarning};
+
+    // Encode the length of the issuer countryName relative distinguished name in long form.
+    let mut der_encoded_cert = splice_rsa2048_crt(128..130, &hex!("31810B"));
+    der_encoded_cert[40] += 1;
+
+    assert!(Certificate::from_der(&der_encoded_cert).is_err());
+
+    let lenient = Certificate::from_der_lenient(&der_encoded_cert).unwrap();
+    assert_eq!(
+        lenient.warnings(),
+        &[
+            ParseWarning::NonCanonicalName {
+                field: NameField::Issuer
+            },
+            ParseWarning::NonCanonicalTbsCertificate
+        ]
+    );
+    assert_eq!(
+        lenient.warnings()[0].to_string(),
+        "issuer is not DER-canonical"
+    );
+}
+
 #[cfg(feature = "pem")]
 #[test]
 fn load_certificate_chains() {
@@ -0,0 +1,133 @@
+//! Expiry reporting tests
+
+use core::time::Duration;
+use der::{asn1::GeneralizedTime, DateTime, Decode};
+use x509_cert::{
+    expiry::{ExpiryReport, ExpiryStatus},
+    time::{Time, Validity},
+    Certificate,
+};
+
+const CERT_DER: &[u8] = include_bytes!("examples/amazon.der");
+
+/// notBefore: 2021-10-06T00:00:00Z
+const NOT_BEFORE: u64 = 1_633_478_400;
+
+/// notAfter: 2022-09-19T23:59:59Z
+const NOT_AFTER: u64 = 1_663_631_999;
+
+const DAY: u64 = 86400;
+
+const THRESHOLD: Duration = Duration::from_secs(30 * DAY);
+
+fn report_at(now: u64) -> ExpiryReport {
+    let certificate = Certificate::from_der(CERT_DER).unwrap();
+    ExpiryReport::at(
+        certificate.tbs_certificate().validity(),
+        Duration::from_secs(now),
+        THRESHOLD,
+    )
+}
+
+#[test]
+fn not_yet_valid() {
+    let report = report_at(NOT_BEFORE - 1);
+    assert_eq!(report.status, ExpiryStatus::NotYetValid);
+    assert_eq!(report.days_remaining, Some(349));
+    assert_eq!(
+        report.not_after.unwrap().to_unix_duration(),
+        Duration::from_secs(NOT_AFTER)
+    );
+}
+
+#[test]
+fn valid() {
+    let report = report_at(NOT_BEFORE);
+    assert_eq!(report.status, ExpiryStatus::Valid);
+
+    let report = report_at(NOT_AFTER - THRESHOLD.as_secs() - 1);
+    assert_eq!(report.status, ExpiryStatus::Valid);
+    assert_eq!(report.days_remaining, Some(30));
+}
+
+#[test]
+fn expiring() {
+    let report = report_at(NOT_AFTER - THRESHOLD.as_secs());
+    assert_eq!(report.status, ExpiryStatus::ExpiringWithin(THRESHOLD));
+    assert_eq!(report.days_remaining, Some(30));
+
+    let report = report_at(NOT_AFTER - 1);
+    assert_eq!(
+        report.status,
+        ExpiryStatus::ExpiringWithin(Duration::from_secs(1))
+    );
+    assert_eq!(report.days_remaining, Some(0));
+
+    // The validity period is inclusive of notAfter
+    let report = report_at(NOT_AFTER);
+    assert_eq!(report.status, ExpiryStatus::ExpiringWithin(Duration::ZERO));
+    assert_eq!(report.days_remaining, Some(0));
+}
+
+#[test]
+fn expired() {
+    let report = report_at(NOT_AFTER + 1);
+    assert_eq!(report.status, ExpiryStatus::Expired);
+    assert_eq!(report.days_remaining, Some(-1));
+
+    let report = report_at(NOT_AFTER + DAY);
+    assert_eq!(report.days_remaining, Some(-1));
+
+    let report = report_at(NOT_AFTER + DAY + 1);
+    assert_eq!(report.days_remaining, Some(-2));
+}
+
+#[test]
+fn no_expiry() {
+    let not_before = Time::GeneralTime(
+        GeneralizedTime::from_unix_duration(Duration::from_secs(NOT_BEFORE)).unwrap(),
+    );
+    assert!(!not_before.is_infinity());
+    assert!(Time::INFINITY.is_infinity());
+
+    // Same sentinel, encoded as UTCTime-incompatible GeneralizedTime by a third party
+    let infinity = Time::GeneralTime(GeneralizedTime::from_date_time(
+        DateTime::new(9999, 12, 31, 23, 59, 59).unwrap(),
+    ));
+    let validity = Validity::<x509_cert::certificate::Rfc5280>::new(not_before, infinity);
+
+    let report = ExpiryReport::at(&validity, Duration::from_secs(NOT_AFTER), THRESHOLD);
+    assert_eq!(report.status, ExpiryStatus::Valid);
+    assert_eq!(report.not_after, None);
+    assert_eq!(report.days_remaining, None);
+
+    let report = ExpiryReport::at(&validity, Duration::from_secs(NOT_BEFORE - 1), THRESHOLD);
+    assert_eq!(report.status, ExpiryStatus::NotYetValid);
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn chrono_not_after() {
+    use chrono::{TimeZone, Utc};
+
+    let report = report_at(NOT_BEFORE);
+    assert_eq!(
+        report.not_after_chrono(),
+        Some(Utc.with_ymd_and_hms(2022, 9, 19, 23, 59, 59).unwrap())
+    );
+
+    let infinity = chrono::DateTime::<Utc>::try_from(Time::INFINITY).unwrap();
+    assert_eq!(
+        infinity,
+        Utc.with_ymd_and_hms(9999, 12, 31, 23, 59, 59).unwrap()
+    );
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn time_not_after() {
+    let report = report_at(NOT_BEFORE);
+    let not_after = report.not_after_time().unwrap();
+    assert_eq!(not_after.unix_timestamp(), NOT_AFTER as i64);
+    assert_eq!(not_after.offset(), time::UtcOffset::UTC);
+}
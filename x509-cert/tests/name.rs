@@ -410,3 +410,106 @@ fn access_attributes() {
         "US"
     );
 }
+
+#[cfg(feature = "std")]
+#[test]
+fn build_name() {
+    use std::str::FromStr;
+
+    let name = Name::builder()
+        .common_name("www.example.org")
+        .unwrap()
+        .email_address("foo@example.org")
+        .unwrap()
+        .organization("Internet Corporation for Assigned Names and Numbers")
+        .unwrap()
+        .locality("Los Angeles")
+        .unwrap()
+        .country("US")
+        .unwrap()
+        .state_or_province("California")
+        .unwrap()
+        .build();
+
+    assert_eq!(
+        name,
+        Name::from_str("emailAddress=foo@example.org,CN=www.example.org,O=Internet Corporation for Assigned Names and Numbers,L=Los Angeles,ST=California,C=US").unwrap()
+    );
+    assert_eq!(name.country().unwrap().unwrap().as_str(), "US");
+
+    let tags: Vec<Tag> = name.iter().map(|atv| atv.value.tag()).collect();
+    assert_eq!(
+        tags,
+        [
+            Tag::PrintableString,
+            Tag::Utf8String,
+            Tag::Utf8String,
+            Tag::Utf8String,
+            Tag::Utf8String,
+            Tag::Ia5String
+        ]
+    );
+
+    let name = Name::builder()
+        .common_name("jsmith")
+        .unwrap()
+        .domain_component("net")
+        .unwrap()
+        .domain_component("example")
+        .unwrap()
+        .build();
+    assert_eq!(name.to_string(), "CN=jsmith,DC=example,DC=net");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn build_name_escape_hatch() {
+    use std::str::FromStr;
+
+    let custom = AttributeTypeAndValue {
+        oid: ObjectIdentifier::new_unwrap("1.3.6.1.4.1.1466.0"),
+        value: Any::from_der(&hex!("04024869")).unwrap(),
+    };
+    let rdn = RelativeDistinguishedName::try_from(vec![
+        AttributeTypeAndValue::new_common_name("J. Smith").unwrap(),
+        AttributeTypeAndValue::new_organizational_unit("Sales").unwrap(),
+    ])
+    .unwrap();
+
+    let name = Name::builder()
+        .attribute(custom)
+        .unwrap()
+        .rdn(rdn)
+        .unwrap()
+        .organization("Example")
+        .unwrap()
+        .build();
+
+    assert_eq!(
+        name,
+        Name::from_str("1.3.6.1.4.1.1466.0=#04024869,OU=Sales+CN=J. Smith,O=Example").unwrap()
+    );
+
+    assert!(Name::builder()
+        .rdn(RelativeDistinguishedName::default())
+        .is_err());
+}
+
+#[test]
+fn build_name_limits() {
+    assert!(AttributeTypeAndValue::new_country("USA").is_err());
+    assert!(AttributeTypeAndValue::new_country("U*").is_err());
+    assert!(AttributeTypeAndValue::new_common_name("").is_err());
+    assert!(AttributeTypeAndValue::new_email_address("jérôme@example.com").is_err());
+
+    // Limits are expressed in characters, not bytes.
+    let cn = "é".repeat(64);
+    assert!(AttributeTypeAndValue::new_common_name(&cn).is_ok());
+    assert!(AttributeTypeAndValue::new_common_name(&format!("{cn}e")).is_err());
+    assert!(AttributeTypeAndValue::new_locality(&"l".repeat(128)).is_ok());
+    assert!(AttributeTypeAndValue::new_locality(&"l".repeat(129)).is_err());
+    assert!(AttributeTypeAndValue::new_serial_number(&"1".repeat(65)).is_err());
+
+    let err = Name::builder().organization(&"o".repeat(65)).unwrap_err();
+    assert_eq!(err.kind(), Tag::Utf8String.length_error().kind());
+}
@@ -0,0 +1,88 @@
+//! serde tests
+
+#![cfg(feature = "serde")]
+
+use der::{DecodePem, Encode};
+use x509_cert::{request::CertReq, Certificate};
+
+const CERT_PEM: &str = include_str!("examples/amazon.pem");
+const CERT_DER: &[u8] = include_bytes!("examples/amazon.der");
+const CSR_PEM: &str = include_str!("examples/rsa2048-csr.pem");
+
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Config {
+    name: String,
+    certificate: Certificate,
+}
+
+fn certificate() -> Certificate {
+    Certificate::from_pem(CERT_PEM).expect("parse certificate")
+}
+
+#[test]
+fn json_round_trip() {
+    let certificate = certificate();
+
+    let json = serde_json::to_string(&certificate).unwrap();
+    let pem: String = serde_json::from_str(&json).unwrap();
+    assert!(pem.starts_with("-----BEGIN CERTIFICATE-----\n"));
+
+    let decoded: Certificate = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, certificate);
+}
+
+#[test]
+fn json_embedded_in_struct() {
+    let config = Config {
+        name: "amazon".into(),
+        certificate: certificate(),
+    };
+
+    let json = serde_json::to_string(&config).unwrap();
+    let decoded: Config = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, config);
+}
+
+#[test]
+fn json_tampered_pem() {
+    let json = serde_json::to_string(&certificate()).unwrap();
+
+    // Corrupt the outer SEQUENCE tag
+    assert!(json.contains("-----\\nMII"));
+    let tampered = json.replacen("-----\\nMII", "-----\\nNII", 1);
+
+    let err = serde_json::from_str::<Certificate>(&tampered).unwrap_err();
+    assert!(err.to_string().contains("tag"), "{err}");
+
+    let err = serde_json::from_str::<Certificate>("\"not a certificate\"").unwrap_err();
+    assert!(err.to_string().contains("PEM"), "{err}");
+}
+
+#[test]
+fn cbor_round_trip() {
+    let certificate = certificate();
+
+    let mut cbor = Vec::new();
+    ciborium::into_writer(&certificate, &mut cbor).unwrap();
+
+    let bytes: ciborium::Value = ciborium::from_reader(cbor.as_slice()).unwrap();
+    assert_eq!(bytes.as_bytes().unwrap().as_slice(), CERT_DER);
+
+    let decoded: Certificate = ciborium::from_reader(cbor.as_slice()).unwrap();
+    assert_eq!(decoded, certificate);
+    assert_eq!(decoded.to_der().unwrap(), CERT_DER);
+}
+
+#[test]
+fn csr_round_trip() {
+    let csr = CertReq::from_pem(CSR_PEM).expect("parse certificate request");
+
+    let json = serde_json::to_string(&csr).unwrap();
+    let decoded: CertReq = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, csr);
+
+    let mut cbor = Vec::new();
+    ciborium::into_writer(&csr, &mut cbor).unwrap();
+    let decoded: CertReq = ciborium::from_reader(cbor.as_slice()).unwrap();
+    assert_eq!(decoded, csr);
+}